@@ -222,6 +222,10 @@ impl pallet_vamm::Config for Runtime {
     type Integer = u128;
     type Moment = Moment;
     type TimeProvider = Timestamp;
+    type MaxObservations = ConstU32<64>;
+    type MaxSwapsPerBatch = ConstU32<16>;
+    type MaxCircuitBreakers = ConstU32<16>;
+    type WeightInfo = ();
 }
 
 // -------------------------------------------------------------------------------------------------
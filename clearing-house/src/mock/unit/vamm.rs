@@ -18,7 +18,10 @@ pub mod pallet {
         ArithmeticError, FixedPointNumber,
     };
     use sp_std::ops::Add;
-    use traits::vamm::{AssetType, Direction, SwapConfig, SwapOutput, Vamm};
+    use traits::vamm::{
+        AssetType, CircuitBreakerConfig, Depth, Direction, Reserves, SwapConfig,
+        SwapExactOutputConfig, SwapOutput, Vamm,
+    };
 
     // ----------------------------------------------------------------------------------------------------
     //                                    Declaration Of The Pallet Type
@@ -156,6 +159,11 @@ pub mod pallet {
     #[pallet::getter(fn _next_twap_of)]
     pub type NextTwaps<T: Config> = StorageMap<_, Twox64Concat, T::VammId, T::Decimal>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn _twap_over)]
+    pub type TwapsOver<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, T::VammId, Twox64Concat, u64, T::Decimal>;
+
     // ----------------------------------------------------------------------------------------------------
     //                                           Trait Implementations
     // ----------------------------------------------------------------------------------------------------
@@ -167,6 +175,7 @@ pub mod pallet {
         type Moment = T::Moment;
         type MovePriceConfig = MovePriceConfig;
         type SwapConfig = SwapConfig<Self::VammId, Self::Balance>;
+        type SwapExactOutputConfig = SwapExactOutputConfig<Self::VammId, Self::Balance>;
         type VammConfig = VammConfig;
         type VammId = T::VammId;
 
@@ -197,6 +206,16 @@ pub mod pallet {
                 .ok_or_else(|| Error::<T>::FailedToCalculateTwap.into())
         }
 
+        fn get_twap_over(
+            vamm_id: Self::VammId,
+            window: Self::Moment,
+        ) -> Result<Self::Decimal, DispatchError> {
+            match Self::_twap_over(vamm_id, window.into()) {
+                Some(twap) => Ok(twap),
+                None => <Self as Vamm>::get_twap(vamm_id, AssetType::Base),
+            }
+        }
+
         fn swap(config: &Self::SwapConfig) -> Result<SwapOutputOf<T>, DispatchError> {
             let negative = config.direction == Direction::Remove;
 
@@ -245,10 +264,56 @@ pub mod pallet {
             Ok(swap_output)
         }
 
+        fn swap_batch(configs: &[Self::SwapConfig]) -> Result<Vec<SwapOutputOf<T>>, DispatchError> {
+            configs.iter().map(<Self as Vamm>::swap).collect()
+        }
+
+        fn swap_exact_output(
+            config: &Self::SwapExactOutputConfig,
+        ) -> Result<SwapOutputOf<T>, DispatchError> {
+            <Self as Vamm>::swap(&Self::SwapConfig {
+                vamm_id: config.vamm_id,
+                asset: config.asset,
+                input_amount: config.output_amount,
+                direction: Direction::Remove,
+                output_amount_limit: config.input_amount_limit,
+            })
+        }
+
+        fn swap_exact_output_simulation(
+            config: &Self::SwapExactOutputConfig,
+        ) -> Result<SwapOutputOf<T>, DispatchError> {
+            <Self as Vamm>::swap_simulation(&Self::SwapConfig {
+                vamm_id: config.vamm_id,
+                asset: config.asset,
+                input_amount: config.output_amount,
+                direction: Direction::Remove,
+                output_amount_limit: config.input_amount_limit,
+            })
+        }
+
         fn move_price(config: &Self::MovePriceConfig) -> Result<U256, DispatchError> {
             unimplemented!()
         }
 
+        fn get_price_impact(config: &Self::SwapConfig) -> Result<Self::Decimal, DispatchError> {
+            unimplemented!()
+        }
+
+        fn get_depth(
+            vamm_id: Self::VammId,
+            price_change: Self::Decimal,
+        ) -> Result<Depth<Self::Balance>, DispatchError> {
+            unimplemented!()
+        }
+
+        fn get_reserves_for_price(
+            vamm_id: Self::VammId,
+            target_price: Self::Decimal,
+        ) -> Result<Reserves<Self::Balance>, DispatchError> {
+            unimplemented!()
+        }
+
         fn update_twap(
             vamm_id: Self::VammId,
             base_twap: Option<Self::Decimal>,
@@ -275,6 +340,21 @@ pub mod pallet {
         fn close(vamm_id: T::VammId, closing_time: T::Moment) -> Result<(), DispatchError> {
             Ok(())
         }
+
+        fn set_circuit_breaker(
+            vamm_id: Self::VammId,
+            config: Option<CircuitBreakerConfig<Self::Decimal, Self::Moment>>,
+        ) -> Result<(), DispatchError> {
+            unimplemented!()
+        }
+
+        fn swap_weight() -> Weight {
+            0
+        }
+
+        fn swap_batch_weight(swaps: u32) -> Weight {
+            0
+        }
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -318,6 +398,12 @@ pub mod pallet {
             });
         }
 
+        pub fn set_twap_over(vamm_id: &T::VammId, window: T::Moment, twap: Option<T::Decimal>) {
+            TwapsOver::<T>::mutate_exists(vamm_id, window.into(), |t| {
+                *t = twap;
+            });
+        }
+
         pub fn set_next_twap_of(vamm_id: &T::VammId, twap: Option<T::Decimal>) {
             NextTwaps::<T>::mutate_exists(vamm_id, |t| {
                 *t = twap;
@@ -33,7 +33,10 @@
 )]
 
 pub use crate::weights::WeightInfo;
+mod math;
+mod snapshots;
 mod types;
+mod volatility;
 mod weights;
 
 #[allow(unused_imports)]
@@ -56,7 +59,7 @@ pub mod pallet {
     // ----------------------------------------------------------------------------------------------------
     //		Imports and Dependencies
     // ----------------------------------------------------------------------------------------------------
-    use crate::{types::*, weights::*};
+    use crate::{math, types::*, weights::*};
 
     use codec::Codec;
     use composable_support::validation::Validated;
@@ -140,6 +143,29 @@ pub mod pallet {
         /// Protocol Origin that can create vaults and options.
         type ProtocolOrigin: EnsureOrigin<Self::Origin>;
 
+        /// Origin that can update the implied volatility surfaces, e.g. governance or an oracle.
+        type VolatilityOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Maximum number of moneyness and time to expiry points of a volatility surface.
+        #[pallet::constant]
+        type MaxSurfacePoints: Get<u32>;
+
+        /// Maximum number of price samples kept for each asset to compute its realized volatility.
+        #[pallet::constant]
+        type MaxPriceSamples: Get<u32>;
+
+        /// Minimum time between two price samples of the same asset.
+        #[pallet::constant]
+        type PriceSamplingPeriod: Get<MomentOf<Self>>;
+
+        /// How long snapshots are kept in the history before being pruned.
+        #[pallet::constant]
+        type SnapshotRetentionPeriod: Get<MomentOf<Self>>;
+
+        /// Maximum weight used each block to copy the latest snapshots into the history.
+        #[pallet::constant]
+        type MaxSnapshotWeight: Get<Weight>;
+
         /// Used for option tokens and other assets management.
         type Assets: Transfer<AccountIdOf<Self>, Balance = BalanceOf<Self>, AssetId = AssetIdOf<Self>>
             + Mutate<AccountIdOf<Self>, Balance = BalanceOf<Self>, AssetId = AssetIdOf<Self>>
@@ -160,6 +186,8 @@ pub mod pallet {
     pub type OptionIdOf<T> = AssetIdOf<T>;
     pub type BlackScholesParamsOf<T> = BlackScholesParams<AssetIdOf<T>, BalanceOf<T>, MomentOf<T>>;
     pub type Decimal = FixedI128;
+    pub type PriceHistoryOf<T> =
+        BoundedVec<(MomentOf<T>, BalanceOf<T>), <T as Config>::MaxPriceSamples>;
 
     // ----------------------------------------------------------------------------------------------------
     //		Storage
@@ -179,6 +207,10 @@ pub mod pallet {
     #[allow(clippy::disallowed_types)]
     pub type LatestSnapshotTimestamp<T: Config> = StorageValue<_, MomentOf<T>, OptionQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn pending_snapshot_round)]
+    pub type PendingSnapshotRound<T: Config> = StorageValue<_, SnapshotRound<T>, OptionQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn latest_snapshot)]
     pub type LatestSnapshots<T: Config> =
@@ -195,13 +227,38 @@ pub mod pallet {
         Snapshot<T>,
     >;
 
+    #[pallet::storage]
+    #[pallet::getter(fn volatility_surface)]
+    pub type VolatilitySurfaces<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, VolatilitySurface<T>>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn price_history)]
+    pub type PriceHistory<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, PriceHistoryOf<T>, ValueQuery>;
+
     // ----------------------------------------------------------------------------------------------------
     //		Events
     // ----------------------------------------------------------------------------------------------------
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        InterestRateUpdated { interest_rate: Decimal },
+        InterestRateUpdated {
+            interest_rate: Decimal,
+        },
+
+        VolatilitySurfaceUpdated {
+            asset_id: AssetIdOf<T>,
+            surface: Option<VolatilitySurface<T>>,
+        },
+
+        SnapshotFrequencyUpdated {
+            snapshot_frequency: MomentOf<T>,
+        },
+
+        SnapshotTaken {
+            timestamp: MomentOf<T>,
+        },
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -214,6 +271,8 @@ pub mod pallet {
         InterestRateNotSet,
 
         LatestSnapshotTimestampNotSet,
+
+        InvalidVolatilitySurface,
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -222,35 +281,7 @@ pub mod pallet {
     #[pallet::hooks]
     impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
         fn on_initialize(_n: T::BlockNumber) -> Weight {
-            let mut used_weight = 0;
-            let now = T::Time::now();
-
-            let latest_snapshot_timestamp = match Self::latest_snapshot_timestamp() {
-                Some(timestamp) => timestamp,
-                None => MomentOf::<T>::from(0_u32),
-            };
-
-            used_weight = used_weight.saturating_add(T::DbWeight::get().reads(1));
-
-            let snapshot_frequency = match Self::snapshot_frequency() {
-                Some(timestamp) => timestamp,
-                None => MomentOf::<T>::from(86400_u32), // Default if not set
-            };
-
-            used_weight = used_weight.saturating_add(T::DbWeight::get().reads(1));
-
-            let new_snapshot_timestamp =
-                latest_snapshot_timestamp.saturating_add(snapshot_frequency);
-
-            if new_snapshot_timestamp > now {
-                LatestSnapshots::<T>::iter().for_each(|(option_id, snapshot)| {
-                    SnapshotsHistory::<T>::insert(option_id, new_snapshot_timestamp, snapshot);
-                    used_weight = used_weight.saturating_add(T::DbWeight::get().writes(1));
-                });
-            }
-
-            let max_weight = <T as frame_system::Config>::BlockWeights::get().max_block;
-            used_weight.min(max_weight)
+            Self::take_snapshots(T::Time::now())
         }
     }
 
@@ -312,6 +343,8 @@ pub mod pallet {
             // Check if it's protocol to call the extrinsic
             T::ProtocolOrigin::ensure_origin(origin)?;
 
+            <Self as OptionsPricing>::calculate_option_greeks(option_id, params)?;
+
             Ok(())
         }
 
@@ -323,9 +356,48 @@ pub mod pallet {
             // Check if it's protocol to call the extrinsic
             T::ProtocolOrigin::ensure_origin(origin)?;
 
+            Self::do_update_interest_rate(interest_rate)?;
+
+            Ok(())
+        }
+
+        /// Set or remove the implied volatility surface of an asset.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: Type of origin for the extrinsic, must be `VolatilityOrigin`.
+        /// - `asset_id`: Asset whose volatility surface is updated.
+        /// - `surface`: New volatility surface of the asset, or `None` to remove it and fall back
+        ///   to the realized volatility.
+        ///
+        /// ## Emits
+        /// - [`Event::VolatilitySurfaceUpdated`]
+        ///
+        /// ## Errors
+        /// - [`Error::InvalidVolatilitySurface`]: the axes of the surface are empty or not strictly
+        ///   increasing, its dimensions don't match or a volatility is not positive.
+        #[pallet::weight(<T as Config>::WeightInfo::update_volatility_surface())]
+        pub fn update_volatility_surface(
+            origin: OriginFor<T>,
+            asset_id: AssetIdOf<T>,
+            surface: Option<VolatilitySurface<T>>,
+        ) -> DispatchResult {
+            T::VolatilityOrigin::ensure_origin(origin)?;
+
+            Self::do_update_volatility_surface(asset_id, surface)?;
+
             Ok(())
         }
 
+        /// Set how often the latest snapshots are copied into the history.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: Type of origin for the extrinsic, must be `ProtocolOrigin`.
+        /// - `snapshot_frequency`: Minimum time between two snapshots, in milliseconds.
+        ///
+        /// ## Emits
+        /// - [`Event::SnapshotFrequencyUpdated`]
         #[pallet::weight(<T as Config>::WeightInfo::update_snapshot_frequency())]
         pub fn update_snapshot_frequency(
             origin: OriginFor<T>,
@@ -334,6 +406,8 @@ pub mod pallet {
             // Check if it's protocol to call the extrinsic
             T::ProtocolOrigin::ensure_origin(origin)?;
 
+            Self::do_update_snapshot_frequency(snapshot_frequency)?;
+
             Ok(())
         }
     }
@@ -359,9 +433,13 @@ pub mod pallet {
         fn calculate_option_greeks(
             option_id: OptionIdOf<T>,
             params: BlackScholesParamsOf<T>,
-        ) -> Result<(), DispatchError> {
+        ) -> Result<Greeks, DispatchError> {
             Self::do_calculate_option_greeks(option_id, params)
         }
+
+        fn get_option_greeks(params: BlackScholesParamsOf<T>) -> Result<Greeks, DispatchError> {
+            Ok(Self::calculate_snapshot(params)?.greeks)
+        }
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -373,6 +451,8 @@ pub mod pallet {
                 *v = interest_rate;
             });
 
+            Self::deposit_event(Event::InterestRateUpdated { interest_rate });
+
             Ok(())
         }
 
@@ -383,6 +463,25 @@ pub mod pallet {
                 *v = Some(snapshot_frequency);
             });
 
+            Self::deposit_event(Event::SnapshotFrequencyUpdated { snapshot_frequency });
+
+            Ok(())
+        }
+
+        fn do_update_volatility_surface(
+            asset_id: AssetIdOf<T>,
+            surface: Option<VolatilitySurface<T>>,
+        ) -> Result<(), DispatchError> {
+            match surface {
+                Some(ref surface) => {
+                    Self::validate_volatility_surface(surface)?;
+                    VolatilitySurfaces::<T>::insert(asset_id, surface);
+                },
+                None => VolatilitySurfaces::<T>::remove(asset_id),
+            }
+
+            Self::deposit_event(Event::VolatilitySurfaceUpdated { asset_id, surface });
+
             Ok(())
         }
 
@@ -390,38 +489,104 @@ pub mod pallet {
             option_id: OptionIdOf<T>,
             params: BlackScholesParamsOf<T>,
         ) -> Result<BalanceOf<T>, DispatchError> {
-            // // Get interest rate index, annualized expiry date and converted prices
-            // let interest_rate = Self::interest_rate();
-            // let time_annualized = Self::get_expiry_time_annualized(params.expiring_date)?;
-            // let strike_price = convert(params.base_asset_strike_price);
-            // let spot_price = convert(params.base_asset_spot_price);
+            Self::record_price_sample(params.base_asset_id, params.base_asset_spot_price);
+
+            let snapshot = Self::calculate_snapshot(params)?;
+            let option_price = snapshot.option_price;
+
+            LatestSnapshots::<T>::insert(option_id, snapshot);
 
-            // // Get volatility for option's asset
-            // let iv: Decimal = Decimal::from_float(200.5); // TODO
+            Ok(option_price)
+        }
+
+        fn do_calculate_option_greeks(
+            option_id: OptionIdOf<T>,
+            params: BlackScholesParamsOf<T>,
+        ) -> Result<Greeks, DispatchError> {
+            Self::record_price_sample(params.base_asset_id, params.base_asset_spot_price);
 
-            // // Calculate price with BS formula
-            // let option_price = Self::black_scholes(
-            // 	strike_price,
-            // 	spot_price,
-            // 	time_annualized,
-            // 	interest_rate,
-            // 	iv,
-            // 	params.option_type,
-            // )?;
+            let snapshot = Self::calculate_snapshot(params)?;
+            let greeks = snapshot.greeks;
 
-            // Ok(option_price)
+            LatestSnapshots::<T>::insert(option_id, snapshot);
 
-            Ok((1000u128 * 10u128.pow(12)).into())
+            Ok(greeks)
         }
 
-        fn black_scholes(
+        /// Prices the option described by `params` and computes its greeks.
+        ///
+        /// Black-Scholes is homogeneous in the spot and strike prices, so the model is evaluated
+        /// for a strike of one and the resulting price is scaled back by the strike. This keeps
+        /// the computation independent of the decimals of the balances.
+        pub(crate) fn calculate_snapshot(
+            params: BlackScholesParamsOf<T>,
+        ) -> Result<Snapshot<T>, DispatchError> {
+            // Get interest rate index, annualized expiry date and normalized prices
+            let interest_rate = Self::interest_rate();
+            let time_annualized = Self::get_expiry_time_annualized(params.expiring_date)?;
+            let strike_price = Decimal::one();
+            let spot_price = Decimal::checked_from_rational(
+                params.base_asset_spot_price,
+                params.base_asset_strike_price,
+            )
+            .ok_or(Error::<T>::FailedConversion)?;
+
+            // Get volatility for option's asset, strike and expiry
+            let moneyness = Decimal::checked_from_rational(
+                params.base_asset_strike_price,
+                params.base_asset_spot_price,
+            )
+            .ok_or(Error::<T>::FailedConversion)?;
+            let iv = Self::get_volatility(params.base_asset_id, moneyness, time_annualized)?;
+
+            let option_price = Self::black_scholes(
+                strike_price,
+                spot_price,
+                time_annualized,
+                interest_rate,
+                iv,
+                params.option_type,
+            )?
+            .checked_mul_int(params.base_asset_strike_price)
+            .ok_or(ArithmeticError::Overflow)?;
+
+            let greeks = Self::calculate_greeks(
+                strike_price,
+                spot_price,
+                time_annualized,
+                interest_rate,
+                iv,
+                params.option_type,
+            )?;
+
+            Ok(Snapshot {
+                interest_rate,
+                iv,
+                greeks,
+                option_price,
+                asset_spot_price: params.base_asset_spot_price,
+                total_issuance_buyer: params.total_issuance_buyer,
+                total_premium_paid: params.total_premium_paid,
+            })
+        }
+
+        pub(crate) fn black_scholes(
             strike_price: Decimal,
             spot_price: Decimal,
             time_annualized: Decimal,
             interest_rate: Decimal,
             iv: Decimal,
             option_type: OptionType,
-        ) -> Result<BalanceOf<T>, DispatchError> {
+        ) -> Result<Decimal, DispatchError> {
+            // At expiry the option is only worth its intrinsic value
+            if time_annualized.is_zero() {
+                let intrinsic_value = match option_type {
+                    OptionType::Call => spot_price.saturating_sub(strike_price),
+                    OptionType::Put => strike_price.saturating_sub(spot_price),
+                };
+                return Ok(intrinsic_value.max(Decimal::zero()))
+            }
+
             // Calculate d1 and d2
             let (d1, d2) = Self::calculate_d1_d2(
                 strike_price,
@@ -451,27 +616,53 @@ pub mod pallet {
                 )?,
             };
 
-            // Ok(option_price)
-            Ok((1000u128 * 10u128.pow(12)).into()) // To make tests pass right now
+            // Rounding can make deep out of the money options slightly negative
+            Ok(option_price.max(Decimal::zero()))
         }
 
-        fn get_expiry_time_annualized(expiry_date: MomentOf<T>) -> Result<Decimal, Error<T>> {
-            // let now = T::Time::now();
-            // let seconds_to_expiry = convert(expiry_date - now);
-            // seconds_to_expiry
-            // 	.checked_div(&SECONDS_PER_YEAR)
-            // 	.ok_or(Error::<T>::FailedConversion)
-            Ok(1.into())
+        /// Returns the time left until `expiry_date` in years, or zero if the date has passed.
+        /// Timestamps are expected in milliseconds.
+        pub(crate) fn get_expiry_time_annualized(
+            expiry_date: MomentOf<T>,
+        ) -> Result<Decimal, Error<T>> {
+            let now = T::Time::now();
+            if expiry_date <= now {
+                return Ok(Decimal::zero())
+            }
+
+            Self::annualize(expiry_date.saturating_sub(now))
         }
 
-        fn normal_cumulative_distribution_function(
+        /// Converts a duration in milliseconds to years.
+        pub(crate) fn annualize(duration: MomentOf<T>) -> Result<Decimal, Error<T>> {
+            let milliseconds: u128 = duration
+                .try_into()
+                .map_err(|_| Error::<T>::FailedConversion)?;
+
+            Decimal::checked_from_rational(milliseconds, MILLISECONDS_PER_YEAR)
+                .ok_or(Error::<T>::FailedConversion)
+        }
+
+        pub(crate) fn normal_cumulative_distribution_function(
             value: Decimal,
         ) -> Result<Decimal, DispatchError> {
-            Ok(1.into())
+            Ok(math::normal_cdf(value)?)
         }
 
-        fn normal_probability_density_function(value: Decimal) -> Result<Decimal, DispatchError> {
-            Ok(1.into())
+        pub(crate) fn normal_probability_density_function(
+            value: Decimal,
+        ) -> Result<Decimal, DispatchError> {
+            Ok(math::normal_pdf(value)?)
+        }
+
+        fn discount_factor(
+            time_annualized: Decimal,
+            interest_rate: Decimal,
+        ) -> Result<Decimal, DispatchError> {
+            let exp = interest_rate
+                .checked_mul(&time_annualized)
+                .ok_or(ArithmeticError::Overflow)?;
+            Ok(math::exp(-exp)?)
         }
 
         fn calculate_d1_d2(
@@ -481,25 +672,31 @@ pub mod pallet {
             interest_rate: Decimal,
             iv: Decimal,
         ) -> Result<(Decimal, Decimal), DispatchError> {
-            // let a = Decimal::sqrt(time_annualized).ok_or(ArithmeticError::Underflow)?;
-            // let a = iv.checked_mul(&a).ok_or(ArithmeticError::Overflow)?;
-
-            // let b = spot_price.checked_div(&strike_price).ok_or(ArithmeticError::Overflow)?;
-            // let b = Decimal::log(b).ok_or(ArithmeticError::Underflow)?;
-
-            // let c = iv.saturating_pow(2);
-            // let c = c.checked_div(&Decimal::from_inner(2.into())).
-            // ok_or(ArithmeticError::Underflow)?; let c = c.checked_add(&
-            // interest_rate).ok_or(ArithmeticError::Overflow)?; let c = c.checked_mul(&
-            // time_annualized).ok_or(ArithmeticError::Overflow)?;
-
-            // let d1 = b.checked_add(&c).ok_or(ArithmeticError::Overflow)?;
-            // let d1 = d1.checked_div(&a).ok_or(ArithmeticError::Overflow)?;
-
-            // let d2 = d1.checked_sub(&a).ok_or(ArithmeticError::Underflow)?;
-
-            // Ok((d1, d2))
-            Ok((Decimal::from_inner(1.into()), Decimal::from_inner(1.into())))
+            let a = math::sqrt(time_annualized)?;
+            let a = iv.checked_mul(&a).ok_or(ArithmeticError::Overflow)?;
+
+            let b = spot_price
+                .checked_div(&strike_price)
+                .ok_or(ArithmeticError::DivisionByZero)?;
+            let b = math::ln(b)?;
+
+            let c = iv.checked_mul(&iv).ok_or(ArithmeticError::Overflow)?;
+            let c = c
+                .checked_div(&Decimal::saturating_from_integer(2))
+                .ok_or(ArithmeticError::DivisionByZero)?;
+            let c = c
+                .checked_add(&interest_rate)
+                .ok_or(ArithmeticError::Overflow)?;
+            let c = c
+                .checked_mul(&time_annualized)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            let d1 = b.checked_add(&c).ok_or(ArithmeticError::Overflow)?;
+            let d1 = d1.checked_div(&a).ok_or(ArithmeticError::DivisionByZero)?;
+
+            let d2 = d1.checked_sub(&a).ok_or(ArithmeticError::Underflow)?;
+
+            Ok((d1, d2))
         }
 
         fn calculate_call_price(
@@ -510,18 +707,19 @@ pub mod pallet {
             d1: Decimal,
             d2: Decimal,
         ) -> Result<Decimal, DispatchError> {
-            // let nd1 = Self::cumulative_normal_distribution(d1)?;
-            // let a = spot_price.checked_mul(&nd1).ok_or(ArithmeticError::Overflow)?;
-
-            // let nd2 = Self::cumulative_normal_distribution(d2)?;
-            // let exp =
-            // 	-interest_rate.checked_mul(&time_annualized).ok_or(ArithmeticError::Overflow)?;
-            // let exp = Decimal::exp(&exp).ok_or(ArithmeticError::Overflow)?;
-            // let b = strike_price.checked_mul(&nd2).ok_or(ArithmeticError::Overflow)?;
-            // let b = b.checked_mul(&exp).ok_or(ArithmeticError::Overflow)?;
-
-            // Ok(a.checked_sub(&b))
-            Ok(1.into())
+            let nd1 = Self::normal_cumulative_distribution_function(d1)?;
+            let a = spot_price
+                .checked_mul(&nd1)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            let nd2 = Self::normal_cumulative_distribution_function(d2)?;
+            let exp = Self::discount_factor(time_annualized, interest_rate)?;
+            let b = strike_price
+                .checked_mul(&nd2)
+                .ok_or(ArithmeticError::Overflow)?;
+            let b = b.checked_mul(&exp).ok_or(ArithmeticError::Overflow)?;
+
+            Ok(a.checked_sub(&b).ok_or(ArithmeticError::Underflow)?)
         }
 
         fn calculate_put_price(
@@ -532,68 +730,102 @@ pub mod pallet {
             d1: Decimal,
             d2: Decimal,
         ) -> Result<Decimal, DispatchError> {
-            // let nd2 = Self::cumulative_normal_distribution(-d2)?;
-            // let exp =
-            // 	-interest_rate.checked_mul(&time_annualized).ok_or(ArithmeticError::Overflow)?;
-            // let exp = Decimal::exp(&exp).ok_or(ArithmeticError::Overflow)?;
-            // let b = strike_price.checked_mul(&nd2).ok_or(ArithmeticError::Overflow)?;
-            // let b = b.checked_mul(&exp).ok_or(ArithmeticError::Overflow)?;
+            let nd2 = Self::normal_cumulative_distribution_function(-d2)?;
+            let exp = Self::discount_factor(time_annualized, interest_rate)?;
+            let b = strike_price
+                .checked_mul(&nd2)
+                .ok_or(ArithmeticError::Overflow)?;
+            let b = b.checked_mul(&exp).ok_or(ArithmeticError::Overflow)?;
+
+            let nd1 = Self::normal_cumulative_distribution_function(-d1)?;
+            let a = spot_price
+                .checked_mul(&nd1)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            Ok(b.checked_sub(&a).ok_or(ArithmeticError::Underflow)?)
+        }
+
+        /// Computes delta, gamma, vega, theta and rho for the given option type.
+        ///
+        /// The greeks are computed for the normalized prices and then converted to the units of
+        /// [`Greeks`], where gamma is expressed for a 1% change of the spot price and vega, theta
+        /// and rho as a fraction of the spot price.
+        fn calculate_greeks(
+            strike_price: Decimal,
+            spot_price: Decimal,
+            time_annualized: Decimal,
+            interest_rate: Decimal,
+            iv: Decimal,
+            option_type: OptionType,
+        ) -> Result<Greeks, DispatchError> {
+            // At expiry only delta is meaningful, being one for options in the money
+            if time_annualized.is_zero() {
+                let delta = match option_type {
+                    OptionType::Call if spot_price > strike_price => Decimal::one(),
+                    OptionType::Put if spot_price < strike_price => -Decimal::one(),
+                    _ => Decimal::zero(),
+                };
+                return Ok(Greeks {
+                    delta,
+                    ..Default::default()
+                })
+            }
 
-            // let nd1 = Self::cumulative_normal_distribution(-d1)?;
-            // let a = spot_price.checked_mul(&nd1).ok_or(ArithmeticError::Overflow)?;
+            let (d1, d2) = Self::calculate_d1_d2(
+                strike_price,
+                spot_price,
+                time_annualized,
+                interest_rate,
+                iv,
+            )?;
 
-            // Ok(b.checked_sub(&a))
-            Ok(1.into())
-        }
+            let (delta_call, delta_put) = Self::calculate_delta(d1)?;
+            let gamma = Self::calculate_gamma(spot_price, time_annualized, iv, d1)?;
+            let vega = Self::calculate_vega(spot_price, time_annualized, d1)?;
+            let (theta_call, theta_put) = Self::calculate_theta(
+                strike_price,
+                spot_price,
+                time_annualized,
+                interest_rate,
+                iv,
+                d1,
+                d2,
+            )?;
+            let (rho_call, rho_put) =
+                Self::calculate_rho(strike_price, time_annualized, interest_rate, d2)?;
 
-        fn do_calculate_option_greeks(
-            option_id: OptionIdOf<T>,
-            params: BlackScholesParamsOf<T>,
-        ) -> Result<(), DispatchError> {
-            // // Get interest rate index, annualized expiry date and converted prices
-            // let interest_rate = Self::interest_rate();
-            // let time_annualized = Self::get_expiry_time_annualized(params.expiring_date)?;
-            // let strike_price =
-            // T::ConvertBalanceToDecimal::convert(params.base_asset_strike_price);
-            // let spot_price = T::ConvertBalanceToDecimal::convert(params.base_asset_spot_price);
-
-            // // Get volatility for option's asset
-            // let iv: Decimal = Decimal::from_float(200.5); // TODO
-
-            // let (d1, d2) = Self::calculate_d1_d2(
-            // 	strike_price,
-            // 	spot_price,
-            // 	time_annualized,
-            // 	interest_rate,
-            // 	iv,
-            // )?;
-
-            // let (delta_call, delta_put) = Self::calculate_delta(d1)?;
-            // let gamma = Self::calculate_gamma(spot_price, time_annualized, iv, d1)?;
-            // let vega = Self::calculate_vega(spot_price, time_annualized, d1)?;
-            // let (theta_call, theta_put) = Self::calculate_theta(
-            // 	strike_price,
-            // 	spot_price,
-            // 	time_annualized,
-            // 	interest_rate,
-            // 	iv,
-            // 	d1,
-            // 	d2,
-            // )?;
-            // let (rho_call, rho_put) =
-            // 	Self::calculate_rho(strike_price, time_annualized, interest_rate, d2)?;
+            let (delta, theta, rho) = match option_type {
+                OptionType::Call => (delta_call, theta_call, rho_call),
+                OptionType::Put => (delta_put, theta_put, rho_put),
+            };
 
-            Ok(())
+            let relative_to_spot = |greek: Decimal| {
+                greek
+                    .checked_div(&spot_price)
+                    .ok_or(ArithmeticError::DivisionByZero)
+            };
+            let gamma = gamma
+                .checked_mul(&spot_price)
+                .ok_or(ArithmeticError::Overflow)?
+                .checked_div(&Decimal::saturating_from_integer(100))
+                .ok_or(ArithmeticError::DivisionByZero)?;
+
+            Ok(Greeks {
+                delta,
+                gamma,
+                vega: relative_to_spot(vega)?,
+                theta: relative_to_spot(theta)?,
+                rho: relative_to_spot(rho)?,
+            })
         }
 
         fn calculate_delta(d1: Decimal) -> Result<(Decimal, Decimal), DispatchError> {
-            // ncdf should have mean=0 and std=1
-            // let delta_call = Self::normal_cumulative_distribution_function(d1)?;
+            let delta_call = Self::normal_cumulative_distribution_function(d1)?;
+            let delta_put = delta_call
+                .checked_sub(&Decimal::one())
+                .ok_or(ArithmeticError::Underflow)?;
 
-            // For delta_put we need to subtract 1 UNIT but we have not defined a number model yet
-            // Alternative is -ncdf(-d1)
-            // Ok((delta_call, delta_call - Decimal::from_inner(1.into())))
-            Ok((1.into(), 1.into()))
+            Ok((delta_call, delta_put))
         }
 
         fn calculate_gamma(
@@ -602,15 +834,19 @@ pub mod pallet {
             iv: Decimal,
             d1: Decimal,
         ) -> Result<Decimal, DispatchError> {
-            // let pdf = Self::normal_probability_density_function(d1)?;
-            // let sqrt_time = Decimal::sqrt(time_annualized).ok_or(ArithmeticError::Underflow)?;
-            // let norm_factor = spot_price.checked_mul(&iv).ok_or(ArithmeticError::Overflow)?;
-            // let norm_factor =
-            // norm_factor.checked_mul(&sqrt_time).ok_or(ArithmeticError::Overflow)?;
-            // let gamma = pdf.checked_div(&norm_factor).ok_or(ArithmeticError::Underflow)?;
-
-            // Ok(gamma)
-            Ok(1.into())
+            let pdf = Self::normal_probability_density_function(d1)?;
+            let sqrt_time = math::sqrt(time_annualized)?;
+            let norm_factor = spot_price
+                .checked_mul(&iv)
+                .ok_or(ArithmeticError::Overflow)?;
+            let norm_factor = norm_factor
+                .checked_mul(&sqrt_time)
+                .ok_or(ArithmeticError::Overflow)?;
+            let gamma = pdf
+                .checked_div(&norm_factor)
+                .ok_or(ArithmeticError::DivisionByZero)?;
+
+            Ok(gamma)
         }
 
         fn calculate_vega(
@@ -618,17 +854,19 @@ pub mod pallet {
             time_annualized: Decimal,
             d1: Decimal,
         ) -> Result<Decimal, DispatchError> {
-            // let pdf = Self::normal_probability_density_function(d1)?;
-            // let sqrt_time = Decimal::sqrt(time_annualized).ok_or(ArithmeticError::Underflow)?;
-            // let norm_factor =
-            // spot_price.checked_div(&Decimal::from_inner(100.into()))).
-            // ok_or(ArithmeticError::Overflow)?; let vega =
-            // pdf.checked_mul(&norm_factor).ok_or(ArithmeticError::Overflow)?; let vega
-            // = vega.checked_mul(&sqrt_time).ok_or(ArithmeticError::Overflow)?;
-
-            // Ok(vega)
-
-            Ok(1.into())
+            let pdf = Self::normal_probability_density_function(d1)?;
+            let sqrt_time = math::sqrt(time_annualized)?;
+            let norm_factor = spot_price
+                .checked_div(&Decimal::saturating_from_integer(100))
+                .ok_or(ArithmeticError::DivisionByZero)?;
+            let vega = pdf
+                .checked_mul(&norm_factor)
+                .ok_or(ArithmeticError::Overflow)?;
+            let vega = vega
+                .checked_mul(&sqrt_time)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            Ok(vega)
         }
 
         fn calculate_theta(
@@ -640,30 +878,36 @@ pub mod pallet {
             d1: Decimal,
             d2: Decimal,
         ) -> Result<(Decimal, Decimal), DispatchError> {
-            // let pdf = Self::normal_probability_density_function(d1)?;
-            // let cdf_call = Self::normal_cumulative_distribution_function(d2)?;
-            // let cdf_put = Self::normal_cumulative_distribution_function(-d2)?;
-            // let sqrt_t = Decimal::sqrt(time_annualized).ok_or(ArithmeticError::Underflow)?;
-            // let exp =
-            // 	-interest_rate.checked_mul(&time_annualized).ok_or(ArithmeticError::Overflow)?;
-            // let exp = Decimal::exp(&exp).ok_or(ArithmeticError::Overflow)?;
-
-            // let a = spot_price.checked_mul(&cdf_call).ok_or(ArithmeticError::Overflow)?;
-            // let a = a.checked_mul(&iv).ok_or(ArithmeticError::Overflow)?;
-            // let a = a.checked_div(&sqrt_t).ok_or(ArithmeticError::Underflow)?;
-            // let a = a.checked_div(2.into()).ok_or(ArithmeticError::Underflow)?;
-
-            // let b = strike_price.checked_mul(&interest_rate).ok_or(ArithmeticError::Overflow)?;
-            // let b = b.checked_mul(&interest_rate).ok_or(ArithmeticError::Overflow)?;
-            // let b = b.checked_mul(&exp).ok_or(ArithmeticError::Overflow)?;
-            // let b_call = b.checked_mul(&cdf_call).ok_or(ArithmeticError::Overflow)?;
-            // let b_put = b.checked_mul(&cdf_put).ok_or(ArithmeticError::Overflow)?;
-
-            // let theta_call = -a.checked_sub(&b_call).ok_or(ArithmeticError::Overflow)?;
-            // let theta_put = b_put.checked_sub(&a).ok_or(ArithmeticError::Overflow)?;
-
-            // Ok((theta_call, theta_put))
-            Ok((1.into(), 1.into()))
+            let pdf = Self::normal_probability_density_function(d1)?;
+            let cdf_call = Self::normal_cumulative_distribution_function(d2)?;
+            let cdf_put = Self::normal_cumulative_distribution_function(-d2)?;
+            let sqrt_t = math::sqrt(time_annualized)?;
+            let exp = Self::discount_factor(time_annualized, interest_rate)?;
+
+            let a = spot_price
+                .checked_mul(&pdf)
+                .ok_or(ArithmeticError::Overflow)?;
+            let a = a.checked_mul(&iv).ok_or(ArithmeticError::Overflow)?;
+            let a = a
+                .checked_div(&sqrt_t)
+                .ok_or(ArithmeticError::DivisionByZero)?;
+            let a = a
+                .checked_div(&Decimal::saturating_from_integer(2))
+                .ok_or(ArithmeticError::DivisionByZero)?;
+
+            let b = strike_price
+                .checked_mul(&interest_rate)
+                .ok_or(ArithmeticError::Overflow)?;
+            let b = b.checked_mul(&exp).ok_or(ArithmeticError::Overflow)?;
+            let b_call = b.checked_mul(&cdf_call).ok_or(ArithmeticError::Overflow)?;
+            let b_put = b.checked_mul(&cdf_put).ok_or(ArithmeticError::Overflow)?;
+
+            let theta_call = (-a)
+                .checked_sub(&b_call)
+                .ok_or(ArithmeticError::Underflow)?;
+            let theta_put = b_put.checked_sub(&a).ok_or(ArithmeticError::Underflow)?;
+
+            Ok((theta_call, theta_put))
         }
 
         fn calculate_rho(
@@ -672,21 +916,22 @@ pub mod pallet {
             interest_rate: Decimal,
             d2: Decimal,
         ) -> Result<(Decimal, Decimal), DispatchError> {
-            // let cdf_call = Self::normal_cumulative_distribution_function(d2)?;
-            // let cdf_put = Self::normal_cumulative_distribution_function(-d2)?;
-            // let exp =
-            // 	-interest_rate.checked_mul(&time_annualized).ok_or(ArithmeticError::Overflow)?;
-            // let exp = Decimal::exp(&exp).ok_or(ArithmeticError::Overflow)?;
-
-            // let a = strike_price.checked_mul(&time_annualized).ok_or(ArithmeticError::Overflow)?;
-            // let a = a.checked_mul(&exp).ok_or(ArithmeticError::Overflow)?;
-            // let a = a.checked_div(100.into()).ok_or(ArithmeticError::Overflow)?;
-
-            // let rho_call = a.checked_mul(&cdf_call).ok_or(ArithmeticError::Overflow)?;
-            // let rho_put = -a.checked_mul(&cdf_put).ok_or(ArithmeticError::Overflow)?;
-
-            // Ok((rho_call, rho_put))
-            Ok((1.into(), 1.into()))
+            let cdf_call = Self::normal_cumulative_distribution_function(d2)?;
+            let cdf_put = Self::normal_cumulative_distribution_function(-d2)?;
+            let exp = Self::discount_factor(time_annualized, interest_rate)?;
+
+            let a = strike_price
+                .checked_mul(&time_annualized)
+                .ok_or(ArithmeticError::Overflow)?;
+            let a = a.checked_mul(&exp).ok_or(ArithmeticError::Overflow)?;
+            let a = a
+                .checked_div(&Decimal::saturating_from_integer(100))
+                .ok_or(ArithmeticError::DivisionByZero)?;
+
+            let rho_call = a.checked_mul(&cdf_call).ok_or(ArithmeticError::Overflow)?;
+            let rho_put = -a.checked_mul(&cdf_put).ok_or(ArithmeticError::Overflow)?;
+
+            Ok((rho_call, rho_put))
         }
     }
 }
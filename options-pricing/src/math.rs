@@ -0,0 +1,245 @@
+//! Deterministic fixed-point implementations of the mathematical functions needed by the
+//! Black-Scholes model.
+//!
+//! All the functions operate on [`Decimal`] values, never use floating point arithmetic and
+//! report failures through [`ArithmeticError`] instead of panicking. Against arbitrary precision
+//! references, the absolute errors are bounded by:
+//! - [`sqrt`]: `1e-18` (the result is the floor of the exact square root).
+//! - [`exp`]: `5e-18` for non-positive inputs; the relative error for positive inputs is bounded by
+//!   `5e-17`.
+//! - [`ln`]: `1e-16`.
+//! - [`normal_pdf`]: `5e-18`.
+//! - [`normal_cdf`]: `5e-16`.
+
+use crate::Decimal;
+use sp_core::U256;
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero},
+    ArithmeticError, FixedPointNumber,
+};
+
+// ----------------------------------------------------------------------------------------------------
+//		Constants
+// ----------------------------------------------------------------------------------------------------
+/// `ln(2)`.
+const LN_2: Decimal = Decimal::from_inner(693_147_180_559_945_309);
+
+/// `1 / sqrt(2 * pi)`.
+const INV_SQRT_2_PI: Decimal = Decimal::from_inner(398_942_280_401_432_678);
+
+/// `exp(x)` is smaller than the precision of [`Decimal`] for any `x` below this value.
+const EXP_MIN_INPUT: Decimal = Decimal::from_inner(-42_000_000_000_000_000_000);
+
+/// The standard normal distribution is within the precision of [`Decimal`] of `0` or `1` for
+/// inputs whose absolute value is greater than this bound.
+const NORMAL_BOUND: Decimal = Decimal::from_inner(10_000_000_000_000_000_000);
+
+/// Below this absolute value the normal CDF is computed with a Taylor series; above it with a
+/// continued fraction for the tail.
+const CDF_SERIES_BOUND: Decimal = Decimal::from_inner(3_000_000_000_000_000_000);
+
+/// Number of terms used to evaluate the continued fraction of the normal tail.
+const CDF_CONTINUED_FRACTION_TERMS: i128 = 128;
+
+/// Upper bound on the number of terms evaluated for the series expansions. For the accepted
+/// inputs every series converges well before reaching it.
+const MAX_SERIES_TERMS: i128 = 256;
+
+// ----------------------------------------------------------------------------------------------------
+//		Functions
+// ----------------------------------------------------------------------------------------------------
+/// Computes the square root of `x`, rounded down.
+///
+/// # Errors
+/// - [`ArithmeticError::Underflow`] if `x` is negative.
+pub fn sqrt(x: Decimal) -> Result<Decimal, ArithmeticError> {
+    if x.is_negative() {
+        return Err(ArithmeticError::Underflow)
+    }
+
+    // sqrt(inner / DIV) * DIV = sqrt(inner * DIV)
+    let radicand = U256::from(x.into_inner().unsigned_abs())
+        .saturating_mul(U256::from(Decimal::DIV.unsigned_abs()));
+
+    i128::try_from(radicand.integer_sqrt().low_u128())
+        .map(Decimal::from_inner)
+        .map_err(|_| ArithmeticError::Overflow)
+}
+
+/// Computes `e^x`.
+///
+/// The input is reduced to `x = k * ln(2) + r`, with `|r| <= ln(2) / 2`, so that `e^r` is
+/// computed with a quickly converging Taylor series and then scaled by `2^k`.
+///
+/// # Errors
+/// - [`ArithmeticError::Overflow`] if the result does not fit in a [`Decimal`].
+pub fn exp(x: Decimal) -> Result<Decimal, ArithmeticError> {
+    if x < EXP_MIN_INPUT {
+        return Ok(Decimal::zero())
+    }
+
+    let inner = x.into_inner();
+    let ln_2 = LN_2.into_inner();
+    let half_ln_2 = ln_2 / 2;
+    let k = if inner.is_negative() {
+        inner.saturating_sub(half_ln_2) / ln_2
+    } else {
+        inner.saturating_add(half_ln_2) / ln_2
+    };
+    let r = Decimal::from_inner(inner.saturating_sub(k.saturating_mul(ln_2)));
+
+    let mut term = Decimal::one();
+    let mut sum = Decimal::one();
+    for n in 1..=MAX_SERIES_TERMS {
+        term = Decimal::from_inner(mul(term, r)?.into_inner() / n);
+        if term.is_zero() {
+            break
+        }
+        sum = add(sum, term)?;
+    }
+
+    if k.is_negative() {
+        let shift = u32::try_from(k.unsigned_abs()).unwrap_or(u32::MAX);
+        Ok(Decimal::from_inner(
+            sum.into_inner().checked_shr(shift).unwrap_or(0),
+        ))
+    } else {
+        u32::try_from(k)
+            .ok()
+            .and_then(|k| 2_i128.checked_pow(k))
+            .and_then(|factor| sum.into_inner().checked_mul(factor))
+            .map(Decimal::from_inner)
+            .ok_or(ArithmeticError::Overflow)
+    }
+}
+
+/// Computes the natural logarithm of `x`.
+///
+/// The input is reduced to `x = 2^k * y`, with `1 <= y < 2`, so that `ln(y)` is computed with the
+/// series `ln(y) = 2 * atanh((y - 1) / (y + 1))` and then shifted by `k * ln(2)`.
+///
+/// # Errors
+/// - [`ArithmeticError::Underflow`] if `x` is not positive.
+pub fn ln(x: Decimal) -> Result<Decimal, ArithmeticError> {
+    if !x.is_positive() {
+        return Err(ArithmeticError::Underflow)
+    }
+
+    let one = Decimal::one().into_inner();
+    let mut y = x.into_inner();
+    let mut k: i128 = 0;
+    while y >= one.saturating_mul(2) {
+        y >>= 1;
+        k = k.saturating_add(1);
+    }
+    while y < one {
+        y <<= 1;
+        k = k.saturating_sub(1);
+    }
+
+    let y = Decimal::from_inner(y);
+    let s = sub(y, Decimal::one())?
+        .checked_div(&add(y, Decimal::one())?)
+        .ok_or(ArithmeticError::DivisionByZero)?;
+    let s_squared = mul(s, s)?;
+
+    let mut term = s;
+    let mut sum = s;
+    for n in 1..=MAX_SERIES_TERMS {
+        term = mul(term, s_squared)?;
+        if term.is_zero() {
+            break
+        }
+        sum = add(sum, Decimal::from_inner(term.into_inner() / (2 * n + 1)))?;
+    }
+
+    add(
+        Decimal::from_inner(k.saturating_mul(LN_2.into_inner())),
+        mul(sum, Decimal::saturating_from_integer(2))?,
+    )
+}
+
+/// Computes the probability density function of the standard normal distribution at `x`.
+///
+/// # Errors
+/// - [`ArithmeticError`] if an intermediate computation fails.
+pub fn normal_pdf(x: Decimal) -> Result<Decimal, ArithmeticError> {
+    let x = abs(x);
+    if x > NORMAL_BOUND {
+        return Ok(Decimal::zero())
+    }
+
+    let exponent = Decimal::from_inner(mul(x, x)?.into_inner() / 2);
+    mul(exp(-exponent)?, INV_SQRT_2_PI)
+}
+
+/// Computes the cumulative distribution function of the standard normal distribution at `x`.
+///
+/// Close to the mean, the function is computed with the Taylor series
+/// `N(x) = 1/2 + pdf(x) * (x + x^3/3 + x^5/(3*5) + ...)`. In the tails, where the precision of
+/// `pdf(x)` would be amplified by the series, the upper tail `1 - N(x) = pdf(x) * R(x)` is used
+/// instead, evaluating the Mills ratio `R(x)` with its continued fraction.
+///
+/// # Errors
+/// - [`ArithmeticError`] if an intermediate computation fails.
+pub fn normal_cdf(x: Decimal) -> Result<Decimal, ArithmeticError> {
+    let x_abs = abs(x);
+
+    let upper_tail = if x_abs > NORMAL_BOUND {
+        Decimal::zero()
+    } else if x_abs < CDF_SERIES_BOUND {
+        let x_squared = mul(x_abs, x_abs)?;
+        let mut term = x_abs;
+        let mut sum = x_abs;
+        for n in 1..=MAX_SERIES_TERMS {
+            term = Decimal::from_inner(mul(term, x_squared)?.into_inner() / (2 * n + 1));
+            if term.is_zero() {
+                break
+            }
+            sum = add(sum, term)?;
+        }
+
+        let half = Decimal::saturating_from_rational(1, 2);
+        sub(half, mul(normal_pdf(x_abs)?, sum)?)?
+    } else {
+        let mut fraction = x_abs;
+        for k in (1..=CDF_CONTINUED_FRACTION_TERMS).rev() {
+            let numerator = Decimal::saturating_from_integer(k);
+            fraction = add(
+                x_abs,
+                numerator
+                    .checked_div(&fraction)
+                    .ok_or(ArithmeticError::DivisionByZero)?,
+            )?;
+        }
+
+        normal_pdf(x_abs)?
+            .checked_div(&fraction)
+            .ok_or(ArithmeticError::DivisionByZero)?
+    };
+
+    if x.is_negative() {
+        Ok(upper_tail)
+    } else {
+        sub(Decimal::one(), upper_tail)
+    }
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Helpers
+// ----------------------------------------------------------------------------------------------------
+fn abs(x: Decimal) -> Decimal {
+    Decimal::from_inner(x.into_inner().saturating_abs())
+}
+
+fn add(a: Decimal, b: Decimal) -> Result<Decimal, ArithmeticError> {
+    a.checked_add(&b).ok_or(ArithmeticError::Overflow)
+}
+
+fn sub(a: Decimal, b: Decimal) -> Result<Decimal, ArithmeticError> {
+    a.checked_sub(&b).ok_or(ArithmeticError::Underflow)
+}
+
+fn mul(a: Decimal, b: Decimal) -> Result<Decimal, ArithmeticError> {
+    a.checked_mul(&b).ok_or(ArithmeticError::Overflow)
+}
@@ -5,6 +5,7 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 
 pub const ADMIN: AccountId = account_id_from_u64(0);
 pub const ALICE: AccountId = account_id_from_u64(1);
+pub const TREASURY: AccountId = account_id_from_u64(6);
 
 pub const fn account_id_from_u64(n: u64) -> AccountId {
     let bytes_src = n.to_be_bytes();
@@ -7,6 +7,7 @@ use composable_traits::{defi::DeFiComposableConfig, governance::SignedRawOrigin,
 use frame_support::{
     ord_parameter_types, parameter_types,
     traits::{EitherOfDiverse, Everything, GenesisBuild, Hooks},
+    weights::Weight,
     PalletId,
 };
 
@@ -17,7 +18,9 @@ use sp_core::{sr25519::Signature, H256};
 use sp_runtime::{
     testing::{Header, TestXt},
     traits::{ConvertInto, Extrinsic as ExtrinsicT, IdentityLookup, Verify},
+    DispatchError, DispatchResult, Permill,
 };
+use traits::collateral_strategy::CollateralStrategy;
 
 pub type BlockNumber = u64;
 pub type Balance = u128;
@@ -344,6 +347,11 @@ impl pallet_vault::Config for MockRuntime {
 
 parameter_types! {
     pub const OptionsPricingPalletId: PalletId = PalletId(*b"pricing_");
+    pub const MaxSurfacePoints: u32 = 8;
+    pub const MaxPriceSamples: u32 = 16;
+    pub const PriceSamplingPeriod: Moment = 1000;
+    pub const SnapshotRetentionPeriod: Moment = 20_000;
+    pub const MaxSnapshotWeight: Weight = 2_500;
 }
 
 impl pallet_options_pricing::Config for MockRuntime {
@@ -355,6 +363,13 @@ impl pallet_options_pricing::Config for MockRuntime {
     type Time = Timestamp;
     type ProtocolOrigin =
         EitherOfDiverse<EnsureSignedBy<RootAccount, AccountId>, EnsureRoot<AccountId>>;
+    type VolatilityOrigin =
+        EitherOfDiverse<EnsureSignedBy<RootAccount, AccountId>, EnsureRoot<AccountId>>;
+    type MaxSurfacePoints = MaxSurfacePoints;
+    type MaxPriceSamples = MaxPriceSamples;
+    type PriceSamplingPeriod = PriceSamplingPeriod;
+    type SnapshotRetentionPeriod = SnapshotRetentionPeriod;
+    type MaxSnapshotWeight = MaxSnapshotWeight;
     type Assets = Assets;
 }
 
@@ -362,9 +377,46 @@ impl pallet_options_pricing::Config for MockRuntime {
 //		Tokenized Options
 // ----------------------------------------------------------------------------------------------------
 
+/// Vaults of the tests of this pallet don't allocate collateral to any strategy.
+pub struct NoCollateralStrategy;
+
+impl CollateralStrategy for NoCollateralStrategy {
+    type AccountId = AccountId;
+    type AssetId = AssetId;
+    type Balance = Balance;
+
+    fn deposit(_asset_id: AssetId, _from: &AccountId, _amount: Balance) -> DispatchResult {
+        Err(DispatchError::Other("NoCollateralStrategy"))
+    }
+
+    fn withdraw(_asset_id: AssetId, _to: &AccountId, _amount: Balance) -> DispatchResult {
+        Err(DispatchError::Other("NoCollateralStrategy"))
+    }
+
+    fn harvest(_asset_id: AssetId, _to: &AccountId) -> Result<Balance, DispatchError> {
+        Ok(0)
+    }
+
+    fn balance(_asset_id: AssetId) -> Balance {
+        0
+    }
+}
+
 parameter_types! {
     pub const TokenizedOptionsPalletId: PalletId = PalletId(*b"options_");
     pub const StablecoinAssetId: AssetId = USDC;
+    pub const BuybackSpread: Permill = Permill::from_percent(5);
+    pub const SettlementGracePeriod: Moment = 3000;
+    pub const MaxSchedulerWeight: Weight = 1_000_000;
+    pub const ClaimWindow: Moment = 5000;
+    pub const UnclaimedFundsAccount: AccountId = TREASURY;
+    pub const MaxCleanupBatch: u32 = 10;
+    pub const MaxStrategyLegs: u32 = 4;
+    pub const MarginStressShock: Permill = Permill::from_percent(20);
+    pub const MaxMarginWriters: u32 = 10;
+    pub const FeeAccount: AccountId = TREASURY;
+    pub const ExerciseKeeperReward: Permill = Permill::from_percent(1);
+    pub const MaxRolloverSellers: u32 = 4;
 }
 
 impl pallet_tokenized_options::Config for MockRuntime {
@@ -383,7 +435,20 @@ impl pallet_tokenized_options::Config for MockRuntime {
     type Assets = Assets;
     type VaultId = VaultId;
     type Vault = Vault;
-    // type OptionsPricing = OptionsPricing;
+    type OptionsPricing = OptionsPricing;
+    type BuybackSpread = BuybackSpread;
+    type SettlementGracePeriod = SettlementGracePeriod;
+    type MaxSchedulerWeight = MaxSchedulerWeight;
+    type ClaimWindow = ClaimWindow;
+    type UnclaimedFundsAccount = UnclaimedFundsAccount;
+    type MaxCleanupBatch = MaxCleanupBatch;
+    type MaxStrategyLegs = MaxStrategyLegs;
+    type MarginStressShock = MarginStressShock;
+    type MaxMarginWriters = MaxMarginWriters;
+    type FeeAccount = FeeAccount;
+    type CollateralStrategy = NoCollateralStrategy;
+    type ExerciseKeeperReward = ExerciseKeeperReward;
+    type MaxRolloverSellers = MaxRolloverSellers;
 }
 
 // ----------------------------------------------------------------------------------------------------
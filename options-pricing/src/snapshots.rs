@@ -0,0 +1,98 @@
+use crate::{
+    types::*, Config, Event, LatestSnapshotTimestamp, LatestSnapshots, MomentOf, Pallet,
+    PendingSnapshotRound, SnapshotFrequency, SnapshotsHistory, WeightInfo,
+};
+use frame_support::pallet_prelude::*;
+use sp_runtime::{traits::Saturating, SaturatedConversion};
+use sp_std::vec::Vec;
+
+impl<T: Config> Pallet<T> {
+    /// Copies the latest snapshot of each option into the history under the timestamp of the
+    /// round, at most once every `SnapshotFrequency`, and prunes the snapshots of these options
+    /// older than `SnapshotRetentionPeriod`.
+    ///
+    /// A round stops once `MaxSnapshotWeight` is used and is resumed in the next blocks before a
+    /// new one can start. At least one option is processed each block so rounds always end.
+    /// Returns the weight used.
+    pub(crate) fn take_snapshots(now: MomentOf<T>) -> Weight {
+        let mut used_weight = T::DbWeight::get().reads(1);
+
+        let round = match PendingSnapshotRound::<T>::get() {
+            Some(round) => round,
+            None => {
+                used_weight = used_weight.saturating_add(T::DbWeight::get().reads(2));
+
+                let snapshot_frequency = SnapshotFrequency::<T>::get()
+                    .unwrap_or_else(|| DEFAULT_SNAPSHOT_FREQUENCY.into());
+                let is_due = LatestSnapshotTimestamp::<T>::get().map_or(true, |latest| {
+                    now >= latest.saturating_add(snapshot_frequency)
+                });
+
+                if !is_due {
+                    return used_weight
+                }
+
+                LatestSnapshotTimestamp::<T>::put(now);
+                used_weight = used_weight.saturating_add(T::DbWeight::get().writes(1));
+
+                SnapshotRound {
+                    timestamp: now,
+                    last_key: None,
+                }
+            },
+        };
+
+        let timestamp = round.timestamp;
+        let cutoff = timestamp.saturating_sub(T::SnapshotRetentionPeriod::get());
+        let budget = T::MaxSnapshotWeight::get();
+
+        let mut snapshots = match round.last_key {
+            Some(last_key) => LatestSnapshots::<T>::iter_from(last_key.into_inner()),
+            None => LatestSnapshots::<T>::iter(),
+        };
+        let mut processed_any = false;
+
+        loop {
+            let previous_key = snapshots.last_raw_key().to_vec();
+
+            let (option_id, snapshot) = match snapshots.next() {
+                Some(entry) => entry,
+                None => {
+                    PendingSnapshotRound::<T>::kill();
+                    Self::deposit_event(Event::SnapshotTaken { timestamp });
+
+                    return used_weight.saturating_add(T::DbWeight::get().writes(1))
+                },
+            };
+
+            let history: Vec<MomentOf<T>> =
+                SnapshotsHistory::<T>::iter_key_prefix(option_id).collect();
+            let weight =
+                <T as Config>::WeightInfo::take_option_snapshot(history.len().saturated_into());
+
+            if processed_any && used_weight.saturating_add(weight) > budget {
+                match BoundedVec::try_from(previous_key) {
+                    Ok(last_key) => PendingSnapshotRound::<T>::put(SnapshotRound {
+                        timestamp,
+                        last_key: Some(last_key),
+                    }),
+                    // The round can't be resumed from a key that doesn't fit, so it ends here
+                    Err(_) => PendingSnapshotRound::<T>::kill(),
+                }
+
+                return used_weight.saturating_add(T::DbWeight::get().writes(1))
+            }
+
+            history
+                .into_iter()
+                .filter(|snapshot_timestamp| *snapshot_timestamp < cutoff)
+                .for_each(|snapshot_timestamp| {
+                    SnapshotsHistory::<T>::remove(option_id, snapshot_timestamp)
+                });
+            SnapshotsHistory::<T>::insert(option_id, timestamp, snapshot);
+
+            used_weight = used_weight.saturating_add(weight);
+            processed_any = true;
+        }
+    }
+}
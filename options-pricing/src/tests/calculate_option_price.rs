@@ -2,48 +2,245 @@ use crate::{
     mocks::{
         accounts::*,
         assets::*,
-        runtime::{ExtBuilder, MockRuntime, OptionsPricing, Origin},
+        runtime::{ExtBuilder, MockRuntime, OptionsPricing, Origin, Timestamp},
     },
-    pallet::{self, Error, InterestRate, LatestSnapshots},
+    pallet::{self, Decimal, Error, InterestRate, LatestSnapshots},
     tests::*,
+    types::{DEFAULT_VOLATILITY, MILLISECONDS_PER_YEAR},
 };
 
 use frame_support::{assert_noop, assert_ok, error::BadOrigin};
-use greeks::*;
-use sp_runtime::FixedPointNumber;
-use std;
+use sp_runtime::{traits::Zero, FixedPointNumber};
+use traits::options_pricing::OptionsPricing as OptionsPricingTrait;
+
 // ----------------------------------------------------------------------------------------------------
-//		Options Price Tests
+//		Helper functions
 // ----------------------------------------------------------------------------------------------------
+const THIRTY_DAYS: Moment = 30 * 24 * 60 * 60 * 1000;
+
+fn to_f64(value: Decimal) -> f64 {
+    value.into_inner() as f64 / 1e18
+}
+
+// Reference Black-Scholes price of an option with strike `strike` and spot `spot`
+fn reference_price(
+    option_type: OptionType,
+    spot: f64,
+    strike: f64,
+    time_annualized: f64,
+    interest_rate: f64,
+    iv: f64,
+) -> f64 {
+    match option_type {
+        OptionType::Call =>
+            greeks::euro_call(spot, strike, time_annualized, interest_rate, 0.0, iv),
+        OptionType::Put => greeks::euro_put(spot, strike, time_annualized, interest_rate, 0.0, iv),
+    }
+}
+
+fn time_annualized(expiring_date: Moment) -> f64 {
+    (expiring_date - Timestamp::now()) as f64 / MILLISECONDS_PER_YEAR as f64
+}
 
+fn assert_option_price(option_type: OptionType, spot_price: Balance, strike_price: Balance) {
+    let option_id = 1_u128;
+    let expiring_date = Timestamp::now() + THIRTY_DAYS;
+    let bs_params = BlackScholesParamsBuilder::default()
+        .option_type(option_type)
+        .base_asset_strike_price(strike_price)
+        .base_asset_spot_price(spot_price)
+        .expiring_date(expiring_date)
+        .build();
+
+    assert_ok!(OptionsPricing::calculate_option_price(
+        Origin::signed(ADMIN),
+        option_id,
+        bs_params
+    ));
+
+    let expected_price = reference_price(
+        option_type,
+        spot_price as f64,
+        strike_price as f64,
+        time_annualized(expiring_date),
+        to_f64(InterestRate::<MockRuntime>::get()),
+        to_f64(DEFAULT_VOLATILITY),
+    );
+
+    let snapshot = LatestSnapshots::<MockRuntime>::get(option_id).unwrap();
+    let error = (snapshot.option_price as f64 - expected_price).abs();
+
+    assert!(
+        error <= strike_price as f64 * 1e-6,
+        "expected {}, got {}",
+        expected_price,
+        snapshot.option_price
+    );
+    assert_eq!(snapshot.asset_spot_price, spot_price);
+    assert_eq!(snapshot.iv, DEFAULT_VOLATILITY);
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Options Price Tests
+// ----------------------------------------------------------------------------------------------------
 #[test]
 fn test_calculate_option_price() {
     ExtBuilder::default()
         .build()
         .initialize_oracle_prices()
         .execute_with(|| {
-            let bs_params = BlackScholesParamsBuilder::default().build();
+            [OptionType::Call, OptionType::Put]
+                .into_iter()
+                .for_each(|option_type| {
+                    [40000u128, 48000u128, 50000u128, 52000u128, 60000u128]
+                        .into_iter()
+                        .for_each(|spot_price| {
+                            assert_option_price(option_type, spot_price * UNIT, 50000u128 * UNIT)
+                        });
+                });
+        });
+}
+
+#[test]
+fn test_calculate_option_price_independent_from_decimals() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            // 6 and 18 decimals assets
+            assert_option_price(
+                OptionType::Call,
+                48000u128 * 10u128.pow(6),
+                50000u128 * 10u128.pow(6),
+            );
+            assert_option_price(
+                OptionType::Put,
+                48000u128 * 10u128.pow(18),
+                50000u128 * 10u128.pow(18),
+            );
+        });
+}
+
+#[test]
+fn test_calculate_option_price_at_expiry_is_intrinsic_value() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            let option_id = 1_u128;
+            let cases = [
+                (OptionType::Call, 55000u128 * UNIT, 5000u128 * UNIT),
+                (OptionType::Call, 45000u128 * UNIT, 0u128),
+                (OptionType::Put, 45000u128 * UNIT, 5000u128 * UNIT),
+                (OptionType::Put, 55000u128 * UNIT, 0u128),
+            ];
+
+            cases
+                .into_iter()
+                .for_each(|(option_type, spot_price, intrinsic_value)| {
+                    let bs_params = BlackScholesParamsBuilder::default()
+                        .option_type(option_type)
+                        .base_asset_spot_price(spot_price)
+                        .expiring_date(Timestamp::now())
+                        .build();
+
+                    assert_ok!(OptionsPricing::calculate_option_price(
+                        Origin::signed(ADMIN),
+                        option_id,
+                        bs_params
+                    ));
+
+                    let snapshot = LatestSnapshots::<MockRuntime>::get(option_id).unwrap();
+                    assert_eq!(snapshot.option_price, intrinsic_value);
+                    assert_eq!(snapshot.greeks.gamma, Decimal::zero());
+                });
+        });
+}
 
+#[test]
+fn test_calculate_option_greeks() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
             let option_id = 1_u128;
+            let strike_price = 50000u128 * UNIT;
+            let spot_price = 48000u128 * UNIT;
+            let expiring_date = Timestamp::now() + THIRTY_DAYS;
 
-            let interest_rate = InterestRate::<MockRuntime>::get();
+            // Prices are normalized for a strike of one
+            let spot = spot_price as f64 / strike_price as f64;
+            let t = time_annualized(expiring_date);
+            let r = to_f64(InterestRate::<MockRuntime>::get());
+            let iv = to_f64(DEFAULT_VOLATILITY);
+            let h = 1e-5;
 
-            let correct_price = greeks::euro_call(
-                f64::from(bs_params.base_asset_spot_price as u32),
-                f64::from(bs_params.base_asset_strike_price as u32),
-                f64::from(bs_params.expiring_date as u32),
-                f64::from(interest_rate.into_inner() as u32),
-                f64::from(0),
-                0.54,
-            );
+            [OptionType::Call, OptionType::Put]
+                .into_iter()
+                .for_each(|option_type| {
+                    let price = |spot: f64, t: f64, r: f64, iv: f64| {
+                        reference_price(option_type, spot, 1.0, t, r, iv)
+                    };
 
-            println!("{:?}", correct_price);
+                    let bs_params = BlackScholesParamsBuilder::default()
+                        .option_type(option_type)
+                        .base_asset_strike_price(strike_price)
+                        .base_asset_spot_price(spot_price)
+                        .expiring_date(expiring_date)
+                        .build();
 
-            assert_ok!(OptionsPricing::calculate_option_price(
-                Origin::signed(ADMIN),
-                option_id,
-                bs_params
-            ));
+                    let greeks = <OptionsPricing as OptionsPricingTrait>::get_option_greeks(
+                        bs_params.clone(),
+                    )
+                    .unwrap();
+
+                    assert_ok!(OptionsPricing::calculate_option_greeks(
+                        Origin::signed(ADMIN),
+                        option_id,
+                        bs_params
+                    ));
+
+                    let snapshot = LatestSnapshots::<MockRuntime>::get(option_id).unwrap();
+                    assert_eq!(snapshot.greeks, greeks);
+
+                    let delta = (price(spot + h, t, r, iv) - price(spot - h, t, r, iv)) / (2.0 * h);
+                    let gamma = (price(spot + h, t, r, iv) - 2.0 * price(spot, t, r, iv) +
+                        price(spot - h, t, r, iv)) /
+                        (h * h);
+                    // Gamma for a 1% change of the spot price, vega and rho for a 1% change and
+                    // theta per year, as a fraction of the spot price
+                    let gamma = gamma * spot / 100.0;
+                    let vega =
+                        (price(spot, t, r, iv + h) - price(spot, t, r, iv - h)) / (2.0 * h) / 100.0;
+                    let theta =
+                        -(price(spot, t + h, r, iv) - price(spot, t - h, r, iv)) / (2.0 * h);
+                    let rho =
+                        (price(spot, t, r + h, iv) - price(spot, t, r - h, iv)) / (2.0 * h) / 100.0;
+
+                    assert!((to_f64(greeks.delta) - delta).abs() <= 1e-6);
+                    assert!((to_f64(greeks.gamma) - gamma).abs() <= 1e-5);
+                    assert!((to_f64(greeks.vega) - vega / spot).abs() <= 1e-6);
+                    assert!((to_f64(greeks.theta) - theta / spot).abs() <= 1e-5);
+                    assert!((to_f64(greeks.rho) - rho / spot).abs() <= 1e-6);
+                });
+        });
+}
+
+#[test]
+fn test_calculate_option_price_error_strike_price_zero() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            let bs_params = BlackScholesParamsBuilder::default()
+                .base_asset_strike_price(0u128)
+                .build();
+            let option_id = 1_u128;
+
+            assert_noop!(
+                OptionsPricing::calculate_option_price(Origin::signed(ADMIN), option_id, bs_params),
+                Error::<MockRuntime>::FailedConversion
+            );
         });
 }
 
@@ -0,0 +1,43 @@
+use crate::{
+    mocks::{
+        accounts::*,
+        runtime::{Event, ExtBuilder, MockRuntime, OptionsPricing, Origin, System},
+    },
+    pallet::{self, Decimal, InterestRate},
+};
+
+use frame_support::{assert_noop, assert_ok, error::BadOrigin};
+use sp_runtime::FixedPointNumber;
+
+// ----------------------------------------------------------------------------------------------------
+//		Update Interest Rate Tests
+// ----------------------------------------------------------------------------------------------------
+#[test]
+fn test_update_interest_rate_success() {
+    ExtBuilder::default().build().execute_with(|| {
+        let interest_rate = Decimal::saturating_from_rational(3, 100);
+
+        assert_ok!(OptionsPricing::update_interest_rate(
+            Origin::signed(ADMIN),
+            interest_rate
+        ));
+
+        assert_eq!(InterestRate::<MockRuntime>::get(), interest_rate);
+        System::assert_last_event(Event::OptionsPricing(pallet::Event::InterestRateUpdated {
+            interest_rate,
+        }));
+    });
+}
+
+#[test]
+fn test_update_interest_rate_error_not_protocol_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            OptionsPricing::update_interest_rate(
+                Origin::signed(ALICE),
+                Decimal::saturating_from_rational(3, 100)
+            ),
+            BadOrigin
+        );
+    });
+}
@@ -0,0 +1,245 @@
+use crate::{math::*, pallet::Decimal};
+
+use proptest::prelude::*;
+use sp_runtime::{ArithmeticError, FixedPointNumber};
+
+// ----------------------------------------------------------------------------------------------------
+//		Helper functions
+// ----------------------------------------------------------------------------------------------------
+// Reference values are computed with 50 digits of precision and rounded to 18 decimals
+fn assert_close(actual: Decimal, expected: i128, tolerance: i128) {
+    let error = (actual.into_inner() - expected).abs();
+    assert!(
+        error <= tolerance,
+        "expected {}, got {}, error {}",
+        expected,
+        actual.into_inner(),
+        error
+    );
+}
+
+fn decimal(value: f64) -> Decimal {
+    Decimal::from_inner((value * 1e18) as i128)
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.into_inner() as f64 / 1e18
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Reference values
+// ----------------------------------------------------------------------------------------------------
+#[test]
+fn test_sqrt_reference_values() {
+    let cases = [
+        (
+            Decimal::saturating_from_integer(2),
+            1_414_213_562_373_095_048,
+        ),
+        (
+            Decimal::saturating_from_rational(1, 4),
+            500_000_000_000_000_000,
+        ),
+        (
+            Decimal::saturating_from_integer(1_000_000),
+            1_000_000_000_000_000_000_000,
+        ),
+        (Decimal::from_inner(0), 0),
+    ];
+
+    cases
+        .into_iter()
+        .for_each(|(x, expected)| assert_close(sqrt(x).unwrap(), expected, 1));
+}
+
+#[test]
+fn test_exp_reference_values() {
+    let cases = [
+        (Decimal::saturating_from_integer(-20), 2_061_153_622),
+        (
+            Decimal::saturating_from_integer(-1),
+            367_879_441_171_442_322,
+        ),
+        (
+            Decimal::saturating_from_integer(0),
+            1_000_000_000_000_000_000,
+        ),
+        (
+            Decimal::saturating_from_rational(1, 2),
+            1_648_721_270_700_128_147,
+        ),
+        (
+            Decimal::saturating_from_integer(1),
+            2_718_281_828_459_045_235,
+        ),
+        (
+            Decimal::saturating_from_integer(10),
+            22_026_465_794_806_716_516_958,
+        ),
+    ];
+
+    cases.into_iter().for_each(|(x, expected)| {
+        // 5e-17 relative error for positive inputs, 5e-18 absolute otherwise
+        let tolerance = (expected / 20_000_000_000_000_000).max(5);
+        assert_close(exp(x).unwrap(), expected, tolerance)
+    });
+
+    assert_close(
+        exp(Decimal::saturating_from_integer(40)).unwrap(),
+        235_385_266_837_019_985_407_899_910_749_034_805,
+        235_385_266_837_019_985_407,
+    );
+    assert_eq!(
+        exp(Decimal::saturating_from_integer(-50)),
+        Ok(Decimal::from_inner(0))
+    );
+    assert_eq!(
+        exp(Decimal::saturating_from_integer(100)),
+        Err(ArithmeticError::Overflow)
+    );
+}
+
+#[test]
+fn test_ln_reference_values() {
+    let cases = [
+        (
+            Decimal::saturating_from_rational(1, 1000),
+            -6_907_755_278_982_137_052,
+        ),
+        (
+            Decimal::saturating_from_rational(1, 2),
+            -693_147_180_559_945_309,
+        ),
+        (Decimal::saturating_from_integer(1), 0),
+        (Decimal::saturating_from_integer(2), 693_147_180_559_945_309),
+        (
+            Decimal::saturating_from_integer(10),
+            2_302_585_092_994_045_684,
+        ),
+        (
+            Decimal::saturating_from_integer(1_000_000),
+            13_815_510_557_964_274_104,
+        ),
+    ];
+
+    cases
+        .into_iter()
+        .for_each(|(x, expected)| assert_close(ln(x).unwrap(), expected, 100));
+
+    assert_eq!(ln(Decimal::from_inner(0)), Err(ArithmeticError::Underflow));
+    assert_eq!(
+        ln(Decimal::saturating_from_integer(-1)),
+        Err(ArithmeticError::Underflow)
+    );
+}
+
+#[test]
+fn test_normal_pdf_reference_values() {
+    let cases = [
+        (Decimal::saturating_from_integer(0), 398_942_280_401_432_678),
+        (Decimal::saturating_from_integer(1), 241_970_724_519_143_350),
+        (Decimal::saturating_from_integer(-3), 4_431_848_411_938_007),
+        (Decimal::saturating_from_integer(20), 0),
+    ];
+
+    cases
+        .into_iter()
+        .for_each(|(x, expected)| assert_close(normal_pdf(x).unwrap(), expected, 5));
+}
+
+#[test]
+fn test_normal_cdf_reference_values() {
+    let cases = [
+        (Decimal::saturating_from_integer(-5), 286_651_571_879),
+        (
+            Decimal::saturating_from_integer(-1),
+            158_655_253_931_457_051,
+        ),
+        (Decimal::saturating_from_integer(0), 500_000_000_000_000_000),
+        (
+            Decimal::saturating_from_rational(1, 2),
+            691_462_461_274_013_104,
+        ),
+        (
+            Decimal::saturating_from_rational(196, 100),
+            975_002_104_851_779_566,
+        ),
+        (Decimal::saturating_from_integer(4), 999_968_328_758_166_880),
+        (Decimal::saturating_from_integer(-20), 0),
+        (
+            Decimal::saturating_from_integer(20),
+            1_000_000_000_000_000_000,
+        ),
+    ];
+
+    cases
+        .into_iter()
+        .for_each(|(x, expected)| assert_close(normal_cdf(x).unwrap(), expected, 500));
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Property tests
+// ----------------------------------------------------------------------------------------------------
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn proptest_sqrt_matches_f64(x in 0.0_f64..1e9) {
+        let expected = x.sqrt();
+        let actual = to_f64(sqrt(decimal(x)).unwrap());
+        prop_assert!((actual - expected).abs() <= 1e-9 * expected.max(1.0));
+    }
+
+    #[test]
+    fn proptest_sqrt_is_floor_of_exact_root(inner in 0_i128..i128::MAX / 1_000_000) {
+        let root = sqrt(Decimal::from_inner(inner)).unwrap().into_inner();
+        let radicand = sp_core::U256::from(inner) * sp_core::U256::from(10_u128.pow(18));
+        prop_assert!(sp_core::U256::from(root) * sp_core::U256::from(root) <= radicand);
+        prop_assert!(sp_core::U256::from(root + 1) * sp_core::U256::from(root + 1) > radicand);
+    }
+
+    #[test]
+    fn proptest_exp_matches_f64(x in -40.0_f64..40.0) {
+        let expected = x.exp();
+        let actual = to_f64(exp(decimal(x)).unwrap());
+        prop_assert!((actual - expected).abs() <= 1e-12 * expected + 1e-17);
+    }
+
+    #[test]
+    fn proptest_ln_matches_f64(x in 1e-9_f64..1e9) {
+        let expected = x.ln();
+        let actual = to_f64(ln(decimal(x)).unwrap());
+        prop_assert!((actual - expected).abs() <= 1e-9);
+    }
+
+    #[test]
+    fn proptest_ln_inverts_exp(x in -10.0_f64..20.0) {
+        let x = decimal(x);
+        let actual = ln(exp(x).unwrap()).unwrap();
+        // The precision of exp(x) limits how well the logarithm can be recovered
+        prop_assert!((to_f64(actual) - to_f64(x)).abs() <= 1e-9);
+    }
+
+    #[test]
+    fn proptest_normal_cdf_is_symmetric(x in -12.0_f64..12.0) {
+        let x = decimal(x);
+        let sum = normal_cdf(x).unwrap().into_inner() + normal_cdf(-x).unwrap().into_inner();
+        prop_assert!((sum - 1_000_000_000_000_000_000).abs() <= 1_000);
+    }
+
+    #[test]
+    fn proptest_normal_cdf_is_monotonic(x in -12.0_f64..12.0, step in 1e-6_f64..1.0) {
+        let lower = normal_cdf(decimal(x)).unwrap();
+        let upper = normal_cdf(decimal(x + step)).unwrap();
+        // Far in the tails the increments are below the precision of the functions
+        prop_assert!(lower.into_inner() <= upper.into_inner() + 100);
+        prop_assert!(lower.into_inner() >= 0);
+        prop_assert!(upper.into_inner() <= 1_000_000_000_000_000_000);
+    }
+
+    #[test]
+    fn proptest_normal_pdf_is_symmetric(x in -12.0_f64..12.0) {
+        let x = decimal(x);
+        prop_assert_eq!(normal_pdf(x).unwrap(), normal_pdf(-x).unwrap());
+    }
+}
@@ -6,6 +6,10 @@ use traits::{options_pricing::*, tokenized_options::*};
 use frame_support::traits::Hooks;
 
 pub mod calculate_option_price;
+pub mod interest_rate;
+pub mod math;
+pub mod snapshots;
+pub mod volatility;
 
 pub const UNIT: u128 = 10u128.pow(12);
 
@@ -84,6 +88,11 @@ impl BlackScholesParamsBuilder {
         self
     }
 
+    fn base_asset_spot_price(mut self, base_asset_spot_price: Balance) -> Self {
+        self.base_asset_spot_price = base_asset_spot_price;
+        self
+    }
+
     fn option_type(mut self, option_type: OptionType) -> Self {
         self.option_type = option_type;
         self
@@ -119,6 +128,7 @@ pub fn run_to_block(n: u64) {
         // Assuming millisecond timestamps, one second for each block
         System::on_initialize(System::block_number());
         Timestamp::on_initialize(System::block_number());
+        OptionsPricing::on_initialize(System::block_number());
         Timestamp::set(Origin::none(), System::block_number() * 1000).unwrap();
     }
 }
@@ -132,5 +142,6 @@ pub fn run_for_seconds(n: u64) {
     System::set_block_number(System::block_number() + 1);
     System::on_initialize(System::block_number());
     Timestamp::on_initialize(System::block_number());
+    OptionsPricing::on_initialize(System::block_number());
     Timestamp::set(Origin::none(), n * 1000).unwrap();
 }
@@ -0,0 +1,184 @@
+use crate::{
+    mocks::{
+        accounts::*,
+        assets::*,
+        runtime::{Event, ExtBuilder, MockRuntime, OptionsPricing, Origin, System, Timestamp},
+    },
+    pallet::{
+        self, LatestSnapshotTimestamp, LatestSnapshots, PendingSnapshotRound, SnapshotFrequency,
+        SnapshotsHistory,
+    },
+    tests::*,
+};
+
+use frame_support::{assert_noop, assert_ok, error::BadOrigin};
+
+// ----------------------------------------------------------------------------------------------------
+//		Helper functions
+// ----------------------------------------------------------------------------------------------------
+const SNAPSHOT_FREQUENCY: Moment = 10_000;
+const DAY: Moment = 24 * 60 * 60 * 1000;
+
+fn price_options_and_set_frequency(options: u128) {
+    (1..=options).for_each(|option_id| {
+        let bs_params = BlackScholesParamsBuilder::default()
+            .base_asset_spot_price(50000u128 * UNIT)
+            .expiring_date(Timestamp::now() + 30 * DAY)
+            .build();
+
+        assert_ok!(OptionsPricing::calculate_option_price(
+            Origin::signed(ADMIN),
+            option_id,
+            bs_params
+        ));
+    });
+
+    assert_ok!(OptionsPricing::update_snapshot_frequency(
+        Origin::signed(ADMIN),
+        SNAPSHOT_FREQUENCY
+    ));
+}
+
+fn history_timestamps(option_id: u128) -> Vec<Moment> {
+    let mut timestamps: Vec<Moment> =
+        SnapshotsHistory::<MockRuntime>::iter_key_prefix(option_id).collect();
+    timestamps.sort();
+    timestamps
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Snapshot Frequency Tests
+// ----------------------------------------------------------------------------------------------------
+#[test]
+fn test_update_snapshot_frequency_success() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(OptionsPricing::update_snapshot_frequency(
+            Origin::signed(ADMIN),
+            SNAPSHOT_FREQUENCY
+        ));
+
+        assert_eq!(
+            SnapshotFrequency::<MockRuntime>::get(),
+            Some(SNAPSHOT_FREQUENCY)
+        );
+        System::assert_last_event(Event::OptionsPricing(
+            pallet::Event::SnapshotFrequencyUpdated {
+                snapshot_frequency: SNAPSHOT_FREQUENCY,
+            },
+        ));
+    });
+}
+
+#[test]
+fn test_update_snapshot_frequency_error_not_protocol_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            OptionsPricing::update_snapshot_frequency(Origin::signed(ALICE), SNAPSHOT_FREQUENCY),
+            BadOrigin
+        );
+    });
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Snapshot Hook Tests
+// ----------------------------------------------------------------------------------------------------
+#[test]
+fn test_snapshots_taken_once_per_frequency() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            // The first round runs in the first block, before any option is priced
+            assert_eq!(LatestSnapshotTimestamp::<MockRuntime>::get(), Some(0));
+
+            price_options_and_set_frequency(2);
+
+            // The hook of a block sees the timestamp of the previous one
+            run_to_block(10);
+            assert_eq!(SnapshotsHistory::<MockRuntime>::iter().count(), 0);
+            assert_eq!(LatestSnapshotTimestamp::<MockRuntime>::get(), Some(0));
+
+            run_to_block(11);
+            (1..=2).for_each(|option_id| {
+                assert_eq!(
+                    SnapshotsHistory::<MockRuntime>::get(option_id, SNAPSHOT_FREQUENCY),
+                    LatestSnapshots::<MockRuntime>::get(option_id)
+                );
+            });
+            assert_eq!(
+                LatestSnapshotTimestamp::<MockRuntime>::get(),
+                Some(SNAPSHOT_FREQUENCY)
+            );
+            assert_eq!(PendingSnapshotRound::<MockRuntime>::get(), None);
+            System::assert_last_event(Event::OptionsPricing(pallet::Event::SnapshotTaken {
+                timestamp: SNAPSHOT_FREQUENCY,
+            }));
+
+            run_to_block(20);
+            assert_eq!(history_timestamps(1), vec![SNAPSHOT_FREQUENCY]);
+
+            run_to_block(21);
+            assert_eq!(
+                history_timestamps(1),
+                vec![SNAPSHOT_FREQUENCY, 2 * SNAPSHOT_FREQUENCY]
+            );
+        });
+}
+
+#[test]
+fn test_snapshot_round_resumed_across_blocks() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            price_options_and_set_frequency(5);
+
+            // The weight budget allows two options per block
+            let copied_after_block = [(11, 2), (12, 4), (13, 5)];
+            copied_after_block.into_iter().for_each(|(block, copied)| {
+                run_to_block(block);
+                assert_eq!(SnapshotsHistory::<MockRuntime>::iter().count(), copied);
+            });
+
+            // All the snapshots of the round share its timestamp
+            (1..=5).for_each(|option_id| {
+                assert_eq!(history_timestamps(option_id), vec![SNAPSHOT_FREQUENCY]);
+            });
+            assert_eq!(PendingSnapshotRound::<MockRuntime>::get(), None);
+            assert_eq!(
+                LatestSnapshotTimestamp::<MockRuntime>::get(),
+                Some(SNAPSHOT_FREQUENCY)
+            );
+        });
+}
+
+#[test]
+fn test_snapshots_pruned_after_retention_period() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            price_options_and_set_frequency(1);
+
+            run_to_block(31);
+            assert_eq!(
+                history_timestamps(1),
+                vec![
+                    SNAPSHOT_FREQUENCY,
+                    2 * SNAPSHOT_FREQUENCY,
+                    3 * SNAPSHOT_FREQUENCY
+                ]
+            );
+
+            // Snapshots older than the retention period are removed by the next round
+            run_to_block(41);
+            assert_eq!(
+                history_timestamps(1),
+                vec![
+                    2 * SNAPSHOT_FREQUENCY,
+                    3 * SNAPSHOT_FREQUENCY,
+                    4 * SNAPSHOT_FREQUENCY
+                ]
+            );
+        });
+}
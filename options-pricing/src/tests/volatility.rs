@@ -0,0 +1,326 @@
+use crate::{
+    mocks::{
+        accounts::*,
+        assets::*,
+        runtime::{
+            Event, ExtBuilder, MaxPriceSamples, MockRuntime, OptionsPricing, Origin, System,
+            Timestamp,
+        },
+    },
+    pallet::{self, Decimal, Error, LatestSnapshots, PriceHistory, VolatilitySurfaces},
+    tests::*,
+    types::{VolatilitySurface, DEFAULT_VOLATILITY, MILLISECONDS_PER_YEAR},
+};
+
+use frame_support::{assert_noop, assert_ok, error::BadOrigin, BoundedVec};
+use sp_runtime::FixedPointNumber;
+
+// ----------------------------------------------------------------------------------------------------
+//		Helper functions
+// ----------------------------------------------------------------------------------------------------
+const DAY: Moment = 24 * 60 * 60 * 1000;
+
+fn decimal(numerator: i128, denominator: i128) -> Decimal {
+    Decimal::saturating_from_rational(numerator, denominator)
+}
+
+fn surface(
+    moneyness: Vec<Decimal>,
+    time_to_expiry: Vec<Moment>,
+    volatilities: Vec<Vec<Decimal>>,
+) -> VolatilitySurface<MockRuntime> {
+    VolatilitySurface {
+        moneyness: BoundedVec::try_from(moneyness).unwrap(),
+        time_to_expiry: BoundedVec::try_from(time_to_expiry).unwrap(),
+        volatilities: BoundedVec::try_from(
+            volatilities
+                .into_iter()
+                .map(|row| BoundedVec::try_from(row).unwrap())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap(),
+    }
+}
+
+// Moneyness 0.8 and 1.2, expiries in 30 and 90 days
+fn default_surface() -> VolatilitySurface<MockRuntime> {
+    surface(
+        vec![decimal(8, 10), decimal(12, 10)],
+        vec![30 * DAY, 90 * DAY],
+        vec![
+            vec![decimal(6, 10), decimal(4, 10)],
+            vec![decimal(5, 10), decimal(3, 10)],
+        ],
+    )
+}
+
+fn years(milliseconds: Moment) -> Decimal {
+    Decimal::saturating_from_rational(milliseconds, MILLISECONDS_PER_YEAR)
+}
+
+fn assert_close(actual: Decimal, expected: Decimal) {
+    assert!(
+        (actual.into_inner() - expected.into_inner()).abs() <= 10,
+        "expected {:?}, got {:?}",
+        expected,
+        actual
+    );
+}
+
+fn calculate_option_price(spot_price: Balance) {
+    let bs_params = BlackScholesParamsBuilder::default()
+        .base_asset_spot_price(spot_price)
+        .expiring_date(Timestamp::now() + 30 * DAY)
+        .build();
+
+    assert_ok!(OptionsPricing::calculate_option_price(
+        Origin::signed(ADMIN),
+        1_u128,
+        bs_params
+    ));
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Volatility Surface Tests
+// ----------------------------------------------------------------------------------------------------
+#[test]
+fn test_update_volatility_surface_success() {
+    ExtBuilder::default().build().execute_with(|| {
+        let surface = default_surface();
+
+        assert_ok!(OptionsPricing::update_volatility_surface(
+            Origin::signed(ADMIN),
+            BTC,
+            Some(surface.clone())
+        ));
+
+        assert_eq!(
+            VolatilitySurfaces::<MockRuntime>::get(BTC),
+            Some(surface.clone())
+        );
+        System::assert_last_event(Event::OptionsPricing(
+            pallet::Event::VolatilitySurfaceUpdated {
+                asset_id: BTC,
+                surface: Some(surface),
+            },
+        ));
+
+        assert_ok!(OptionsPricing::update_volatility_surface(
+            Origin::signed(ADMIN),
+            BTC,
+            None
+        ));
+
+        assert_eq!(VolatilitySurfaces::<MockRuntime>::get(BTC), None);
+        System::assert_last_event(Event::OptionsPricing(
+            pallet::Event::VolatilitySurfaceUpdated {
+                asset_id: BTC,
+                surface: None,
+            },
+        ));
+    });
+}
+
+#[test]
+fn test_update_volatility_surface_error_not_volatility_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            OptionsPricing::update_volatility_surface(
+                Origin::signed(ALICE),
+                BTC,
+                Some(default_surface())
+            ),
+            BadOrigin
+        );
+    });
+}
+
+#[test]
+fn test_update_volatility_surface_error_invalid_surface() {
+    ExtBuilder::default().build().execute_with(|| {
+        let iv = decimal(5, 10);
+        let invalid_surfaces = [
+            // Empty axes
+            surface(vec![], vec![30 * DAY], vec![vec![]]),
+            surface(vec![decimal(1, 1)], vec![], vec![]),
+            // Axes not strictly increasing
+            surface(
+                vec![decimal(1, 1), decimal(1, 1)],
+                vec![30 * DAY],
+                vec![vec![iv, iv]],
+            ),
+            surface(
+                vec![decimal(1, 1)],
+                vec![90 * DAY, 30 * DAY],
+                vec![vec![iv], vec![iv]],
+            ),
+            // Non positive moneyness
+            surface(
+                vec![decimal(0, 1), decimal(1, 1)],
+                vec![30 * DAY],
+                vec![vec![iv, iv]],
+            ),
+            // Dimensions don't match
+            surface(vec![decimal(1, 1)], vec![30 * DAY], vec![vec![iv, iv]]),
+            surface(
+                vec![decimal(1, 1)],
+                vec![30 * DAY],
+                vec![vec![iv], vec![iv]],
+            ),
+            // Non positive volatility
+            surface(
+                vec![decimal(1, 1)],
+                vec![30 * DAY],
+                vec![vec![decimal(0, 1)]],
+            ),
+        ];
+
+        invalid_surfaces.into_iter().for_each(|surface| {
+            assert_noop!(
+                OptionsPricing::update_volatility_surface(
+                    Origin::signed(ADMIN),
+                    BTC,
+                    Some(surface)
+                ),
+                Error::<MockRuntime>::InvalidVolatilitySurface
+            );
+        });
+    });
+}
+
+#[test]
+fn test_volatility_surface_interpolation() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(OptionsPricing::update_volatility_surface(
+            Origin::signed(ADMIN),
+            BTC,
+            Some(default_surface())
+        ));
+
+        let cases = [
+            // Points of the grid
+            (decimal(8, 10), 30 * DAY, decimal(6, 10)),
+            (decimal(12, 10), 90 * DAY, decimal(3, 10)),
+            // Interpolation along one axis
+            (decimal(1, 1), 30 * DAY, decimal(5, 10)),
+            (decimal(8, 10), 60 * DAY, decimal(55, 100)),
+            // Interpolation along both axes
+            (decimal(1, 1), 60 * DAY, decimal(45, 100)),
+            // Outside of the grid
+            (decimal(5, 10), 30 * DAY, decimal(6, 10)),
+            (decimal(2, 1), 1 * DAY, decimal(4, 10)),
+            (decimal(1, 1), 365 * DAY, decimal(4, 10)),
+        ];
+
+        cases
+            .into_iter()
+            .for_each(|(moneyness, time_to_expiry, expected)| {
+                let iv =
+                    OptionsPricing::get_volatility(BTC, moneyness, years(time_to_expiry)).unwrap();
+                assert_close(iv, expected);
+            });
+    });
+}
+
+#[test]
+fn test_snapshot_volatility_from_surface() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            assert_ok!(OptionsPricing::update_volatility_surface(
+                Origin::signed(ADMIN),
+                BTC,
+                Some(default_surface())
+            ));
+
+            // At the money option expiring in 30 days
+            calculate_option_price(50000u128 * UNIT);
+
+            let snapshot = LatestSnapshots::<MockRuntime>::get(1_u128).unwrap();
+            assert_eq!(snapshot.iv, decimal(5, 10));
+        });
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Realized Volatility Tests
+// ----------------------------------------------------------------------------------------------------
+#[test]
+fn test_snapshot_volatility_default_without_history() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            calculate_option_price(50000u128 * UNIT);
+
+            let snapshot = LatestSnapshots::<MockRuntime>::get(1_u128).unwrap();
+            assert_eq!(snapshot.iv, DEFAULT_VOLATILITY);
+            assert_eq!(OptionsPricing::realized_volatility(BTC), Ok(None));
+        });
+}
+
+#[test]
+fn test_snapshot_volatility_from_realized_volatility() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            let prices = [100u128, 110u128, 99u128];
+            prices.iter().enumerate().for_each(|(i, price)| {
+                run_to_block(i as u64 + 1);
+                calculate_option_price(price * UNIT);
+            });
+
+            let expected = ((1.1f64.ln().powi(2) + 0.9f64.ln().powi(2)) /
+                (2000f64 / MILLISECONDS_PER_YEAR as f64))
+                .sqrt();
+            let realized_volatility = OptionsPricing::realized_volatility(BTC).unwrap().unwrap();
+            let error = (realized_volatility.into_inner() as f64 / 1e18 - expected).abs();
+            assert!(error <= expected * 1e-9);
+
+            let snapshot = LatestSnapshots::<MockRuntime>::get(1_u128).unwrap();
+            assert_eq!(snapshot.iv, realized_volatility);
+
+            // A volatility surface takes precedence
+            assert_ok!(OptionsPricing::update_volatility_surface(
+                Origin::signed(ADMIN),
+                BTC,
+                Some(default_surface())
+            ));
+            assert_eq!(
+                OptionsPricing::get_volatility(BTC, decimal(1, 1), years(30 * DAY)),
+                Ok(decimal(5, 10))
+            );
+        });
+}
+
+#[test]
+fn test_price_history_sampling() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            // Samples closer than the sampling period are ignored
+            calculate_option_price(100u128 * UNIT);
+            calculate_option_price(110u128 * UNIT);
+            assert_eq!(
+                PriceHistory::<MockRuntime>::get(BTC).into_inner(),
+                vec![(1000, 100u128 * UNIT)]
+            );
+
+            // The oldest samples are dropped when the history is full
+            let samples = MaxPriceSamples::get() as u64 + 2;
+            (2..=samples).for_each(|block| {
+                run_to_block(block);
+                calculate_option_price(100u128 * UNIT + block as u128);
+            });
+
+            let history = PriceHistory::<MockRuntime>::get(BTC);
+            assert_eq!(history.len(), MaxPriceSamples::get() as usize);
+            assert_eq!(history.first(), Some(&(3000, 100u128 * UNIT + 3)));
+            assert_eq!(
+                history.last(),
+                Some(&(samples * 1000, 100u128 * UNIT + samples as u128))
+            );
+        });
+}
@@ -1,5 +1,6 @@
-use crate::{BalanceOf, Config, Decimal};
+use crate::{BalanceOf, Config, Decimal, MomentOf};
 use frame_support::pallet_prelude::*;
+use traits::options_pricing::Greeks;
 
 use sp_std::fmt::Debug;
 
@@ -12,18 +13,55 @@ use sp_std::fmt::Debug;
 pub struct Snapshot<T: Config> {
     pub interest_rate: Decimal,
     pub iv: Decimal,
-    pub delta: Decimal,
-    pub theta: Decimal,
-    pub rho: Decimal,
-    pub vega: Decimal,
-    pub gamma: Decimal,
+    pub greeks: Greeks,
     pub option_price: BalanceOf<T>,
     pub asset_spot_price: BalanceOf<T>,
     pub total_issuance_buyer: BalanceOf<T>,
     pub total_premium_paid: BalanceOf<T>,
 }
 
+/// Implied volatility surface of an asset.
+///
+/// The surface is a grid of annualized volatilities, with a row for each time to expiry and a
+/// column for each strike moneyness (strike price / spot price). Volatilities between the points
+/// of the grid are linearly interpolated, while outside of it the closest point is used.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct VolatilitySurface<T: Config> {
+    /// Strike moneyness of the columns, strictly increasing.
+    pub moneyness: BoundedVec<Decimal, T::MaxSurfacePoints>,
+    /// Time to expiry in milliseconds of the rows, strictly increasing.
+    pub time_to_expiry: BoundedVec<MomentOf<T>, T::MaxSurfacePoints>,
+    /// Volatilities, indexed first by time to expiry and then by moneyness.
+    pub volatilities: BoundedVec<BoundedVec<Decimal, T::MaxSurfacePoints>, T::MaxSurfacePoints>,
+}
+
+/// Progress of a snapshot round, copying the latest snapshot of each option into the history.
+///
+/// Rounds that don't fit in the snapshot weight budget of a block are resumed in the next ones.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct SnapshotRound<T: Config> {
+    /// Timestamp under which the snapshots of the round are stored in the history.
+    pub timestamp: MomentOf<T>,
+    /// Raw storage key of the last latest snapshot copied, `None` if the round hasn't started.
+    pub last_key: Option<BoundedVec<u8, ConstU32<MAX_SNAPSHOT_KEY_LENGTH>>>,
+}
+
 // ----------------------------------------------------------------------------------------------------
 //		Constants
 // ----------------------------------------------------------------------------------------------------
-// pub const SECONDS_PER_YEAR: Decimal = Decimal::from_inner(31536000);
+/// Milliseconds in a (365 days) year, used to annualize the time to expiry.
+pub const MILLISECONDS_PER_YEAR: u128 = 31_536_000_000;
+
+/// Annualized volatility (50%) used for assets without a volatility surface or enough price
+/// history to compute their realized volatility.
+pub const DEFAULT_VOLATILITY: Decimal = Decimal::from_inner(500_000_000_000_000_000);
+
+/// Time between two snapshots (one day in milliseconds) if the snapshot frequency is not set.
+pub const DEFAULT_SNAPSHOT_FREQUENCY: u32 = 86_400_000;
+
+/// Maximum length of the raw storage key of a latest snapshot, used to resume snapshot rounds.
+pub const MAX_SNAPSHOT_KEY_LENGTH: u32 = 128;
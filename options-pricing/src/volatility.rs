@@ -0,0 +1,215 @@
+use crate::{
+    math, types::*, AssetIdOf, BalanceOf, Config, Decimal, Error, Pallet, PriceHistory,
+    VolatilitySurfaces,
+};
+use frame_support::{pallet_prelude::*, traits::Time};
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating, Zero},
+    ArithmeticError, FixedPointNumber,
+};
+
+impl<T: Config> Pallet<T> {
+    /// Returns the annualized volatility to price an option on `asset_id` with the given strike
+    /// `moneyness` (strike price / spot price) and `time_to_expiry` in years.
+    ///
+    /// The volatility is interpolated from the implied volatility surface of the asset if it
+    /// has one. Otherwise the realized volatility of the asset is used, falling back to
+    /// [`DEFAULT_VOLATILITY`] if there isn't enough price history to compute it.
+    pub fn get_volatility(
+        asset_id: AssetIdOf<T>,
+        moneyness: Decimal,
+        time_to_expiry: Decimal,
+    ) -> Result<Decimal, DispatchError> {
+        if let Some(surface) = VolatilitySurfaces::<T>::get(asset_id) {
+            return Self::interpolate_volatility(&surface, moneyness, time_to_expiry)
+        }
+
+        Ok(Self::realized_volatility(asset_id)?.unwrap_or(DEFAULT_VOLATILITY))
+    }
+
+    /// Ensures the axes of `surface` are not empty and strictly increasing, that there is a
+    /// volatility for each of their points and that all of them are positive.
+    pub(crate) fn validate_volatility_surface(
+        surface: &VolatilitySurface<T>,
+    ) -> Result<(), Error<T>> {
+        let moneyness_is_valid = surface
+            .moneyness
+            .first()
+            .map_or(false, |first| first.is_positive()) &&
+            surface
+                .moneyness
+                .windows(2)
+                .all(|pair| pair.first() < pair.last());
+        let time_to_expiry_is_valid = !surface.time_to_expiry.is_empty() &&
+            surface
+                .time_to_expiry
+                .windows(2)
+                .all(|pair| pair.first() < pair.last());
+
+        ensure!(
+            moneyness_is_valid && time_to_expiry_is_valid,
+            Error::<T>::InvalidVolatilitySurface
+        );
+        ensure!(
+            surface.volatilities.len() == surface.time_to_expiry.len() &&
+                surface.volatilities.iter().all(|row| {
+                    row.len() == surface.moneyness.len() && row.iter().all(|iv| iv.is_positive())
+                }),
+            Error::<T>::InvalidVolatilitySurface
+        );
+
+        Ok(())
+    }
+
+    /// Interpolates the volatility surface bilinearly at the given `moneyness` and
+    /// `time_to_expiry` in years.
+    pub(crate) fn interpolate_volatility(
+        surface: &VolatilitySurface<T>,
+        moneyness: Decimal,
+        time_to_expiry: Decimal,
+    ) -> Result<Decimal, DispatchError> {
+        let time_axis = surface
+            .time_to_expiry
+            .iter()
+            .map(|time| Self::annualize(*time))
+            .collect::<Result<sp_std::vec::Vec<_>, _>>()?;
+
+        let (m0, m1, moneyness_weight) =
+            Self::interpolation_bracket(&surface.moneyness, moneyness)?;
+        let (t0, t1, time_weight) = Self::interpolation_bracket(&time_axis, time_to_expiry)?;
+
+        let volatility_at = |t: usize, m: usize| {
+            surface
+                .volatilities
+                .get(t)
+                .and_then(|row| row.get(m))
+                .copied()
+                .ok_or(Error::<T>::InvalidVolatilitySurface)
+        };
+
+        let iv0 = Self::lerp(
+            volatility_at(t0, m0)?,
+            volatility_at(t0, m1)?,
+            moneyness_weight,
+        )?;
+        let iv1 = Self::lerp(
+            volatility_at(t1, m0)?,
+            volatility_at(t1, m1)?,
+            moneyness_weight,
+        )?;
+
+        Self::lerp(iv0, iv1, time_weight)
+    }
+
+    /// Computes the annualized realized volatility of `asset_id` from its price history, as the
+    /// square root of the sum of the squared log returns divided by the time they span.
+    ///
+    /// Returns `None` if there are less than two samples or the prices haven't moved.
+    pub fn realized_volatility(asset_id: AssetIdOf<T>) -> Result<Option<Decimal>, DispatchError> {
+        let samples = PriceHistory::<T>::get(asset_id);
+
+        let (first, last) = match (samples.first(), samples.last()) {
+            (Some(first), Some(last)) if first.0 < last.0 => (first, last),
+            _ => return Ok(None),
+        };
+        let elapsed = Self::annualize(last.0.saturating_sub(first.0))?;
+        if elapsed.is_zero() {
+            return Ok(None)
+        }
+
+        let mut sum_squared_returns = Decimal::zero();
+        for ((_, previous_price), (_, price)) in samples.iter().zip(samples.iter().skip(1)) {
+            let log_return = math::ln(
+                Decimal::checked_from_rational(*price, *previous_price)
+                    .ok_or(Error::<T>::FailedConversion)?,
+            )?;
+            sum_squared_returns = log_return
+                .checked_mul(&log_return)
+                .and_then(|squared_return| sum_squared_returns.checked_add(&squared_return))
+                .ok_or(ArithmeticError::Overflow)?;
+        }
+
+        let variance = sum_squared_returns
+            .checked_div(&elapsed)
+            .ok_or(ArithmeticError::DivisionByZero)?;
+        let volatility = math::sqrt(variance)?;
+
+        if volatility.is_zero() {
+            return Ok(None)
+        }
+
+        Ok(Some(volatility))
+    }
+
+    /// Adds `price` to the price history of `asset_id`, dropping the oldest sample if the history
+    /// is full. Nothing is recorded if the latest sample is more recent than
+    /// `PriceSamplingPeriod` or the price is zero.
+    pub(crate) fn record_price_sample(asset_id: AssetIdOf<T>, price: BalanceOf<T>) {
+        if price.is_zero() {
+            return
+        }
+
+        let now = T::Time::now();
+        PriceHistory::<T>::mutate(asset_id, |samples| {
+            if let Some((latest, _)) = samples.last() {
+                if now < latest.saturating_add(T::PriceSamplingPeriod::get()) {
+                    return
+                }
+            }
+
+            if !samples.is_empty() && samples.len() >= T::MaxPriceSamples::get() as usize {
+                samples.remove(0);
+            }
+            // Can't fail since there is room for at least one sample
+            let _ = samples.try_push((now, price));
+        });
+    }
+
+    /// Returns the indexes of the points of `axis` surrounding `x` and the weight of the upper
+    /// one. Outside of the axis, both indexes point to its closest end.
+    fn interpolation_bracket(
+        axis: &[Decimal],
+        x: Decimal,
+    ) -> Result<(usize, usize, Decimal), DispatchError> {
+        let last = axis.len().saturating_sub(1);
+        let upper = axis
+            .iter()
+            .position(|point| *point > x)
+            .unwrap_or(axis.len());
+
+        if upper == 0 {
+            return Ok((0, 0, Decimal::zero()))
+        }
+        if upper > last {
+            return Ok((last, last, Decimal::zero()))
+        }
+
+        let lower = upper.saturating_sub(1);
+        let (lower_point, upper_point) = match (axis.get(lower), axis.get(upper)) {
+            (Some(lower_point), Some(upper_point)) => (*lower_point, *upper_point),
+            _ => return Err(Error::<T>::InvalidVolatilitySurface.into()),
+        };
+
+        let weight = x
+            .checked_sub(&lower_point)
+            .ok_or(ArithmeticError::Underflow)?
+            .checked_div(
+                &upper_point
+                    .checked_sub(&lower_point)
+                    .ok_or(ArithmeticError::Underflow)?,
+            )
+            .ok_or(ArithmeticError::DivisionByZero)?;
+
+        Ok((lower, upper, weight))
+    }
+
+    /// Linear interpolation between `a` and `b`.
+    fn lerp(a: Decimal, b: Decimal, weight: Decimal) -> Result<Decimal, DispatchError> {
+        let difference = b.checked_sub(&a).ok_or(ArithmeticError::Underflow)?;
+        let offset = difference
+            .checked_mul(&weight)
+            .ok_or(ArithmeticError::Overflow)?;
+
+        Ok(a.checked_add(&offset).ok_or(ArithmeticError::Overflow)?)
+    }
+}
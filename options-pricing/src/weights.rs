@@ -8,6 +8,8 @@ pub trait WeightInfo {
     fn calculate_option_greeks() -> Weight;
     fn update_interest_rate() -> Weight;
     fn update_snapshot_frequency() -> Weight;
+    fn update_volatility_surface() -> Weight;
+    fn take_option_snapshot(h: u32) -> Weight;
 }
 
 /// Weights for pallet_tokenized_options using the Substrate node and recommended hardware.
@@ -28,6 +30,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn update_snapshot_frequency() -> Weight {
         WEIGHT as Weight
     }
+
+    fn update_volatility_surface() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn take_option_snapshot(h: u32) -> Weight {
+        (WEIGHT as Weight).saturating_add((h as Weight).saturating_mul(100))
+    }
 }
 
 // For backwards compatibility and tests
@@ -47,4 +57,12 @@ impl WeightInfo for () {
     fn update_snapshot_frequency() -> Weight {
         WEIGHT as Weight
     }
+
+    fn update_volatility_surface() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn take_option_snapshot(h: u32) -> Weight {
+        (WEIGHT as Weight).saturating_add((h as Weight).saturating_mul(100))
+    }
 }
@@ -0,0 +1,35 @@
+//! # Tokenized Options Runtime API
+//!
+//! Runtime API for querying the greeks of the options managed by the Tokenized Options Pallet,
+//! both for single options and aggregated for each vault, and the positions of buyers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+use traits::{options_pricing::Greeks, tokenized_options::BuyerPosition};
+
+sp_api::decl_runtime_apis! {
+    /// Queries exposed by the Tokenized Options Pallet to clients.
+    pub trait TokenizedOptionsRuntimeApi<AccountId, AssetId, Balance, OptionId>
+    where
+        AccountId: Codec,
+        AssetId: Codec,
+        Balance: Codec,
+        OptionId: Codec,
+    {
+        /// Returns the greeks of a single option at the current spot price.
+        fn get_option_greeks(option_id: OptionId) -> Result<Greeks, DispatchError>;
+
+        /// Returns the net greeks of the vault of `asset_id`, grouped by the base asset of the
+        /// options it collateralizes. The vault is short the options bought from it, so its
+        /// greeks are the opposite of theirs.
+        fn get_vault_greeks(asset_id: AssetId) -> Result<Vec<(AssetId, Greeks)>, DispatchError>;
+
+        /// Returns the positions of `account` in the options it bought or received, with the
+        /// premium paid for the options held and the amount of options exercised.
+        fn get_buyer_positions(account: AccountId) -> Vec<(OptionId, BuyerPosition<Balance>)>;
+    }
+}
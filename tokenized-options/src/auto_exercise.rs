@@ -0,0 +1,70 @@
+use crate::{
+    types::*, AccountIdOf, AssetsOf, AutoExerciseOptOut, Config, Error, Event, OptionIdOf, Pallet,
+};
+use frame_support::{
+    pallet_prelude::*,
+    traits::fungibles::{Inspect, Transfer},
+};
+use sp_runtime::{
+    traits::{CheckedSub, Zero},
+    ArithmeticError, DispatchError,
+};
+use traits::tokenized_options::*;
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn do_exercise_for(
+        keeper: &AccountIdOf<T>,
+        holder: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+        option: &mut OptionToken<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            option.status == Status::Exercise,
+            Error::<T>::NotIntoExerciseWindow
+        );
+
+        // Only the payout of cash settled options in the money can be paid without the holder
+        ensure!(
+            option.settlement_type == SettlementType::Cash && !option.exercise_amount.is_zero(),
+            Error::<T>::OptionCannotBeAutoExercised
+        );
+
+        ensure!(
+            !AutoExerciseOptOut::<T>::contains_key(holder),
+            Error::<T>::AutoExerciseDisabled
+        );
+
+        let option_amount = AssetsOf::<T>::balance(option_id, holder);
+
+        let (asset_id, payout) = Self::settled_exercise_amount(option_amount, option)?;
+        let protocol_fee = Self::calculate_exercise_fee(
+            option_amount,
+            payout,
+            option.base_asset_spot_price,
+            option,
+        )?;
+
+        Self::do_exercise_option(holder, option_amount, option_id, option)?;
+
+        // The keeper is rewarded with a share of the payout received by the holder
+        let reward = T::ExerciseKeeperReward::get().mul_floor(
+            payout
+                .checked_sub(&protocol_fee)
+                .ok_or(ArithmeticError::Underflow)?,
+        );
+
+        if !reward.is_zero() {
+            AssetsOf::<T>::transfer(asset_id, holder, keeper, reward, false)?;
+        }
+
+        Self::deposit_event(Event::ExercisedFor {
+            keeper: keeper.clone(),
+            user: holder.clone(),
+            option_id,
+            option_amount,
+            reward,
+        });
+
+        Ok(())
+    }
+}
@@ -54,6 +54,12 @@ fn set_oracle_price<T: Config + pallet_oracle::Config>(asset_id: T::MayBeAssetId
 	);
 }
 
+fn remove_oracle_price<T: Config + pallet_oracle::Config>(asset_id: T::MayBeAssetId) {
+	let asset_id: T::AssetId = encode_decode(asset_id);
+
+	pallet_oracle::Prices::<T>::remove(asset_id);
+}
+
 fn initial_setup<T: Config + pallet_timestamp::Config>() {
 	System::<T>::set_block_number(0u32.into());
 	System::<T>::on_initialize(System::<T>::block_number());
@@ -103,6 +109,7 @@ fn valid_option_config<T: Config>() -> OptionConfigOf<T> {
 		quote_asset_strike_price: UNIT.into(),
 		option_type: OptionType::Call,
 		exercise_type: ExerciseType::European,
+		settlement_type: SettlementType::Cash,
 		expiring_date: recode_unwrap_u128(30000u64),
 		// Use this when https://github.com/paritytech/substrate/pull/10128 is merged
 		// epoch: Epoch {
@@ -144,6 +151,7 @@ fn default_option_benchmarking_setup<T: Config + pallet_timestamp::Config>() ->
 		option_config.option_type,
 		option_config.expiring_date,
 		option_config.exercise_type,
+		option_config.settlement_type,
 	);
 
 	produce_block::<T>(2u32.into(), (2u32 * MINIMUM_PERIOD).into());
@@ -151,6 +159,103 @@ fn default_option_benchmarking_setup<T: Config + pallet_timestamp::Config>() ->
 	OptionHashToOptionId::<T>::get(option_hash).unwrap()
 }
 
+fn option_series_benchmarking_setup<T: Config + pallet_timestamp::Config>(
+	rollover_sellers: u32,
+) -> OptionIdOf<T> {
+	let origin = OriginFor::<T>::from(RawOrigin::Root);
+
+	// Same epoch as `valid_option_config`
+	let series = OptionSeriesOf::<T> {
+		base_asset_id: recode_unwrap_u128(B),
+		quote_asset_id: recode_unwrap_u128(C),
+		quote_asset_strike_price: UNIT.into(),
+		option_type: OptionType::Call,
+		exercise_type: ExerciseType::European,
+		settlement_type: SettlementType::Cash,
+		strike_selection: StrikeSelection::AtTheMoney,
+		base_asset_amount_per_option: UNIT.into(),
+		quote_asset_amount_per_option: UNIT.into(),
+		start: recode_unwrap_u128(0u64),
+		cadence: recode_unwrap_u128(48000u64),
+		deposit_duration: recode_unwrap_u128(12000u64),
+		purchase_duration: recode_unwrap_u128(18000u64),
+		exercise_duration: recode_unwrap_u128(18000u64),
+	};
+
+	TokenizedOptions::<T>::create_option_series(origin, series).unwrap();
+
+	for i in 0..rollover_sellers {
+		let seller_account: T::AccountId = account("SELLER", i, 0);
+		let seller_origin = OriginFor::<T>::from(RawOrigin::Signed(seller_account.clone()));
+
+		AssetsOf::<T>::mint_into(recode_unwrap_u128(B), &seller_account, UNIT.into()).unwrap();
+		TokenizedOptions::<T>::update_collateral_rollover(seller_origin, 0, true).unwrap();
+	}
+
+	OptionIdToSeries::<T>::iter()
+		.find(|(_, series_id)| *series_id == 0)
+		.map(|(option_id, _)| option_id)
+		.unwrap()
+}
+
+// Sells the option of `default_option_benchmarking_setup` from the vault and from
+// `margin_writers` margined writers, buys all of it and moves to the start of the exercise phase
+// with the option in the money, so that settlement withdraws from the vault and from the margins
+fn settlement_benchmarking_setup<T: Config + pallet_oracle::Config + pallet_timestamp::Config>(
+	margin_writers: u32,
+) -> OptionIdOf<T> {
+	let seller_account: T::AccountId = whitelisted_caller::<T::AccountId>();
+	let seller_origin = OriginFor::<T>::from(RawOrigin::Signed(seller_account.clone()));
+
+	let buyer_account: T::AccountId = account("BUYER", 1, 0);
+	let buyer_origin = OriginFor::<T>::from(RawOrigin::Signed(buyer_account.clone()));
+
+	vault_benchmarking_setup::<T>(recode_unwrap_u128(B), 50_000);
+	vault_benchmarking_setup::<T>(recode_unwrap_u128(C), 1);
+	AssetsOf::<T>::mint_into(recode_unwrap_u128(B), &seller_account, UNIT.into()).unwrap();
+	AssetsOf::<T>::mint_into(
+		recode_unwrap_u128(C),
+		&buyer_account,
+		(UNIT * 1000u128 * (margin_writers as u128 + 1)).into(),
+	)
+	.unwrap();
+
+	let option_id = default_option_benchmarking_setup::<T>();
+	let option_amount: BalanceOf<T> = 1u128.into();
+
+	TokenizedOptions::<T>::sell_option(seller_origin, option_amount, option_id).unwrap();
+
+	for i in 0..margin_writers {
+		let writer_account: T::AccountId = account("WRITER", i, 0);
+		let writer_origin = OriginFor::<T>::from(RawOrigin::Signed(writer_account.clone()));
+
+		AssetsOf::<T>::mint_into(recode_unwrap_u128(B), &writer_account, UNIT.into()).unwrap();
+		TokenizedOptions::<T>::sell_option_with_margin(
+			writer_origin,
+			option_amount,
+			option_id,
+			UNIT.into(),
+		)
+		.unwrap();
+	}
+
+	produce_block::<T>(3u32.into(), (3u32 * MINIMUM_PERIOD).into());
+
+	TokenizedOptions::<T>::buy_option(
+		buyer_origin,
+		(margin_writers as u128 + 1).into(),
+		option_id,
+		BalanceOf::<T>::max_value(),
+	)
+	.unwrap();
+
+	// Set timestamp to 30000 (exercise phase can start)
+	produce_block::<T>(5u32.into(), (5u32 * MINIMUM_PERIOD).into());
+	set_oracle_price::<T>(recode_unwrap_u128(B), 60_000 * (UNIT as u64));
+
+	option_id
+}
+
 // ----------------------------------------------------------------------------------------------------
 //		Benchmark tests
 // ----------------------------------------------------------------------------------------------------
@@ -203,6 +308,7 @@ benchmarks! {
 			quote_asset_strike_price: UNIT.into(),
 			option_type: OptionType::Call,
 			exercise_type: ExerciseType::European,
+			settlement_type: SettlementType::Cash,
 			expiring_date: recode_unwrap_u128(36000u64),
 			epoch: Epoch {
 				deposit: recode_unwrap_u128(0u64),
@@ -310,14 +416,18 @@ benchmarks! {
 		TokenizedOptions::<T>::buy_option(
 			buyer_origin,
 			option_amount,
-			option_id
+			option_id,
+			BalanceOf::<T>::max_value()
 		)?
 	}
 	verify {
+		let option_premium = BalanceOf::<T>::from(UNIT * 1000u128)
+			- AssetsOf::<T>::balance(recode_unwrap_u128(C), &buyer_account);
 		assert_last_event::<T>(Event::BuyOption {
 			user: buyer_account,
 			option_amount,
 			option_id,
+			option_premium,
 		}.into())
 	}
 
@@ -341,7 +451,7 @@ benchmarks! {
 		TokenizedOptions::<T>::sell_option(seller_origin, option_amount, option_id).unwrap();
 		produce_block::<T>(3u32.into(), (3u32 * MINIMUM_PERIOD).into());
 
-		TokenizedOptions::<T>::buy_option(buyer_origin.clone(), option_amount, option_id).unwrap();
+		TokenizedOptions::<T>::buy_option(buyer_origin.clone(), option_amount, option_id, BalanceOf::<T>::max_value()).unwrap();
 
 		// Set timestamp to 5000 (exercise phase can start)
 		// This can be deleted when https://github.com/paritytech/substrate/pull/10128 is merged
@@ -384,7 +494,7 @@ benchmarks! {
 		TokenizedOptions::<T>::sell_option(seller_origin.clone(), option_amount, option_id).unwrap();
 		produce_block::<T>(3u32.into(), (3u32 * MINIMUM_PERIOD).into());
 
-		TokenizedOptions::<T>::buy_option(buyer_origin.clone(), option_amount, option_id).unwrap();
+		TokenizedOptions::<T>::buy_option(buyer_origin.clone(), option_amount, option_id, BalanceOf::<T>::max_value()).unwrap();
 
 		// Set timestamp to 5000 (exercise phase can start)
 		// This can be deleted when https://github.com/paritytech/substrate/pull/10128 is merged
@@ -409,6 +519,112 @@ benchmarks! {
 		}.into())
 	}
 
+	option_deposit_start {
+		let r in 0 .. T::MaxRolloverSellers::get();
+
+		initial_setup::<T>();
+
+		vault_benchmarking_setup::<T>(recode_unwrap_u128(B), 50_000);
+		vault_benchmarking_setup::<T>(recode_unwrap_u128(C), 1);
+
+		let option_id = option_series_benchmarking_setup::<T>(r);
+
+		// Collateral withdrawn from the previous option of the series
+		for i in 0..r {
+			let seller_account: T::AccountId = account("SELLER", i, 0);
+			PendingRollovers::<T>::insert(0, seller_account, BalanceOf::<T>::from(UNIT));
+		}
+	}: {
+		// The option passes to deposit phase
+		TokenizedOptions::<T>::on_initialize(System::<T>::block_number())
+	}
+	verify {
+		assert_eq!(Sellers::<T>::iter_prefix(option_id).count(), r as usize);
+		assert_eq!(PendingRollovers::<T>::iter_prefix(0).count(), 0);
+	}
+
+	option_purchase_start {
+		initial_setup::<T>();
+
+		vault_benchmarking_setup::<T>(recode_unwrap_u128(B), 50_000);
+		vault_benchmarking_setup::<T>(recode_unwrap_u128(C), 1);
+
+		let option_id = default_option_benchmarking_setup::<T>();
+	}: {
+		// The option passes to purchase phase
+		TokenizedOptions::<T>::on_initialize(System::<T>::block_number())
+	}
+	verify {
+		assert_last_event::<T>(Event::OptionPurchaseStart { option_id }.into())
+	}
+
+	option_exercise_start {
+		let m in 0 .. T::MaxMarginWriters::get();
+
+		initial_setup::<T>();
+
+		let option_id = settlement_benchmarking_setup::<T>(m);
+	}: {
+		// The option passes to exercise phase and is settled
+		TokenizedOptions::<T>::on_initialize(System::<T>::block_number())
+	}
+	verify {
+		assert_last_event::<T>(Event::OptionExerciseStart { option_id }.into())
+	}
+
+	retry_pending_settlement {
+		let m in 0 .. T::MaxMarginWriters::get();
+
+		initial_setup::<T>();
+
+		let option_id = settlement_benchmarking_setup::<T>(m);
+
+		// Settlement fails without the oracle price
+		remove_oracle_price::<T>(recode_unwrap_u128(B));
+		produce_block::<T>(6u32.into(), (6u32 * MINIMUM_PERIOD).into());
+		set_oracle_price::<T>(recode_unwrap_u128(B), 60_000 * (UNIT as u64));
+	}: {
+		TokenizedOptions::<T>::on_initialize(System::<T>::block_number())
+	}
+	verify {
+		assert!(!PendingSettlements::<T>::contains_key(option_id));
+		assert_last_event::<T>(Event::OptionExerciseStart { option_id }.into())
+	}
+
+	option_end {
+		let r in 0 .. T::MaxRolloverSellers::get();
+
+		initial_setup::<T>();
+
+		vault_benchmarking_setup::<T>(recode_unwrap_u128(B), 50_000);
+		vault_benchmarking_setup::<T>(recode_unwrap_u128(C), 1);
+
+		let option_id = option_series_benchmarking_setup::<T>(r);
+		let option_amount: BalanceOf<T> = 1u128.into();
+
+		// During this block's on_initialize, the option passes to deposit phase
+		produce_block::<T>(2u32.into(), (2u32 * MINIMUM_PERIOD).into());
+
+		for i in 0..r {
+			let seller_account: T::AccountId = account("SELLER", i, 0);
+			let seller_origin = OriginFor::<T>::from(RawOrigin::Signed(seller_account));
+			TokenizedOptions::<T>::sell_option(seller_origin, option_amount, option_id).unwrap();
+		}
+
+		produce_block::<T>(3u32.into(), (3u32 * MINIMUM_PERIOD).into());
+		produce_block::<T>(5u32.into(), (5u32 * MINIMUM_PERIOD).into());
+
+		// Set timestamp to 48000 (the option can end)
+		produce_block::<T>(6u32.into(), (8u32 * MINIMUM_PERIOD).into());
+	}: {
+		// The option ends and its sellers roll over into the next option of the series
+		TokenizedOptions::<T>::on_initialize(System::<T>::block_number())
+	}
+	verify {
+		assert_eq!(OptionIdToSeries::<T>::iter().count(), 2);
+		assert_eq!(PendingRollovers::<T>::iter_prefix(0).count(), r as usize);
+	}
+
 }
 
 impl_benchmark_test_suite!(
@@ -0,0 +1,93 @@
+use crate::{types::*, AccountIdOf, AssetsOf, BalanceOf, Config, Error, Event, OptionIdOf, Pallet};
+use frame_support::{
+    pallet_prelude::*,
+    traits::fungibles::{Inspect, Mutate, Transfer},
+};
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedSub, Zero},
+    ArithmeticError, DispatchError,
+};
+use traits::tokenized_options::*;
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn do_sell_back_option(
+        from: &AccountIdOf<T>,
+        option_amount: BalanceOf<T>,
+        option_id: OptionIdOf<T>,
+        min_proceeds: BalanceOf<T>,
+        option: &mut OptionToken<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            option_amount != BalanceOf::<T>::zero(),
+            Error::<T>::CannotPassZeroOptionAmount
+        );
+
+        // Options can only be sold back while they can be bought
+        ensure!(
+            option.status == Status::Purchase,
+            Error::<T>::NotIntoPurchaseWindow
+        );
+
+        ensure!(
+            AssetsOf::<T>::balance(option_id, from) >= option_amount,
+            Error::<T>::UserHasNotEnoughOptionTokens
+        );
+
+        let proceeds = Self::calculate_buyback_proceeds(option_id, option_amount, option)?;
+
+        ensure!(
+            proceeds >= min_proceeds,
+            Error::<T>::ProceedsBelowMinProceeds
+        );
+
+        // Buybacks are paid from the premium collected for the option, so that sellers never
+        // pay more than they received
+        let new_total_buyback_paid = option
+            .total_buyback_paid
+            .checked_add(&proceeds)
+            .ok_or(ArithmeticError::Overflow)?;
+
+        ensure!(
+            new_total_buyback_paid <= option.total_premium_paid,
+            Error::<T>::NotEnoughPremiumForBuyback
+        );
+
+        option.total_buyback_paid = new_total_buyback_paid;
+
+        // Burn the options, which become available for sale again
+        AssetsOf::<T>::burn_from(option_id, from, option_amount)?;
+        Self::remove_from_buyer_position(from, option_id, option_amount, false)?;
+
+        let stablecoin_id = T::StablecoinAssetId::get();
+        AssetsOf::<T>::transfer(
+            stablecoin_id,
+            &Self::account_id(stablecoin_id),
+            from,
+            proceeds,
+            true,
+        )?;
+
+        Self::deposit_event(Event::SellBackOption {
+            user: from.clone(),
+            option_amount,
+            option_id,
+            proceeds,
+        });
+
+        Ok(())
+    }
+
+    /// Computes the amount paid for buying back `option_amount` options: their current premium
+    /// minus the [`BuybackSpread`](Config::BuybackSpread), which is kept by sellers.
+    pub fn calculate_buyback_proceeds(
+        option_id: OptionIdOf<T>,
+        option_amount: BalanceOf<T>,
+        option: &OptionToken<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let option_premium = Self::calculate_option_premium(option_id, option_amount, option)?;
+
+        option_premium
+            .checked_sub(&T::BuybackSpread::get().mul_ceil(option_premium))
+            .ok_or_else(|| ArithmeticError::Underflow.into())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    types::*, AccountIdOf, AssetsOf, Buyers, CleanupHoldersOf, Config, Error, Event,
+    MarginPositions, OptionHashToOptionId, OptionIdOf, OptionIdToOption, OptionIdToSeries, Pallet,
+    PendingSettlements, RetiredOptionAssets, Sellers,
+};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{
+        fungibles::{Inspect, Mutate},
+        Time,
+    },
+};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    DispatchError,
+};
+use sp_std::vec::Vec;
+use traits::tokenized_options::*;
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn do_clean_up_option(
+        option_id: OptionIdOf<T>,
+        holders: CleanupHoldersOf<T>,
+    ) -> Result<(), DispatchError> {
+        let option =
+            OptionIdToOption::<T>::get(option_id).ok_or(Error::<T>::OptionDoesNotExists)?;
+
+        let max_batch = T::MaxCleanupBatch::get();
+
+        ensure!(
+            option.status == Status::End &&
+                T::Time::now() >= option.epoch.end.saturating_add(T::ClaimWindow::get()),
+            Error::<T>::ClaimWindowNotOver
+        );
+
+        // Options covering netted positions are held by the protocol until those positions are
+        // released, which needs the covering option to exist
+        ensure!(
+            AssetsOf::<T>::balance(option_id, &Self::account_id(option_id)).is_zero(),
+            Error::<T>::OptionStillPledged
+        );
+
+        // Collateral and premium not withdrawn by sellers
+        let beneficiary = T::UnclaimedFundsAccount::get();
+        let sellers: Vec<AccountIdOf<T>> = Sellers::<T>::iter_key_prefix(option_id)
+            .take(max_batch as usize)
+            .collect();
+
+        for seller in sellers {
+            Sellers::<T>::try_mutate(option_id, &seller, |position| {
+                Self::release_seller_position(&seller, &beneficiary, option_id, &option, position)
+            })?;
+
+            Self::deposit_event(Event::UnclaimedCollateralReleased {
+                user: seller,
+                option_id,
+            });
+        }
+
+        // Margin left to writers that did not withdraw it
+        let writers: Vec<AccountIdOf<T>> = MarginPositions::<T>::iter_key_prefix(option_id)
+            .take(max_batch as usize)
+            .collect();
+
+        for writer in writers {
+            if let Some(position) = MarginPositions::<T>::take(option_id, &writer) {
+                Self::release_margin_position(&beneficiary, &option, position)?;
+            }
+
+            Self::deposit_event(Event::UnclaimedCollateralReleased {
+                user: writer,
+                option_id,
+            });
+        }
+
+        // Option tokens not exercised, which are worthless now
+        for holder in holders {
+            // Options held by the protocol are not unexercised options of a holder
+            if Self::is_protocol_account(&holder) {
+                continue
+            }
+
+            let option_amount = AssetsOf::<T>::balance(option_id, &holder);
+
+            if !option_amount.is_zero() {
+                AssetsOf::<T>::burn_from(option_id, &holder, option_amount)?;
+            }
+
+            Buyers::<T>::remove(&holder, option_id);
+        }
+
+        if Sellers::<T>::iter_key_prefix(option_id).next().is_none() &&
+            MarginPositions::<T>::iter_key_prefix(option_id)
+                .next()
+                .is_none() &&
+            AssetsOf::<T>::total_issuance(option_id).is_zero()
+        {
+            Self::remove_option(option_id, &option);
+        }
+
+        Ok(())
+    }
+
+    /// Removes a cleaned up option from storage and retires its option asset, whose supply has
+    /// been burned.
+    fn remove_option(option_id: OptionIdOf<T>, option: &OptionToken<T>) {
+        let option_hash = Self::generate_id(
+            option.base_asset_id,
+            option.quote_asset_id,
+            option.base_asset_strike_price,
+            option.quote_asset_strike_price,
+            option.option_type,
+            option.expiring_date,
+            option.exercise_type,
+            option.settlement_type,
+        );
+
+        OptionHashToOptionId::<T>::remove(option_hash);
+        OptionIdToOption::<T>::remove(option_id);
+        OptionIdToSeries::<T>::remove(option_id);
+        PendingSettlements::<T>::remove(option_id);
+        RetiredOptionAssets::<T>::insert(option_id, ());
+
+        Self::deposit_event(Event::OptionCleanedUp { option_id });
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    AccountIdOf, AssetIdOf, AssetsOf, BalanceOf, Config, Error, Event, Pallet, VaultIdOf, VaultOf,
+};
+use composable_traits::vault::{FundsAvailability, StrategicVault, Vault};
+use frame_support::{pallet_prelude::*, traits::fungibles::Inspect};
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, Zero},
+    DispatchError,
+};
+use sp_std::cmp::min;
+use traits::collateral_strategy::CollateralStrategy;
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn do_rebalance_collateral_strategy(
+        asset_id: AssetIdOf<T>,
+    ) -> Result<(), DispatchError> {
+        let vault_id =
+            Self::asset_id_to_vault_id(asset_id).ok_or(Error::<T>::AssetVaultDoesNotExists)?;
+        let strategy_account = Self::strategy_account_id(asset_id);
+
+        // All the funds go back to the vault before being allocated again, so the yield
+        // increases the value of the shares of sellers instead of the principal of the strategy
+        let harvested = T::CollateralStrategy::harvest(asset_id, &strategy_account)?;
+        Self::return_strategy_funds(
+            asset_id,
+            &vault_id,
+            T::CollateralStrategy::balance(asset_id),
+        )?;
+
+        if !harvested.is_zero() {
+            <VaultOf<T> as StrategicVault>::deposit(&vault_id, &strategy_account, harvested)?;
+        }
+
+        if let FundsAvailability::Withdrawable(amount) =
+            <VaultOf<T> as StrategicVault>::available_funds(&vault_id, &strategy_account)?
+        {
+            if !amount.is_zero() {
+                <VaultOf<T> as StrategicVault>::withdraw(&vault_id, &strategy_account, amount)?;
+                T::CollateralStrategy::deposit(asset_id, &strategy_account, amount)?;
+            }
+        }
+
+        Self::deposit_event(Event::CollateralStrategyRebalanced {
+            asset_id,
+            harvested,
+            allocated: T::CollateralStrategy::balance(asset_id),
+        });
+
+        Ok(())
+    }
+
+    /// Recalls from the strategy the collateral missing in the vault of `asset_id` for
+    /// withdrawing `amount` from it.
+    pub(crate) fn recall_strategy_funds(
+        asset_id: AssetIdOf<T>,
+        vault_id: &VaultIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        let idle_amount = AssetsOf::<T>::balance(asset_id, &VaultOf::<T>::account_id(vault_id));
+
+        if idle_amount >= amount {
+            return Ok(())
+        }
+
+        let recalled_amount =
+            Self::return_strategy_funds(asset_id, vault_id, amount.saturating_sub(idle_amount))?;
+
+        if !recalled_amount.is_zero() {
+            Self::deposit_event(Event::StrategyFundsRecalled {
+                asset_id,
+                amount: recalled_amount,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Withdraws up to `amount` of principal from the strategy and deposits it back into the
+    /// vault, returning the amount moved.
+    fn return_strategy_funds(
+        asset_id: AssetIdOf<T>,
+        vault_id: &VaultIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let amount = min(amount, T::CollateralStrategy::balance(asset_id));
+
+        if amount.is_zero() {
+            return Ok(amount)
+        }
+
+        let strategy_account = Self::strategy_account_id(asset_id);
+        T::CollateralStrategy::withdraw(asset_id, &strategy_account, amount)?;
+        <VaultOf<T> as StrategicVault>::deposit(vault_id, &strategy_account, amount)?;
+
+        Ok(amount)
+    }
+
+    /// Account moving the collateral of the vault of `asset_id` in and out of the
+    /// [`CollateralStrategy`](Config::CollateralStrategy). It is the only strategy the vault can
+    /// allocate funds to.
+    pub fn strategy_account_id(asset_id: AssetIdOf<T>) -> AccountIdOf<T> {
+        T::PalletId::get().into_sub_account_truncating((b"strategy", asset_id))
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    types::*, AccountIdOf, AssetIdOf, AssetsOf, BalanceOf, Config, Event, OptionIdOf, Pallet,
+    ProtocolFees,
+};
+use composable_traits::currency::LocalAssets;
+use frame_support::{pallet_prelude::*, traits::fungibles::Transfer};
+use sp_arithmetic::Rounding;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    DispatchError,
+};
+use sp_std::cmp::min;
+use traits::tokenized_options::*;
+
+impl<T: Config> Pallet<T> {
+    /// Computes the protocol fee taken from the premium paid for `option_amount` options.
+    pub fn calculate_premium_fee(
+        option_amount: BalanceOf<T>,
+        option_premium: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let fees = ProtocolFees::<T>::get();
+
+        min(
+            fees.premium_fee.mul_floor(option_premium),
+            fees.max_fee_per_option.saturating_mul(option_amount),
+        )
+    }
+
+    /// Computes the protocol fee taken from the payout of `option_amount` options exercised at
+    /// `base_asset_spot_price`. The cap of the fee is converted to base asset for call options,
+    /// whose payout is in base asset.
+    pub fn calculate_exercise_fee(
+        option_amount: BalanceOf<T>,
+        payout: BalanceOf<T>,
+        base_asset_spot_price: BalanceOf<T>,
+        option: &OptionToken<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let fees = ProtocolFees::<T>::get();
+
+        let max_fee_per_option = match option.option_type {
+            OptionType::Call => Self::convert_and_multiply_by_rational(
+                fees.max_fee_per_option,
+                T::LocalAssets::unit::<BalanceOf<T>>(option.base_asset_id)?,
+                base_asset_spot_price,
+                Rounding::Down,
+            )?,
+            OptionType::Put => fees.max_fee_per_option,
+        };
+
+        Ok(min(
+            fees.exercise_fee.mul_floor(payout),
+            max_fee_per_option.saturating_mul(option_amount),
+        ))
+    }
+
+    /// Transfers a protocol fee paid by `user` from `source` to the
+    /// [`FeeAccount`](Config::FeeAccount).
+    pub(crate) fn pay_protocol_fee(
+        source: &AccountIdOf<T>,
+        user: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+        asset_id: AssetIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        if amount.is_zero() {
+            return Ok(())
+        }
+
+        AssetsOf::<T>::transfer(asset_id, source, &T::FeeAccount::get(), amount, true)?;
+
+        Self::deposit_event(Event::ProtocolFeePaid {
+            user: user.clone(),
+            option_id,
+            asset_id,
+            amount,
+        });
+
+        Ok(())
+    }
+}
@@ -58,20 +58,89 @@
 //!
 //! - [`exercise_option`](Pallet::exercise_option): burn the option tokens from user's account and
 //!   transfer buyer's profit into
-//! buyer's account.
+//! buyer's account. American options can also be exercised before expiry, at the current price.
 //|
 //! - [`withdraw_collateral`](Pallet::withdraw_collateral): withdraw seller's deposited collateral
 //!   and its part of the premium.
 //!
+//! - [`sell_back_option`](Pallet::sell_back_option): sell option tokens back to the protocol during
+//!   the purchase window, at the current price minus a spread.
+//!
+//! - [`transfer_option`](Pallet::transfer_option): transfer option tokens keeping the buyer
+//!   positions of the sender and the receiver up to date.
+//!
+//! - [`settle_option_with_price`](Pallet::settle_option_with_price): settle an option whose
+//!   settlement keeps failing at a price given by the protocol, after a grace period.
+//!
+//! - [`create_option_series`](Pallet::create_option_series): create a recurring series of options.
+//!   The next option of the series is created when the previous one ends, with a strike selected
+//!   from the oracle price.
+//!
+//! - [`stop_option_series`](Pallet::stop_option_series): stop creating new options for a series.
+//!
+//! - [`update_collateral_rollover`](Pallet::update_collateral_rollover): opt in or out of rolling
+//!   the seller's collateral from each option of a series to the next one.
+//!
+//! - [`clean_up_option`](Pallet::clean_up_option): release the positions and burn the option tokens
+//!   left after the claim window of an option, then remove it from storage.
+//!
+//! - [`execute_strategy`](Pallet::execute_strategy): buy and sell several options sharing the same
+//!   expiring date at once, netting the collateral of sold legs covered by bought legs.
+//!
+//! - [`sell_option_with_margin`](Pallet::sell_option_with_margin): write options posting margin
+//!   instead of the full collateral.
+//!
+//! - [`add_margin`](Pallet::add_margin): add margin to a margined position when the spot price
+//!   moves against the writer.
+//!
+//! - [`liquidate_margin_position`](Pallet::liquidate_margin_position): take over a margined
+//!   position whose margin fell below the required margin by posting its full collateral.
+//!
+//! - [`withdraw_margin`](Pallet::withdraw_margin): withdraw the margin left after settlement and
+//!   the part of the premium of a margined writer.
+//!
+//! - [`release_unsold_collateral`](Pallet::release_unsold_collateral): release the seller's
+//!   collateral locked for options that were not bought, once the option is settled.
+//!
+//! - [`update_protocol_fees`](Pallet::update_protocol_fees): update the fees taken by the protocol
+//!   on premiums and on the payout of exercised options.
+//!
+//! - [`update_risk_limits`](Pallet::update_risk_limits): set or remove the risk limits of the
+//!   options on an underlying asset.
+//!
+//! - [`rebalance_collateral_strategy`](Pallet::rebalance_collateral_strategy): harvest the yield of
+//!   the collateral allocated to the collateral strategy and rebalance the allocation.
+//!
+//! - [`exercise_for`](Pallet::exercise_for): exercise the settled options of a holder for them,
+//!   rewarding the caller with a share of the payout.
+//!
+//! - [`update_auto_exercise`](Pallet::update_auto_exercise): opt in or out of having options
+//!   exercised by keepers.
+//!
 //! ### Runtime Storage Objects
 //! - [`AssetToVault`]: maps an AssetId to its vault.
 //! - [`OptionIdToOption`]: maps an OptionId to its option information.
 //! - [`OptionHashToOptionId`]: maps a `H256` to its optionId. The hash is obtained from option's
 //!   attributes.
 //! - [`Sellers`]: maps an OptionId and an AccountId to its position as a seller.
+//! - [`Buyers`]: maps an AccountId and an OptionId to its position as a buyer.
 //! - `Scheduler`: maps a [`Moment`](Config::Moment) to an OptionId [`OptionId`](OptionIdOf)
 //!   identifying the timestamp
 //! of the next phase of the epoch for the option.
+//! - [`PendingSettlements`]: options whose settlement failed and is retried at each block.
+//! - [`Series`]: maps a [`SeriesId`] to the template of the options of the series.
+//! - [`OptionIdToSeries`]: maps an OptionId to the series it was created from.
+//! - [`RolloverSellers`] and [`PendingRollovers`]: sellers rolling over their collateral in a
+//!   series and the collateral waiting for the next option of the series.
+//! - [`RolloverSellersCount`]: maps a [`SeriesId`] to the number of sellers rolling over in it.
+//! - [`RetiredOptionAssets`]: option assets of cleaned up options, which are never reused.
+//! - [`SpreadPledges`]: maps an OptionId and an AccountId to the options covering a netted seller
+//!   position.
+//! - [`MarginPositions`]: maps an OptionId and an AccountId to its position as a margined writer.
+//! - [`ProtocolFees`]: fees taken by the protocol on premiums and exercise payouts.
+//! - [`AssetRiskLimits`]: maps an AssetId to the risk limits of the options on it.
+//! - [`OpenInterest`]: maps an AssetId to the options written on it, with collateral or margin.
+//! - [`AutoExerciseOptOut`]: holders that opted out of having their options exercised by keepers.
 //!
 //! ### Example
 //!
@@ -115,6 +184,16 @@
 )]
 
 pub use crate::weights::WeightInfo;
+mod auto_exercise;
+mod buyback;
+mod cleanup;
+mod collateral_strategy;
+mod fees;
+mod margin;
+mod positions;
+mod series;
+mod settlement;
+mod strategy;
 mod types;
 mod validation;
 mod weights;
@@ -154,6 +233,7 @@ pub mod pallet {
     };
 
     use traits::{
+        collateral_strategy::CollateralStrategy,
         options_pricing::*,
         swap_bytes::{SwapBytes, Swapped},
         tokenized_options::*,
@@ -162,7 +242,10 @@ pub mod pallet {
     use frame_support::{
         pallet_prelude::*,
         sp_runtime::traits::Hash,
-        storage::{bounded_btree_map::BoundedBTreeMap, bounded_btree_set::BoundedBTreeSet},
+        storage::{
+            bounded_btree_map::BoundedBTreeMap, bounded_btree_set::BoundedBTreeSet,
+            with_transaction, TransactionOutcome,
+        },
         traits::{
             fungibles::{Inspect, InspectHold, Mutate, MutateHold, Transfer},
             EnsureOrigin, Time,
@@ -179,11 +262,12 @@ pub mod pallet {
             AccountIdConversion, AtLeast32Bit, AtLeast32BitUnsigned, BlakeTwo256, CheckedAdd,
             CheckedDiv, CheckedMul, CheckedSub, Convert, One, Saturating, Zero,
         },
-        ArithmeticError, DispatchError, FixedPointNumber, FixedPointOperand, Perquintill,
+        ArithmeticError, DispatchError, FixedI128, FixedPointNumber, FixedPointOperand, Permill,
+        Perquintill,
     };
     use sp_std::cmp::min;
 
-    use sp_std::{collections::btree_map::BTreeMap, fmt::Debug};
+    use sp_std::{collections::btree_map::BTreeMap, fmt::Debug, vec::Vec};
 
     // ----------------------------------------------------------------------------------------------------
     //		Declaration Of The Pallet Type
@@ -249,20 +333,96 @@ pub mod pallet {
         /// Vault pallet.
         type VaultId: Clone + Copy + Codec + MaxEncodedLen + Debug + PartialEq + Default + Parameter;
 
-        /// Vaults to collect collaterals.
+        /// Vaults to collect collaterals. Strategies move the collateral allocated to them in and
+        /// out of the vaults as a [`StrategicVault`](composable_traits::vault::StrategicVault).
         type Vault: CapabilityVault<
+                AssetId = AssetIdOf<Self>,
+                Balance = BalanceOf<Self>,
+                AccountId = AccountIdOf<Self>,
+                VaultId = VaultIdOf<Self>,
+            > + composable_traits::vault::StrategicVault<
+                AssetId = AssetIdOf<Self>,
+                Balance = BalanceOf<Self>,
+                AccountId = AccountIdOf<Self>,
+                VaultId = VaultIdOf<Self>,
+            >;
+
+        /// Pricing model used to compute the premium paid by buyers.
+        type OptionsPricing: OptionsPricing<
             AssetId = AssetIdOf<Self>,
             Balance = BalanceOf<Self>,
+            Moment = MomentOf<Self>,
+            OptionId = OptionIdOf<Self>,
+        >;
+
+        /// Spread kept by sellers when the protocol buys options back from buyers, as a share of
+        /// the price of the options.
+        #[pallet::constant]
+        type BuybackSpread: Get<Permill>;
+
+        /// Time after a failed settlement from which the protocol origin can settle the option
+        /// at a given price, if the oracle is still unavailable.
+        #[pallet::constant]
+        type SettlementGracePeriod: Get<MomentOf<Self>>;
+
+        /// Maximum weight used each block to move options to their next phase and retry pending
+        /// settlements. Phase changes that don't fit are applied in the next blocks.
+        #[pallet::constant]
+        type MaxSchedulerWeight: Get<Weight>;
+
+        /// Time after the end of an option during which sellers can still withdraw their
+        /// collateral. Once it is over, the option can be cleaned up.
+        #[pallet::constant]
+        type ClaimWindow: Get<MomentOf<Self>>;
+
+        /// Account receiving the collateral and premium not withdrawn by sellers when an option
+        /// is cleaned up.
+        #[pallet::constant]
+        type UnclaimedFundsAccount: Get<AccountIdOf<Self>>;
+
+        /// Maximum number of seller positions released and of option holders burned by each call
+        /// to [`clean_up_option`](Pallet::clean_up_option).
+        #[pallet::constant]
+        type MaxCleanupBatch: Get<u32>;
+
+        /// Maximum number of legs of a strategy.
+        #[pallet::constant]
+        type MaxStrategyLegs: Get<u32>;
+
+        /// Move of the spot price against margined writers used to compute their margin. The
+        /// margin of each option covers its payoff after this move.
+        #[pallet::constant]
+        type MarginStressShock: Get<Permill>;
+
+        /// Maximum number of margined writers of an option, whose positions are all settled with
+        /// the option.
+        #[pallet::constant]
+        type MaxMarginWriters: Get<u32>;
+
+        /// Account receiving the protocol fees set with
+        /// [`update_protocol_fees`](Pallet::update_protocol_fees).
+        #[pallet::constant]
+        type FeeAccount: Get<AccountIdOf<Self>>;
+
+        /// Strategy earning yield on the collateral of the vaults. Each vault allocates to it the
+        /// share of its collateral set for the
+        /// [`strategy_account_id`](Pallet::strategy_account_id) in the `strategies` of its
+        /// [`VaultConfig`].
+        type CollateralStrategy: CollateralStrategy<
             AccountId = AccountIdOf<Self>,
-            VaultId = VaultIdOf<Self>,
+            AssetId = AssetIdOf<Self>,
+            Balance = BalanceOf<Self>,
         >;
 
-        // type OptionsPricing: OptionsPricing<
-        // 	AssetId = AssetIdOf<Self>,
-        // 	Balance = BalanceOf<Self>,
-        // 	Moment = MomentOf<Self>,
-        // 	OptionId = OptionIdOf<Self>,
-        // >;
+        /// Share of the payout of the options exercised for a holder by
+        /// [`exercise_for`](Pallet::exercise_for), paid by the holder to the caller.
+        #[pallet::constant]
+        type ExerciseKeeperReward: Get<Permill>;
+
+        /// Maximum number of sellers rolling over their collateral in a series, all of which are
+        /// processed when an option of the series ends and when the next one starts.
+        #[pallet::constant]
+        type MaxRolloverSellers: Get<u32>;
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -279,7 +439,13 @@ pub mod pallet {
     pub type VaultIdOf<T> = <T as Config>::VaultId;
     pub type VaultOf<T> = <T as Config>::Vault;
     pub type VaultConfigOf<T> = VaultConfig<AccountIdOf<T>, AssetIdOf<T>>;
-    // pub type OptionsPricingOf<T> = <T as Config>::OptionsPricing;
+    pub type OptionsPricingOf<T> = <T as Config>::OptionsPricing;
+    pub type OptionSeriesOf<T> = OptionSeries<AssetIdOf<T>, BalanceOf<T>, MomentOf<T>>;
+    pub type StrategyLegOf<T> = StrategyLeg<OptionIdOf<T>, BalanceOf<T>>;
+    pub type StrategyLegsOf<T> = BoundedVec<StrategyLegOf<T>, <T as Config>::MaxStrategyLegs>;
+    pub type CleanupHoldersOf<T> = BoundedVec<AccountIdOf<T>, <T as Config>::MaxCleanupBatch>;
+    pub type FeeConfigOf<T> = FeeConfig<BalanceOf<T>>;
+    pub type RiskLimitsOf<T> = RiskLimits<BalanceOf<T>, MomentOf<T>>;
 
     // ----------------------------------------------------------------------------------------------------
     //		Storage
@@ -304,6 +470,12 @@ pub mod pallet {
     #[pallet::getter(fn options_hash)]
     pub type OptionHashToOptionId<T: Config> = StorageMap<_, Blake2_128Concat, H256, OptionIdOf<T>>;
 
+    /// Option assets of the options removed by [`clean_up_option`](Pallet::clean_up_option).
+    /// Assets can't be destroyed through the [`CurrencyFactory`](Config::CurrencyFactory), so
+    /// they are retired once their whole supply is burned and never minted again.
+    #[pallet::storage]
+    pub type RetiredOptionAssets<T: Config> = StorageMap<_, Blake2_128Concat, OptionIdOf<T>, ()>;
+
     /// Maps [`AccountId`](frame_system::Config::AccountId) and option id
     /// [`OptionId`](OptionIdOf) to the user's
     /// `SellerPosition`.
@@ -318,6 +490,47 @@ pub mod pallet {
         SellerPosition<T>,
     >;
 
+    /// Maps [`AccountId`](frame_system::Config::AccountId) and option id
+    /// [`OptionId`](OptionIdOf) to the user's `BuyerPosition`, tracking the options bought or
+    /// received through the pallet.
+    #[pallet::storage]
+    #[pallet::getter(fn buyers)]
+    pub type Buyers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        Blake2_128Concat,
+        OptionIdOf<T>,
+        BuyerPosition<BalanceOf<T>>,
+    >;
+
+    /// Maps option id [`OptionId`](OptionIdOf) and [`AccountId`](frame_system::Config::AccountId)
+    /// of a seller that only posted the maximum loss of a spread as collateral to the options
+    /// covering its position, held by the protocol until the position is released.
+    #[pallet::storage]
+    #[pallet::getter(fn spread_pledges)]
+    pub type SpreadPledges<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        OptionIdOf<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        SpreadPledge<OptionIdOf<T>, BalanceOf<T>>,
+    >;
+
+    /// Maps option id [`OptionId`](OptionIdOf) and [`AccountId`](frame_system::Config::AccountId)
+    /// of a seller that posted margin instead of the full collateral to its `MarginPosition`.
+    #[pallet::storage]
+    #[pallet::getter(fn margin_positions)]
+    pub type MarginPositions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        OptionIdOf<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        MarginPosition<BalanceOf<T>>,
+    >;
+
     /// Maps a timestamp [`Moment`](Config::Moment) and option id
     /// [`OptionId`](OptionIdOf) to its currently active window type.
     /// Scheduler is a timestamp-ordered list.
@@ -325,6 +538,85 @@ pub mod pallet {
     pub(crate) type Scheduler<T: Config> =
         StorageDoubleMap<_, Identity, Swapped<MomentOf<T>>, Identity, OptionIdOf<T>, Status>;
 
+    /// Maps the option id [`OptionId`](OptionIdOf) of the options whose settlement failed to the
+    /// moment of the first failure. Their settlement is retried in the next blocks, within the
+    /// [`MaxSchedulerWeight`](Config::MaxSchedulerWeight).
+    #[pallet::storage]
+    #[pallet::getter(fn pending_settlements)]
+    pub type PendingSettlements<T: Config> =
+        StorageMap<_, Blake2_128Concat, OptionIdOf<T>, MomentOf<T>>;
+
+    /// Option id [`OptionId`](OptionIdOf) of the last entry of [`PendingSettlements`] visited
+    /// when the budget of a block ran out. The next blocks resume iterating after it, so that
+    /// each entry is eventually retried.
+    #[pallet::storage]
+    pub(crate) type PendingSettlementsCursor<T: Config> = StorageValue<_, OptionIdOf<T>>;
+
+    /// Fees taken by the protocol on premiums and on the payout of exercised options. No fee is
+    /// taken until they are set by the protocol origin.
+    #[pallet::storage]
+    #[pallet::getter(fn protocol_fees)]
+    pub type ProtocolFees<T: Config> = StorageValue<_, FeeConfigOf<T>, ValueQuery>;
+
+    /// Maps the [`AssetId`](AssetIdOf) of an underlying asset to the `RiskLimits` enforced on the
+    /// options having it as base asset. Options on assets without limits are not restricted.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_risk_limits)]
+    pub type AssetRiskLimits<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, RiskLimitsOf<T>>;
+
+    /// Maps the [`AssetId`](AssetIdOf) of an underlying asset to the options written on it,
+    /// with collateral or with margin, across all the options having it as base asset. Options
+    /// are counted until the position writing them is released.
+    #[pallet::storage]
+    #[pallet::getter(fn open_interest)]
+    pub type OpenInterest<T: Config> =
+        StorageMap<_, Blake2_128Concat, AssetIdOf<T>, BalanceOf<T>, ValueQuery>;
+
+    /// Holders that opted out of having their options exercised by
+    /// [`exercise_for`](Pallet::exercise_for).
+    #[pallet::storage]
+    pub type AutoExerciseOptOut<T: Config> = StorageMap<_, Blake2_128Concat, AccountIdOf<T>, ()>;
+
+    /// Counter of the series created, used as the id of the next one.
+    #[pallet::storage]
+    pub type SeriesCount<T: Config> = StorageValue<_, SeriesId, ValueQuery>;
+
+    /// Maps a [`SeriesId`] to the template of the options of the series. Removed when the series
+    /// is stopped.
+    #[pallet::storage]
+    #[pallet::getter(fn series)]
+    pub type Series<T: Config> = StorageMap<_, Twox64Concat, SeriesId, OptionSeriesOf<T>>;
+
+    /// Maps an option id [`OptionId`](OptionIdOf) to the series it was created from.
+    #[pallet::storage]
+    #[pallet::getter(fn option_id_to_series)]
+    pub type OptionIdToSeries<T: Config> = StorageMap<_, Blake2_128Concat, OptionIdOf<T>, SeriesId>;
+
+    /// Sellers that roll their collateral from each option of a series to the next one, at most
+    /// [`MaxRolloverSellers`](Config::MaxRolloverSellers) per series.
+    #[pallet::storage]
+    pub type RolloverSellers<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, SeriesId, Blake2_128Concat, AccountIdOf<T>, ()>;
+
+    /// Maps a [`SeriesId`] to the number of its sellers in [`RolloverSellers`].
+    #[pallet::storage]
+    pub type RolloverSellersCount<T: Config> =
+        StorageMap<_, Twox64Concat, SeriesId, u32, ValueQuery>;
+
+    /// Collateral withdrawn from the option of a series that just ended, to be deposited into the
+    /// next option of the series when its deposit phase starts.
+    #[pallet::storage]
+    pub type PendingRollovers<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SeriesId,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
     // ----------------------------------------------------------------------------------------------------
     //		Events
     // ----------------------------------------------------------------------------------------------------
@@ -365,12 +657,38 @@ pub mod pallet {
             user: AccountIdOf<T>,
             option_amount: BalanceOf<T>,
             option_id: OptionIdOf<T>,
+            option_premium: BalanceOf<T>,
+        },
+
+        /// Emitted after a successful call to the [`transfer_option`](Pallet::transfer_option)
+        /// extrinsic.
+        TransferOption {
+            from: AccountIdOf<T>,
+            to: AccountIdOf<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+        },
+
+        /// Emitted after a successful call to the
+        /// [`sell_back_option`](Pallet::sell_back_option) extrinsic.
+        SellBackOption {
+            user: AccountIdOf<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+            proceeds: BalanceOf<T>,
         },
 
         /// Emitted after a successful call to the `do_settle_option`
         /// function.
         SettleOption { option_id: OptionIdOf<T> },
 
+        /// Emitted each time the settlement of the reported option fails. The option stays
+        /// pending settlement until it succeeds.
+        SettlementFailed {
+            option_id: OptionIdOf<T>,
+            error: DispatchError,
+        },
+
         /// Emitted after a successful call to the `exercise_option`
         /// extrinsic.
         ExerciseOption {
@@ -379,6 +697,20 @@ pub mod pallet {
             option_id: OptionIdOf<T>,
         },
 
+        /// Emitted after a successful call to the [`exercise_for`](Pallet::exercise_for)
+        /// extrinsic.
+        ExercisedFor {
+            keeper: AccountIdOf<T>,
+            user: AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+            option_amount: BalanceOf<T>,
+            reward: BalanceOf<T>,
+        },
+
+        /// Emitted after a successful call to the
+        /// [`update_auto_exercise`](Pallet::update_auto_exercise) extrinsic.
+        AutoExerciseUpdated { user: AccountIdOf<T>, enabled: bool },
+
         /// Emitted after a successful call to the
         /// [`withdraw_collateral`](Pallet::withdraw_collateral) extrinsic.
         WithdrawCollateral {
@@ -386,6 +718,14 @@ pub mod pallet {
             option_id: OptionIdOf<T>,
         },
 
+        /// Emitted after a successful call to the
+        /// [`release_unsold_collateral`](Pallet::release_unsold_collateral) extrinsic.
+        UnsoldCollateralReleased {
+            user: AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+            collateral_amount: BalanceOf<T>,
+        },
+
         /// Emitted when the deposit phase for the reported option starts.
         OptionDepositStart { option_id: OptionIdOf<T> },
 
@@ -397,6 +737,112 @@ pub mod pallet {
 
         /// Emitted when the reported option epoch ends.
         OptionEnd { option_id: OptionIdOf<T> },
+
+        /// Emitted after a successful call to the
+        /// [`create_option_series`](Pallet::create_option_series) extrinsic.
+        CreatedOptionSeries {
+            series_id: SeriesId,
+            series: OptionSeriesOf<T>,
+        },
+
+        /// Emitted when a series is stopped, either by the
+        /// [`stop_option_series`](Pallet::stop_option_series) extrinsic or because its next
+        /// option could not be created.
+        OptionSeriesStopped { series_id: SeriesId },
+
+        /// Emitted after a successful call to the
+        /// [`update_collateral_rollover`](Pallet::update_collateral_rollover) extrinsic.
+        CollateralRolloverUpdated {
+            user: AccountIdOf<T>,
+            series_id: SeriesId,
+            enabled: bool,
+        },
+
+        /// Emitted when the position of a seller that did not withdraw its collateral during the
+        /// claim window is released to the
+        /// [`UnclaimedFundsAccount`](Config::UnclaimedFundsAccount).
+        UnclaimedCollateralReleased {
+            user: AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+        },
+
+        /// Emitted when the option has been removed from storage by the
+        /// [`clean_up_option`](Pallet::clean_up_option) extrinsic.
+        OptionCleanedUp { option_id: OptionIdOf<T> },
+
+        /// Emitted after a successful call to the
+        /// [`execute_strategy`](Pallet::execute_strategy) extrinsic.
+        StrategyExecuted {
+            user: AccountIdOf<T>,
+            legs: StrategyLegsOf<T>,
+            premium_paid: BalanceOf<T>,
+        },
+
+        /// Emitted after a successful call to the
+        /// [`sell_option_with_margin`](Pallet::sell_option_with_margin) extrinsic.
+        SellOptionWithMargin {
+            user: AccountIdOf<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+            margin: BalanceOf<T>,
+        },
+
+        /// Emitted after a successful call to the [`add_margin`](Pallet::add_margin) extrinsic.
+        MarginAdded {
+            user: AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+            amount: BalanceOf<T>,
+        },
+
+        /// Emitted after a successful call to the
+        /// [`liquidate_margin_position`](Pallet::liquidate_margin_position) extrinsic.
+        MarginPositionLiquidated {
+            user: AccountIdOf<T>,
+            liquidator: AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+            option_amount: BalanceOf<T>,
+            margin_seized: BalanceOf<T>,
+        },
+
+        /// Emitted after a successful call to the [`withdraw_margin`](Pallet::withdraw_margin)
+        /// extrinsic.
+        WithdrawMargin {
+            user: AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+        },
+
+        /// Emitted after a successful call to the
+        /// [`update_protocol_fees`](Pallet::update_protocol_fees) extrinsic.
+        ProtocolFeesUpdated { fees: FeeConfigOf<T> },
+
+        /// Emitted when a protocol fee is paid by a user buying or exercising options.
+        ProtocolFeePaid {
+            user: AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+            asset_id: AssetIdOf<T>,
+            amount: BalanceOf<T>,
+        },
+
+        /// Emitted after a successful call to the
+        /// [`rebalance_collateral_strategy`](Pallet::rebalance_collateral_strategy) extrinsic.
+        CollateralStrategyRebalanced {
+            asset_id: AssetIdOf<T>,
+            harvested: BalanceOf<T>,
+            allocated: BalanceOf<T>,
+        },
+
+        /// Emitted when collateral is recalled from the strategy to withdraw it from the vault.
+        StrategyFundsRecalled {
+            asset_id: AssetIdOf<T>,
+            amount: BalanceOf<T>,
+        },
+
+        /// Emitted after a successful call to the
+        /// [`update_risk_limits`](Pallet::update_risk_limits) extrinsic.
+        RiskLimitsUpdated {
+            asset_id: AssetIdOf<T>,
+            limits: Option<RiskLimitsOf<T>>,
+        },
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -455,6 +901,10 @@ pub mod pallet {
         /// Raised when trying to buy an option, but there are not enough options for sale.
         NotEnoughOptionsForSale,
 
+        /// Raised when trying to buy an option, but the premium to pay is greater than the
+        /// maximum premium the user is willing to pay.
+        PremiumExceedsMaxPremium,
+
         /// Raised when trying to exercise options, but the amount is greater than what user owns.
         UserHasNotEnoughOptionTokens,
 
@@ -470,6 +920,109 @@ pub mod pallet {
 
         /// Raised when trying to exercise an option, but it is not exercise phase for that option.
         NotIntoExerciseWindow,
+
+        /// Raised when trying to withdraw the collateral of a physically settled option, but its
+        /// exercise phase is not over.
+        ExerciseWindowNotOver,
+
+        /// Raised when trying to release the collateral of the options not bought, but the
+        /// purchase phase of the option is not over or the option has not been settled yet.
+        PurchaseWindowNotOver,
+
+        /// Raised when trying to release the collateral of the options not bought, but it has
+        /// already been released for the seller position.
+        UnsoldCollateralAlreadyReleased,
+
+        /// Raised when trying to settle an option at a given price, but the option is not pending
+        /// settlement.
+        OptionNotPendingSettlement,
+
+        /// Raised when trying to settle an option at a given price, but the grace period since
+        /// its settlement failed is not over.
+        SettlementGracePeriodNotOver,
+
+        /// Raised when trying to sell options back, but the proceeds are lower than the minimum
+        /// the user is willing to receive.
+        ProceedsBelowMinProceeds,
+
+        /// Raised when trying to sell options back, but the premium collected for the option is
+        /// not enough to pay for them.
+        NotEnoughPremiumForBuyback,
+
+        /// Raised when trying to retrieve the series corresponding to the given series id, but it
+        /// does not exist or it has been stopped.
+        OptionSeriesDoesNotExist,
+
+        /// Raised when trying to create a new series, but at least one of its attributes has an
+        /// invalid value.
+        OptionSeriesAttributesAreInvalid,
+
+        /// Raised when trying to clean up an option, but it has not ended or its claim window is
+        /// not over.
+        ClaimWindowNotOver,
+
+        /// Raised when trying to execute a strategy, but its legs are empty, trade the same
+        /// option twice or options with different expiring dates.
+        StrategyLegsAreInvalid,
+
+        /// Raised when trying to delete the sale of an option whose collateral has been netted
+        /// by a strategy.
+        NettedPositionCannotBeDeleted,
+
+        /// Raised when trying to write an option with margin, but the option is not a cash
+        /// settled European option.
+        MarginNotSupported,
+
+        /// Raised when trying to write an option with margin, but the margin posted is lower than
+        /// the margin required.
+        InsufficientMargin,
+
+        /// Raised when trying to write an option with margin, but the option already has
+        /// [`MaxMarginWriters`](Config::MaxMarginWriters) margined writers.
+        TooManyMarginWriters,
+
+        /// Raised when trying to add margin, liquidate or withdraw, but the user does not have a
+        /// margin position for the option.
+        UserDoesNotHaveMarginPosition,
+
+        /// Raised when trying to liquidate a margin position whose margin is not lower than the
+        /// margin required.
+        MarginPositionIsHealthy,
+
+        /// Raised when trying to create a new option, but its epoch length, its strike price or
+        /// its time to expiry are outside the risk limits of its base asset.
+        OptionOutsideRiskLimits,
+
+        /// Raised when trying to sell an option, but the options written would exceed the
+        /// maximum open interest of its base asset.
+        OpenInterestLimitExceeded,
+
+        /// Raised when trying to buy an option, but its expiring date is closer than the minimum
+        /// time to expiry of its base asset.
+        TimeToExpiryBelowLimit,
+
+        /// Raised when trying to set risk limits whose minimum epoch length is greater than the
+        /// maximum one.
+        RiskLimitsAreInvalid,
+
+        /// Raised when trying to create a new vault, but it allocates funds to a strategy account
+        /// other than the [`strategy_account_id`](Pallet::strategy_account_id) of its asset.
+        VaultStrategiesAreInvalid,
+
+        /// Raised when trying to exercise options for a holder, but the option is not cash
+        /// settled or it expired out of the money.
+        OptionCannotBeAutoExercised,
+
+        /// Raised when trying to exercise options for a holder that opted out of it.
+        AutoExerciseDisabled,
+
+        /// Raised when trying to opt in the rollover of a series, but the series already has
+        /// [`MaxRolloverSellers`](Config::MaxRolloverSellers) sellers rolling over.
+        TooManyRolloverSellers,
+
+        /// Raised when trying to clean up an option whose tokens still cover the netted positions
+        /// of other options, which must be cleaned up first.
+        OptionStillPledged,
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -481,26 +1034,15 @@ pub mod pallet {
         // TODO: use on_post_inherent after https://github.com/paritytech/substrate/pull/10128 is merged.
         /// At each block we perform timestamp checks to update the Scheduler.
         fn on_initialize(_n: T::BlockNumber) -> Weight {
-            let mut used_weight = 0;
             let now = T::Time::now();
+            let budget = T::MaxSchedulerWeight::get();
 
-            while let Some((moment_swapped, option_id, moment_type)) = <Scheduler<T>>::iter().next()
-            {
-                used_weight = used_weight.saturating_add(T::DbWeight::get().reads(1));
-                let moment = moment_swapped.into_value();
-
-                if now < moment {
-                    break
-                }
-
-                <Scheduler<T>>::remove(moment_swapped, &option_id);
+            let used_weight = Self::process_scheduler(now, budget);
 
-                used_weight = used_weight
-                    .saturating_add(T::DbWeight::get().writes(1))
-                    .saturating_add(Self::option_status_change(option_id, moment_type));
-            }
-            let max_weight = <T as frame_system::Config>::BlockWeights::get().max_block;
-            used_weight.min(max_weight)
+            used_weight.saturating_add(Self::retry_pending_settlements(
+                now,
+                budget.saturating_sub(used_weight),
+            ))
         }
     }
 
@@ -566,6 +1108,7 @@ pub mod pallet {
         /// 2. The option should not already exist.
         /// 3. Both the base asset and the quote asset vaults should exist.
         /// 4. The option attributes should all have valid values.
+        /// 5. The option should respect the risk limits of its base asset, if any.
         ///
         /// ## Emits
         /// - [`Event::CreatedOption`]
@@ -588,6 +1131,8 @@ pub mod pallet {
         /// - [`OptionAttributesAreInvalid`](Error::OptionAttributesAreInvalid): raised when trying
         ///   to create a new option,
         /// but at least one of the option's attributes has an invalid value.
+        /// - [`OptionOutsideRiskLimits`](Error::OptionOutsideRiskLimits): raised when the option is
+        ///   outside the risk limits of its base asset.
         ///
         /// # Examples
         ///
@@ -628,6 +1173,7 @@ pub mod pallet {
         /// - Updates the [`OptionIdToOption`] storage adding the amount of option to sell to the
         ///   total amount
         /// already for sale.
+        /// - Adds the amount of option to sell to the [`OpenInterest`] of the base asset.
         ///
         /// ## Errors
         /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when trying to retrieve
@@ -648,6 +1194,8 @@ pub mod pallet {
         /// - [`CannotPassZeroOptionAmount`](Error::CannotPassZeroOptionAmount): raised when trying
         ///   to sell an option,
         /// but the option amount is zero.
+        /// - [`OpenInterestLimitExceeded`](Error::OpenInterestLimitExceeded): raised when the
+        ///   options written would exceed the maximum open interest of the base asset.
         ///
         /// # Examples
         ///
@@ -689,6 +1237,8 @@ pub mod pallet {
         /// - Updates the [`OptionIdToOption`] storage subtracting the amount of option to delete
         ///   the sale of from the total amount
         /// already for sale.
+        /// - Subtracts the amount of option to delete the sale of from the [`OpenInterest`] of the
+        ///   base asset.
         ///
         /// ## Errors
         /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when trying to retrieve
@@ -736,15 +1286,18 @@ pub mod pallet {
         /// - `origin`: type representing the origin of this dispatch.
         /// - `option_amount`: the amount of option the user wants to buy.
         /// - `option_id`: the option id.
+        /// - `max_premium`: the maximum total premium the user is willing to pay.
         ///
         /// ## Requirements
         /// 1. The call must have been signed by the user.
         /// 2. The option should exist.
         /// 3. The option should be in purchase phase.
         /// 4. The option amount should not be zero.
+        /// 5. The premium should not be greater than `max_premium`.
         ///
         /// ## Emits
         /// - [`Event::BuyOption`]
+        /// - [`Event::ProtocolFeePaid`], if a premium fee is set.
         ///
         /// ## State Changes
         /// - Updates the [`OptionIdToOption`] storage adding the amount of option to buy to the
@@ -767,6 +1320,11 @@ pub mod pallet {
         /// - [`CannotPassZeroOptionAmount`](Error::CannotPassZeroOptionAmount): raised when trying
         ///   to buy an option,
         /// but the option amount is zero.
+        /// - [`PremiumExceedsMaxPremium`](Error::PremiumExceedsMaxPremium): raised when trying to
+        ///   buy an option,
+        /// but the premium is greater than `max_premium`.
+        /// - [`TimeToExpiryBelowLimit`](Error::TimeToExpiryBelowLimit): raised when the expiring
+        ///   date is closer than the minimum time to expiry of the base asset.
         ///
         /// # Examples
         ///
@@ -776,10 +1334,11 @@ pub mod pallet {
             origin: OriginFor<T>,
             option_amount: BalanceOf<T>,
             option_id: OptionIdOf<T>,
+            max_premium: BalanceOf<T>,
         ) -> DispatchResult {
             let from = ensure_signed(origin)?;
 
-            <Self as TokenizedOptions>::buy_option(&from, option_amount, option_id)?;
+            <Self as TokenizedOptions>::buy_option(&from, option_amount, option_id, max_premium)?;
 
             Ok(())
         }
@@ -795,11 +1354,13 @@ pub mod pallet {
         /// ## Requirements
         /// 1. The call must have been signed by the user.
         /// 2. The option should exist.
-        /// 3. The option should be in exercise phase.
+        /// 3. The option should be in exercise phase, or in purchase phase before expiry if it is
+        /// an American option.
         /// 4. The option amount should not be zero.
         ///
         /// ## Emits
         /// - [`Event::ExerciseOption`]
+        /// - [`Event::ProtocolFeePaid`], if an exercise fee is set.
         ///
         /// ## State Changes
         ///
@@ -843,7 +1404,8 @@ pub mod pallet {
         /// ## Requirements
         /// 1. The call must have been signed by the user.
         /// 2. The option should exist.
-        /// 3. The option should be in exercise phase.
+        /// 3. The option should be in exercise phase or ended, and ended if it is physically
+        ///    settled. Ended options can be withdrawn from until they are cleaned up.
         ///
         /// ## Emits
         /// - [`Event::WithdrawCollateral`]
@@ -858,6 +1420,9 @@ pub mod pallet {
         /// - [`NotIntoExerciseWindow`](Error::NotIntoExerciseWindow): raised when trying to
         ///   withdraw collateral,
         /// but it is not exercise phase for that option.
+        /// - [`ExerciseWindowNotOver`](Error::ExerciseWindowNotOver): raised when trying to
+        ///   withdraw the collateral of a physically settled option before the end of its exercise
+        ///   phase.
         /// - [`UserDoesNotHaveSellerPosition`](Error::UserDoesNotHaveSellerPosition): raised when
         ///   trying to withdraw collateral,
         /// but the seller has not a seller position.
@@ -879,44 +1444,945 @@ pub mod pallet {
 
             Ok(())
         }
-    }
-
-    // ----------------------------------------------------------------------------------------------------
-    //		TokenizedOptions Trait
-    // ----------------------------------------------------------------------------------------------------
-    impl<T: Config> TokenizedOptions for Pallet<T> {
-        type AccountId = AccountIdOf<T>;
-        type OptionId = OptionIdOf<T>;
-        type Balance = BalanceOf<T>;
-        type Moment = MomentOf<T>;
-        type VaultId = VaultIdOf<T>;
-        type OptionConfig = OptionConfigOf<T>;
-        type VaultConfig = VaultConfigOf<T>;
 
-        /// Create a new vault for the given asset and save the vault id on storage.
+        /// Release the seller's collateral locked for options that were not bought, once the
+        /// option has been settled at the end of its purchase phase. The collateral of each seller
+        /// is released pro rata to the options it sold, and the rest of its position is
+        /// withdrawn as usual with [`withdraw_collateral`](Pallet::withdraw_collateral).
         ///
         /// # Overview
         /// ## Parameters
-        /// - `vault_config`: the configuration of the vault to create.
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `option_id`: the option id.
         ///
         /// ## Requirements
-        /// 1. The vault should not already exist.
-        /// 2. The asset should be supported by the Oracle.
+        /// 1. The call must have been signed by the user.
+        /// 2. The option should exist and have been settled, i.e. be in exercise phase or ended.
+        /// 3. The user should have a seller position whose unsold collateral has not been released
+        ///    yet.
         ///
         /// ## Emits
-        /// - [`Event::CreatedAssetVault`]
+        /// - [`Event::UnsoldCollateralReleased`]
         ///
         /// ## State Changes
-        /// - Updates the [`AssetToVault`] storage mapping the asset id with the new created vault
-        ///   id.
+        /// - Updates the seller's position in the [`Sellers`] storage, subtracting the shares
+        ///   released.
         ///
         /// ## Errors
-        /// - [`AssetIsNotSupported`](Error::AssetIsNotSupported): raised when trying to create a
-        ///   new vault,
-        ///  but the asset is not supported by the Oracle.
-        /// - [`AssetVaultAlreadyExists`](Error::AssetVaultAlreadyExists): raised when trying to
-        ///   create a new vault,
-        /// but it already exists.
+        /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when the option does not
+        ///   exist.
+        /// - [`PurchaseWindowNotOver`](Error::PurchaseWindowNotOver): raised when the purchase
+        ///   phase of the option is not over or the option is still pending settlement.
+        /// - [`UserDoesNotHaveSellerPosition`](Error::UserDoesNotHaveSellerPosition): raised when
+        ///   the user does not have a seller position for the option.
+        /// - [`UnsoldCollateralAlreadyReleased`](Error::UnsoldCollateralAlreadyReleased): raised
+        ///   when the unsold collateral of the position has already been released.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::release_unsold_collateral())]
+        #[transactional]
+        pub fn release_unsold_collateral(
+            origin: OriginFor<T>,
+            option_id: OptionIdOf<T>,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            let option =
+                Self::option_id_to_option(option_id).ok_or(Error::<T>::OptionDoesNotExists)?;
+
+            Sellers::<T>::try_mutate(option_id, &from, |position| {
+                Self::do_release_unsold_collateral(&from, option_id, &option, position)
+            })?;
+
+            Ok(())
+        }
+
+        /// Sell options back to the protocol before expiry. The options are priced with
+        /// [`OptionsPricing`](Config::OptionsPricing) minus the
+        /// [`BuybackSpread`](Config::BuybackSpread), paid from the premium collected for the
+        /// option, and burned. They can then be bought again.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `option_amount`: the amount of option the user wants to sell back.
+        /// - `option_id`: the option id.
+        /// - `min_proceeds`: the minimum amount of stablecoin the user is willing to receive.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        /// 2. The option should exist.
+        /// 3. The option should be in purchase phase.
+        /// 4. The option amount should not be zero.
+        ///
+        /// ## Emits
+        /// - [`Event::SellBackOption`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`OptionIdToOption`] storage adding the proceeds to the premium paid for
+        ///   buybacks, which is not distributed to sellers.
+        ///
+        /// ## Errors
+        /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when the option does not
+        ///   exist.
+        /// - [`NotIntoPurchaseWindow`](Error::NotIntoPurchaseWindow): raised when it is not
+        ///   purchase phase for that option.
+        /// - [`UserHasNotEnoughOptionTokens`](Error::UserHasNotEnoughOptionTokens): raised when the
+        ///   user owns less options than the amount to sell back.
+        /// - [`ProceedsBelowMinProceeds`](Error::ProceedsBelowMinProceeds): raised when the
+        ///   proceeds are lower than `min_proceeds`.
+        /// - [`NotEnoughPremiumForBuyback`](Error::NotEnoughPremiumForBuyback): raised when the
+        ///   premium collected for the option is not enough to pay the proceeds.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::sell_back_option())]
+        #[transactional]
+        pub fn sell_back_option(
+            origin: OriginFor<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+            min_proceeds: BalanceOf<T>,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            OptionIdToOption::<T>::try_mutate(option_id, |option| match option {
+                Some(option) =>
+                    Self::do_sell_back_option(&from, option_amount, option_id, min_proceeds, option),
+                None => Err(Error::<T>::OptionDoesNotExists.into()),
+            })
+        }
+
+        /// Transfer option tokens to another account, moving their share of the premium paid in
+        /// the sender's position to the receiver's one. Option tokens transferred in other ways
+        /// are not tracked in buyer positions.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `to`: the account receiving the options.
+        /// - `option_amount`: the amount of option to transfer.
+        /// - `option_id`: the option id.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        /// 2. The option should exist.
+        /// 3. The option amount should not be zero.
+        ///
+        /// ## Emits
+        /// - [`Event::TransferOption`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`Buyers`] storage for both the sender and the receiver.
+        ///
+        /// ## Errors
+        /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when the option does not
+        ///   exist.
+        /// - [`CannotPassZeroOptionAmount`](Error::CannotPassZeroOptionAmount): raised when the
+        ///   option amount is zero.
+        /// - [`UserHasNotEnoughOptionTokens`](Error::UserHasNotEnoughOptionTokens): raised when the
+        ///   user owns less options than the amount to transfer.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_option())]
+        #[transactional]
+        pub fn transfer_option(
+            origin: OriginFor<T>,
+            to: AccountIdOf<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            ensure!(
+                OptionIdToOption::<T>::contains_key(option_id),
+                Error::<T>::OptionDoesNotExists
+            );
+            ensure!(
+                option_amount != BalanceOf::<T>::zero(),
+                Error::<T>::CannotPassZeroOptionAmount
+            );
+
+            AssetsOf::<T>::transfer(option_id, &from, &to, option_amount, false)
+                .map_err(|_| Error::<T>::UserHasNotEnoughOptionTokens)?;
+
+            let premium = Self::remove_from_buyer_position(&from, option_id, option_amount, false)?;
+            Self::add_to_buyer_position(&to, option_id, option_amount, premium)?;
+
+            Self::deposit_event(Event::TransferOption {
+                from,
+                to,
+                option_amount,
+                option_id,
+            });
+
+            Ok(())
+        }
+
+        /// Settle an option whose settlement failed at the given price of its base asset. Used as
+        /// a fallback when the oracle stays unavailable after expiry.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `option_id`: the option id.
+        /// - `base_asset_spot_price`: the price of the base asset to settle the option at.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been made by the protocol origin.
+        /// 2. The option should exist and be pending settlement.
+        /// 3. The [`SettlementGracePeriod`](Config::SettlementGracePeriod) since the first failed
+        ///    settlement should be over.
+        ///
+        /// ## Emits
+        /// - [`Event::SettleOption`]
+        /// - [`Event::OptionExerciseStart`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`OptionIdToOption`] storage as settlement does and opens the exercise
+        ///   phase.
+        /// - Removes the option from the [`PendingSettlements`] storage.
+        ///
+        /// ## Errors
+        /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when the option does not
+        ///   exist.
+        /// - [`OptionNotPendingSettlement`](Error::OptionNotPendingSettlement): raised when the
+        ///   option is not pending settlement.
+        /// - [`SettlementGracePeriodNotOver`](Error::SettlementGracePeriodNotOver): raised when the
+        ///   grace period is not over.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::settle_option_with_price())]
+        #[transactional]
+        pub fn settle_option_with_price(
+            origin: OriginFor<T>,
+            option_id: OptionIdOf<T>,
+            base_asset_spot_price: BalanceOf<T>,
+        ) -> DispatchResult {
+            T::ProtocolOrigin::ensure_origin(origin)?;
+
+            OptionIdToOption::<T>::try_mutate(option_id, |option| match option {
+                Some(option) =>
+                    Self::do_settle_option_with_price(option_id, base_asset_spot_price, option),
+                None => Err(Error::<T>::OptionDoesNotExists.into()),
+            })
+        }
+
+        /// Create a recurring series of options and its first option. The next option of the
+        /// series is created when the previous one ends, with the strike price selected from the
+        /// oracle price at that moment.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `series`: the template of the options of the series, where `start` is the start of the
+        ///   deposit phase of the first option.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been made by the protocol origin.
+        /// 2. Both the base asset and the quote asset vaults should exist.
+        /// 3. Phase durations should not be zero and each option should end before the next one
+        ///    starts.
+        ///
+        /// ## Emits
+        /// - [`Event::CreatedOption`]
+        /// - [`Event::CreatedOptionSeries`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`Series`] storage with the series template.
+        /// - Updates the [`OptionIdToSeries`] storage linking the first option to the series.
+        /// - Creates the first option as [`create_option`](Pallet::create_option) does.
+        ///
+        /// ## Errors
+        /// - [`OptionSeriesAttributesAreInvalid`](Error::OptionSeriesAttributesAreInvalid): raised
+        ///   when at least one of the series' attributes has an invalid value.
+        /// - [`AssetPriceNotFound`](Error::AssetPriceNotFound): raised when the oracle price of the
+        ///   base asset is not available to select the strike price.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::create_option_series())]
+        pub fn create_option_series(
+            origin: OriginFor<T>,
+            series: OptionSeriesOf<T>,
+        ) -> DispatchResult {
+            T::ProtocolOrigin::ensure_origin(origin)?;
+
+            let series =
+                Validated::new(series).map_err(|_| Error::<T>::OptionSeriesAttributesAreInvalid)?;
+
+            Self::do_create_option_series(series)?;
+
+            Ok(())
+        }
+
+        /// Stop a series: the current option of the series runs until its end, but no new option
+        /// is created after it.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `series_id`: the series id.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been made by the protocol origin.
+        /// 2. The series should exist.
+        ///
+        /// ## Emits
+        /// - [`Event::OptionSeriesStopped`]
+        ///
+        /// ## State Changes
+        /// - Removes the series from the [`Series`] storage.
+        ///
+        /// ## Errors
+        /// - [`OptionSeriesDoesNotExist`](Error::OptionSeriesDoesNotExist): raised when the series
+        ///   does not exist.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::stop_option_series())]
+        pub fn stop_option_series(origin: OriginFor<T>, series_id: SeriesId) -> DispatchResult {
+            T::ProtocolOrigin::ensure_origin(origin)?;
+
+            Series::<T>::take(series_id).ok_or(Error::<T>::OptionSeriesDoesNotExist)?;
+
+            Self::deposit_event(Event::OptionSeriesStopped { series_id });
+
+            Ok(())
+        }
+
+        /// Opt in or out of the automatic rollover of the seller's collateral in a series. When
+        /// an option of the series ends, the collateral of the opted-in sellers is withdrawn
+        /// together with their premium, and the collateral is used to sell the next option of the
+        /// series as soon as its deposit phase starts.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `series_id`: the series id.
+        /// - `enabled`: whether the collateral should be rolled over.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        /// 2. The series should exist.
+        /// 3. When opting in, the series should have less than
+        ///    [`MaxRolloverSellers`](Config::MaxRolloverSellers) sellers rolling over.
+        ///
+        /// ## Emits
+        /// - [`Event::CollateralRolloverUpdated`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`RolloverSellers`] and [`RolloverSellersCount`] storages. Opting out also
+        ///   drops the collateral waiting to be deposited in [`PendingRollovers`], which is already
+        ///   in the user's account.
+        ///
+        /// ## Errors
+        /// - [`OptionSeriesDoesNotExist`](Error::OptionSeriesDoesNotExist): raised when the series
+        ///   does not exist.
+        /// - [`TooManyRolloverSellers`](Error::TooManyRolloverSellers): raised when opting in a
+        ///   series that already has [`MaxRolloverSellers`](Config::MaxRolloverSellers) sellers
+        ///   rolling over.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::update_collateral_rollover())]
+        pub fn update_collateral_rollover(
+            origin: OriginFor<T>,
+            series_id: SeriesId,
+            enabled: bool,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            ensure!(
+                Series::<T>::contains_key(series_id),
+                Error::<T>::OptionSeriesDoesNotExist
+            );
+
+            if enabled {
+                // Rollover sellers are bounded, since all of them are processed in the scheduler
+                if !RolloverSellers::<T>::contains_key(series_id, &from) {
+                    RolloverSellersCount::<T>::try_mutate(series_id, |count| {
+                        ensure!(
+                            *count < T::MaxRolloverSellers::get(),
+                            Error::<T>::TooManyRolloverSellers
+                        );
+
+                        *count += 1;
+                        Ok::<_, DispatchError>(())
+                    })?;
+
+                    RolloverSellers::<T>::insert(series_id, &from, ());
+                }
+            } else {
+                if RolloverSellers::<T>::take(series_id, &from).is_some() {
+                    RolloverSellersCount::<T>::mutate(series_id, |count| {
+                        *count = count.saturating_sub(1)
+                    });
+                }
+
+                PendingRollovers::<T>::remove(series_id, &from);
+            }
+
+            Self::deposit_event(Event::CollateralRolloverUpdated {
+                user: from,
+                series_id,
+                enabled,
+            });
+
+            Ok(())
+        }
+
+        /// Clean up an option whose claim window is over. Anyone can call it, as many times as
+        /// needed. Each call releases up to [`MaxCleanupBatch`](Config::MaxCleanupBatch) seller
+        /// positions and margin positions not withdrawn yet, paying their collateral and premium
+        /// to the [`UnclaimedFundsAccount`](Config::UnclaimedFundsAccount), and burns the
+        /// unexercised option tokens of `holders`. Once no position and no option token is left,
+        /// the option is removed from storage.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `option_id`: the option id.
+        /// - `holders`: accounts holding option tokens, found off-chain. At most
+        ///   [`MaxCleanupBatch`](Config::MaxCleanupBatch) holders can be passed.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed.
+        /// 2. The option should exist and be ended.
+        /// 3. The [`ClaimWindow`](Config::ClaimWindow) after the end of the option should be over.
+        /// 4. The option should not cover netted positions in [`SpreadPledges`] anymore, so the
+        ///    options sold in a strategy are cleaned up before the options protecting them.
+        ///    Protocol accounts passed as holders are skipped.
+        ///
+        /// ## Emits
+        /// - [`Event::UnclaimedCollateralReleased`]
+        /// - [`Event::OptionCleanedUp`]
+        ///
+        /// ## State Changes
+        /// - Deletes the released positions from [`Sellers`] and [`MarginPositions`], and the
+        ///   positions of `holders` from [`Buyers`].
+        /// - Once the option is fully cleaned up, deletes it from [`OptionIdToOption`],
+        ///   [`OptionHashToOptionId`] and [`OptionIdToSeries`], and retires its option asset in
+        ///   [`RetiredOptionAssets`].
+        ///
+        /// ## Errors
+        /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when the option does not
+        ///   exist.
+        /// - [`ClaimWindowNotOver`](Error::ClaimWindowNotOver): raised when the option has not
+        ///   ended or its claim window is not over.
+        /// - [`OptionStillPledged`](Error::OptionStillPledged): raised when the option still covers
+        ///   netted positions of other options.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::clean_up_option(
+            T::MaxCleanupBatch::get(),
+            holders.len() as u32,
+        ))]
+        #[transactional]
+        pub fn clean_up_option(
+            origin: OriginFor<T>,
+            option_id: OptionIdOf<T>,
+            holders: CleanupHoldersOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::do_clean_up_option(option_id, holders)?;
+
+            Ok(())
+        }
+
+        /// Buy and sell several options sharing the same expiring date at once, for example to
+        /// build a call spread or a straddle. The legs are executed atomically: if one fails,
+        /// none is executed. Bought legs are executed first.
+        ///
+        /// The premium of the bought legs is paid when the strategy is executed, while the premium
+        /// of the sold legs is received by withdrawing the collateral. `max_premium` bounds the net
+        /// premium of the strategy, the premium of the bought legs minus the one of the sold legs,
+        /// as returned by [`calculate_strategy_premium`](Pallet::calculate_strategy_premium).
+        ///
+        /// A sold leg covered by a bought leg of the same strategy, for an option with the same
+        /// assets and type whose strike is further out of the money, only requires the maximum
+        /// loss of the spread as collateral. The covering options are held by the protocol and
+        /// their payoff is added to the seller's position when its collateral is withdrawn.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `legs`: the options to buy or sell, with their amounts.
+        /// - `max_premium`: the maximum net premium the user is willing to pay for the strategy.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        /// 2. The legs should trade different existing options with the same expiring date, for
+        ///    nonzero amounts.
+        /// 3. Each option should be in purchase phase if bought or in deposit phase if sold. A sold
+        ///    leg covered by a bought leg can also be sold in purchase phase, so that options
+        ///    sharing an epoch can be traded as a spread.
+        /// 4. Only cash settled European options can be sold with netted collateral.
+        ///
+        /// ## Emits
+        /// - [`Event::BuyOption`] and [`Event::SellOption`] for each leg.
+        /// - [`Event::StrategyExecuted`]
+        ///
+        /// ## State Changes
+        /// - Updates the storage as [`buy_option`](Pallet::buy_option) and
+        ///   [`sell_option`](Pallet::sell_option) do for each leg.
+        /// - Updates the [`SpreadPledges`] storage with the options covering netted sales.
+        ///
+        /// ## Errors
+        /// - [`StrategyLegsAreInvalid`](Error::StrategyLegsAreInvalid): raised when the legs are
+        ///   empty, trade the same option twice, a nonexistent option or options with different
+        ///   expiring dates.
+        /// - [`PremiumExceedsMaxPremium`](Error::PremiumExceedsMaxPremium): raised when the net
+        ///   premium of the strategy is greater than `max_premium`.
+        /// - Any error raised by [`buy_option`](Pallet::buy_option) or
+        ///   [`sell_option`](Pallet::sell_option) for a leg.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::execute_strategy(legs.len() as u32))]
+        #[transactional]
+        pub fn execute_strategy(
+            origin: OriginFor<T>,
+            legs: StrategyLegsOf<T>,
+            max_premium: BalanceOf<T>,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            let legs = Validated::new(legs).map_err(|_| Error::<T>::StrategyLegsAreInvalid)?;
+
+            Self::do_execute_strategy(&from, legs, max_premium)?;
+
+            Ok(())
+        }
+
+        /// Write options posting margin instead of the full collateral. The margin required for
+        /// each option is the greater between the full collateral scaled by the option delta and
+        /// the payoff of the option after a [`MarginStressShock`](Config::MarginStressShock) move
+        /// of the spot price, capped at the full collateral. The margin is held by the protocol
+        /// and not deposited into the vault. Margined options are sold to buyers together with the
+        /// options backed by the vault and share the premium with them.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `option_amount`: the amount of option to write.
+        /// - `option_id`: the option id.
+        /// - `margin`: the margin posted, in the collateral asset of the option.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        /// 2. The option should be a cash settled European option in deposit phase.
+        /// 3. `margin` should cover the margin required for `option_amount` options, taking into
+        ///    account the margin already posted for the option.
+        /// 4. The option should have less than [`MaxMarginWriters`](Config::MaxMarginWriters)
+        ///    margined writers, unless the user is one of them.
+        ///
+        /// ## Emits
+        /// - [`Event::SellOptionWithMargin`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`MarginPositions`] storage with the options written and the margin.
+        /// - Updates the [`OptionIdToOption`] storage adding `option_amount` to the margined
+        ///   options.
+        /// - Adds `option_amount` to the [`OpenInterest`] of the base asset.
+        ///
+        /// ## Errors
+        /// - [`MarginNotSupported`](Error::MarginNotSupported): raised when the option is not a
+        ///   cash settled European option.
+        /// - [`InsufficientMargin`](Error::InsufficientMargin): raised when the margin is lower
+        ///   than the margin required.
+        /// - [`TooManyMarginWriters`](Error::TooManyMarginWriters): raised when the option already
+        ///   has [`MaxMarginWriters`](Config::MaxMarginWriters) margined writers.
+        /// - [`NotIntoDepositWindow`](Error::NotIntoDepositWindow): raised when the option is not
+        ///   in deposit phase.
+        /// - [`OpenInterestLimitExceeded`](Error::OpenInterestLimitExceeded): raised when the
+        ///   options written would exceed the maximum open interest of the base asset.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::sell_option_with_margin())]
+        #[transactional]
+        pub fn sell_option_with_margin(
+            origin: OriginFor<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+            margin: BalanceOf<T>,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            OptionIdToOption::<T>::try_mutate(option_id, |option| {
+                let option = option.as_mut().ok_or(Error::<T>::OptionDoesNotExists)?;
+                Self::do_sell_option_with_margin(&from, option_amount, option_id, margin, option)
+            })?;
+
+            Ok(())
+        }
+
+        /// Add margin to a margin position, to keep it above the margin required when the spot
+        /// price moves against the writer.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `option_id`: the option id.
+        /// - `amount`: the margin to add, in the collateral asset of the option.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        /// 2. The user should have a margin position for the option.
+        /// 3. The option should not be settled yet.
+        ///
+        /// ## Emits
+        /// - [`Event::MarginAdded`]
+        ///
+        /// ## State Changes
+        /// - Updates the margin of the user in the [`MarginPositions`] storage.
+        ///
+        /// ## Errors
+        /// - [`UserDoesNotHaveMarginPosition`](Error::UserDoesNotHaveMarginPosition): raised when
+        ///   the user does not have a margin position for the option.
+        /// - [`NotIntoPurchaseWindow`](Error::NotIntoPurchaseWindow): raised when the option is not
+        ///   in deposit or purchase phase.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::add_margin())]
+        #[transactional]
+        pub fn add_margin(
+            origin: OriginFor<T>,
+            option_id: OptionIdOf<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            Self::do_add_margin(&from, option_id, amount)?;
+
+            Ok(())
+        }
+
+        /// Liquidate a margin position whose margin is lower than the margin required at the
+        /// current spot price. Anyone can call it, for example when the oracle price moves. The
+        /// liquidator buys the options written back from the writer by posting their full
+        /// collateral into the vault, taking over the position as a seller, and seizes the margin
+        /// of the writer.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `option_id`: the option id.
+        /// - `writer`: the account owning the margin position.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the liquidator.
+        /// 2. The option should be in deposit or purchase phase.
+        /// 3. The margin of the position should be lower than the margin required.
+        ///
+        /// ## Emits
+        /// - [`Event::MarginPositionLiquidated`]
+        ///
+        /// ## State Changes
+        /// - Deletes the position of the writer from the [`MarginPositions`] storage.
+        /// - Updates the [`Sellers`] storage with the position taken over by the liquidator.
+        /// - Updates the [`OptionIdToOption`] storage moving the options from the margined options
+        ///   to the options backed by the vault.
+        ///
+        /// ## Errors
+        /// - [`UserDoesNotHaveMarginPosition`](Error::UserDoesNotHaveMarginPosition): raised when
+        ///   `writer` does not have a margin position for the option.
+        /// - [`MarginPositionIsHealthy`](Error::MarginPositionIsHealthy): raised when the margin of
+        ///   the position is not lower than the margin required.
+        /// - [`UserHasNotEnoughFundsToDeposit`](Error::UserHasNotEnoughFundsToDeposit): raised when
+        ///   the liquidator can't post the full collateral.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::liquidate_margin_position())]
+        #[transactional]
+        pub fn liquidate_margin_position(
+            origin: OriginFor<T>,
+            option_id: OptionIdOf<T>,
+            writer: AccountIdOf<T>,
+        ) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            OptionIdToOption::<T>::try_mutate(option_id, |option| {
+                let option = option.as_mut().ok_or(Error::<T>::OptionDoesNotExists)?;
+                Self::do_liquidate_margin_position(&from, &writer, option_id, option)
+            })?;
+
+            Ok(())
+        }
+
+        /// Withdraw the margin left after settlement and the part of the premium of a margined
+        /// writer.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `option_id`: the option id.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        /// 2. The user should have a margin position for the option.
+        /// 3. The option should be in exercise phase or ended.
+        ///
+        /// ## Emits
+        /// - [`Event::WithdrawMargin`]
+        ///
+        /// ## State Changes
+        /// - Deletes the position of the user from the [`MarginPositions`] storage.
+        ///
+        /// ## Errors
+        /// - [`UserDoesNotHaveMarginPosition`](Error::UserDoesNotHaveMarginPosition): raised when
+        ///   the user does not have a margin position for the option.
+        /// - [`NotIntoExerciseWindow`](Error::NotIntoExerciseWindow): raised when the option is not
+        ///   settled yet.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::withdraw_margin())]
+        #[transactional]
+        pub fn withdraw_margin(origin: OriginFor<T>, option_id: OptionIdOf<T>) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            Self::do_withdraw_margin(&from, option_id)?;
+
+            Ok(())
+        }
+
+        /// Update the fees taken by the protocol. The premium fee is a share of the premium paid
+        /// by buyers, which is subtracted from the premium collected by sellers. The exercise fee
+        /// is a share of the payout of cash settled options exercised in the money. Each fee is
+        /// capped for each option at `max_fee_per_option`, in quote asset.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `fees`: the new fees.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been made by the protocol origin.
+        ///
+        /// ## Emits
+        /// - [`Event::ProtocolFeesUpdated`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`ProtocolFees`] storage.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::update_protocol_fees())]
+        pub fn update_protocol_fees(origin: OriginFor<T>, fees: FeeConfigOf<T>) -> DispatchResult {
+            T::ProtocolOrigin::ensure_origin(origin)?;
+
+            ProtocolFees::<T>::put(fees);
+
+            Self::deposit_event(Event::ProtocolFeesUpdated { fees });
+
+            Ok(())
+        }
+
+        /// Set or remove the risk limits of the options having `asset_id` as base asset. They
+        /// are checked when an option is created, sold or bought, and options already created
+        /// are subject to the new limits from then on.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `asset_id`: the underlying asset the limits apply to.
+        /// - `limits`: the new limits, or `None` to remove them.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been made by the protocol origin.
+        /// 2. The minimum epoch length should not be greater than the maximum one.
+        ///
+        /// ## Emits
+        /// - [`Event::RiskLimitsUpdated`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`AssetRiskLimits`] storage.
+        ///
+        /// ## Errors
+        /// - [`RiskLimitsAreInvalid`](Error::RiskLimitsAreInvalid): raised when the minimum epoch
+        ///   length is greater than the maximum one.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::update_risk_limits())]
+        pub fn update_risk_limits(
+            origin: OriginFor<T>,
+            asset_id: AssetIdOf<T>,
+            limits: Option<RiskLimitsOf<T>>,
+        ) -> DispatchResult {
+            T::ProtocolOrigin::ensure_origin(origin)?;
+
+            if let Some(limits) = limits {
+                ensure!(
+                    limits.min_epoch_length <= limits.max_epoch_length,
+                    Error::<T>::RiskLimitsAreInvalid
+                );
+            }
+
+            AssetRiskLimits::<T>::set(asset_id, limits);
+
+            Self::deposit_event(Event::RiskLimitsUpdated { asset_id, limits });
+
+            Ok(())
+        }
+
+        /// Harvest the yield earned by the collateral of the vault of `asset_id` allocated to the
+        /// [`CollateralStrategy`](Config::CollateralStrategy) and move funds between the vault and
+        /// the strategy to match the share of the collateral allocated to it. The yield is
+        /// deposited into the vault, so it accrues to the shares of sellers.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `asset_id`: the asset of the vault.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        /// 2. The vault of the asset should exist.
+        ///
+        /// ## Emits
+        /// - [`Event::CollateralStrategyRebalanced`]
+        ///
+        /// ## Errors
+        /// - [`AssetVaultDoesNotExists`](Error::AssetVaultDoesNotExists): raised when the vault of
+        ///   the asset does not exist.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::rebalance_collateral_strategy())]
+        #[transactional]
+        pub fn rebalance_collateral_strategy(
+            origin: OriginFor<T>,
+            asset_id: AssetIdOf<T>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            Self::do_rebalance_collateral_strategy(asset_id)?;
+
+            Ok(())
+        }
+
+        /// Exercise all the options of `holder` for them once the option is settled, paying the
+        /// payout to the holder. The caller is rewarded with the
+        /// [`ExerciseKeeperReward`](Config::ExerciseKeeperReward) share of the payout, so that
+        /// holders who forget to exercise still receive the payout of options in the money.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `holder`: the account holding the options.
+        /// - `option_id`: the option id.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the keeper.
+        /// 2. The option should be cash settled, in exercise phase, and in the money.
+        /// 3. The holder should own options and should not have opted out of auto-exercise.
+        ///
+        /// ## Emits
+        /// - [`Event::ExerciseOption`]
+        /// - [`Event::ProtocolFeePaid`], if an exercise fee is set.
+        /// - [`Event::ExercisedFor`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`Buyers`] storage removing the options exercised from the position of the
+        ///   holder.
+        ///
+        /// ## Errors
+        /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when the option does not
+        ///   exist.
+        /// - [`NotIntoExerciseWindow`](Error::NotIntoExerciseWindow): raised when the option is not
+        ///   in exercise phase.
+        /// - [`OptionCannotBeAutoExercised`](Error::OptionCannotBeAutoExercised): raised when the
+        ///   option is not cash settled or it expired out of the money.
+        /// - [`AutoExerciseDisabled`](Error::AutoExerciseDisabled): raised when the holder opted
+        ///   out of auto-exercise.
+        /// - [`CannotPassZeroOptionAmount`](Error::CannotPassZeroOptionAmount): raised when the
+        ///   holder does not own options.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::exercise_for())]
+        #[transactional]
+        pub fn exercise_for(
+            origin: OriginFor<T>,
+            holder: AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+        ) -> DispatchResult {
+            let keeper = ensure_signed(origin)?;
+
+            OptionIdToOption::<T>::try_mutate(option_id, |option| {
+                let option = option.as_mut().ok_or(Error::<T>::OptionDoesNotExists)?;
+                Self::do_exercise_for(&keeper, &holder, option_id, option)
+            })?;
+
+            Ok(())
+        }
+
+        /// Opt in or out of having options exercised by keepers with
+        /// [`exercise_for`](Pallet::exercise_for). Users are opted in by default.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `origin`: type representing the origin of this dispatch.
+        /// - `enabled`: whether options can be exercised for the user.
+        ///
+        /// ## Requirements
+        /// 1. The call must have been signed by the user.
+        ///
+        /// ## Emits
+        /// - [`Event::AutoExerciseUpdated`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`AutoExerciseOptOut`] storage.
+        ///
+        /// # Weight: O(TBD)
+        #[pallet::weight(<T as Config>::WeightInfo::update_auto_exercise())]
+        pub fn update_auto_exercise(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+
+            if enabled {
+                AutoExerciseOptOut::<T>::remove(&from);
+            } else {
+                AutoExerciseOptOut::<T>::insert(&from, ());
+            }
+
+            Self::deposit_event(Event::AutoExerciseUpdated {
+                user: from,
+                enabled,
+            });
+
+            Ok(())
+        }
+    }
+
+    // ----------------------------------------------------------------------------------------------------
+    //		TokenizedOptions Trait
+    // ----------------------------------------------------------------------------------------------------
+    impl<T: Config> TokenizedOptions for Pallet<T> {
+        type AccountId = AccountIdOf<T>;
+        type OptionId = OptionIdOf<T>;
+        type Balance = BalanceOf<T>;
+        type Moment = MomentOf<T>;
+        type VaultId = VaultIdOf<T>;
+        type OptionConfig = OptionConfigOf<T>;
+        type VaultConfig = VaultConfigOf<T>;
+
+        /// Create a new vault for the given asset and save the vault id on storage.
+        ///
+        /// # Overview
+        /// ## Parameters
+        /// - `vault_config`: the configuration of the vault to create.
+        ///
+        /// ## Requirements
+        /// 1. The vault should not already exist.
+        /// 2. The asset should be supported by the Oracle.
+        ///
+        /// ## Emits
+        /// - [`Event::CreatedAssetVault`]
+        ///
+        /// ## State Changes
+        /// - Updates the [`AssetToVault`] storage mapping the asset id with the new created vault
+        ///   id.
+        ///
+        /// ## Errors
+        /// - [`AssetIsNotSupported`](Error::AssetIsNotSupported): raised when trying to create a
+        ///   new vault,
+        ///  but the asset is not supported by the Oracle.
+        /// - [`AssetVaultAlreadyExists`](Error::AssetVaultAlreadyExists): raised when trying to
+        ///   create a new vault,
+        /// but it already exists.
         ///
         /// # Weight: O(TBD)
         #[transactional]
@@ -928,6 +2394,7 @@ pub mod pallet {
                 Err(error) => match error {
                     "ValidateVaultDoesNotExist" => Err(Error::<T>::AssetVaultAlreadyExists.into()),
                     "ValidateAssetIsSupported" => Err(Error::<T>::AssetIsNotSupported.into()),
+                    "ValidateVaultStrategies" => Err(Error::<T>::VaultStrategiesAreInvalid.into()),
                     _ => Err(Error::<T>::UnexpectedError.into()),
                 },
             }
@@ -944,6 +2411,7 @@ pub mod pallet {
         /// 1. The option should not already exist.
         /// 2. Both the base asset and the quote asset vaults should exist.
         /// 3. The option attributes should all have valid values.
+        /// 4. The option should respect the risk limits of its base asset, if any.
         ///
         /// ## Emits
         /// - [`Event::CreatedOption`]
@@ -966,6 +2434,8 @@ pub mod pallet {
         /// - [`OptionAttributesAreInvalid`](Error::OptionAttributesAreInvalid): raised when trying
         ///   to create a new option,
         /// but at least one of the option's attributes has an invalid value.
+        /// - [`OptionOutsideRiskLimits`](Error::OptionOutsideRiskLimits): raised when the option is
+        ///   outside the risk limits of its base asset.
         ///
         /// # Weight: O(TBD)
         #[transactional]
@@ -980,6 +2450,7 @@ pub mod pallet {
                         Err(Error::<T>::OptionAssetVaultsDoNotExist.into()),
                     "ValidateOptionAttributes" =>
                         Err(Error::<T>::OptionAttributesAreInvalid.into()),
+                    "ValidateOptionRiskLimits" => Err(Error::<T>::OptionOutsideRiskLimits.into()),
                     _ => Err(Error::<T>::UnexpectedError.into()),
                 },
             }
@@ -1007,6 +2478,7 @@ pub mod pallet {
         /// - Updates the [`OptionIdToOption`] storage adding the amount of option to sell to the
         ///   total amount
         /// already for sale.
+        /// - Adds the amount of option to sell to the [`OpenInterest`] of the base asset.
         ///
         /// ## Errors
         /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when trying to retrieve
@@ -1027,6 +2499,8 @@ pub mod pallet {
         /// - [`CannotPassZeroOptionAmount`](Error::CannotPassZeroOptionAmount): raised when trying
         ///   to sell an option,
         /// but the option amount is zero.
+        /// - [`OpenInterestLimitExceeded`](Error::OpenInterestLimitExceeded): raised when the
+        ///   options written would exceed the maximum open interest of the base asset.
         ///
         /// # Weight: O(TBD)
         #[transactional]
@@ -1064,6 +2538,8 @@ pub mod pallet {
         /// - Updates the [`OptionIdToOption`] storage subtracting the amount of option to delete
         ///   the sale of from the total amount
         /// already for sale.
+        /// - Subtracts the amount of option to delete the sale of from the [`OpenInterest`] of the
+        ///   base asset.
         ///
         /// ## Errors
         /// - [`OptionDoesNotExists`](Error::OptionDoesNotExists): raised when trying to retrieve
@@ -1110,11 +2586,13 @@ pub mod pallet {
         /// - `from`: user's account id.
         /// - `option_amount`: the amount of option the user wants to buy.
         /// - `option_id`: the option id.
+        /// - `max_premium`: the maximum total premium the user is willing to pay.
         ///
         /// ## Requirements
         /// 1. The option should exist.
         /// 2. The option should be in purchase phase.
         /// 3. The option amount should not be zero.
+        /// 4. The premium should not be greater than `max_premium`.
         ///
         /// ## Emits
         /// - [`Event::BuyOption`]
@@ -1140,6 +2618,11 @@ pub mod pallet {
         /// - [`CannotPassZeroOptionAmount`](Error::CannotPassZeroOptionAmount): raised when trying
         ///   to buy an option,
         /// but the option amount is zero.
+        /// - [`PremiumExceedsMaxPremium`](Error::PremiumExceedsMaxPremium): raised when trying to
+        ///   buy an option,
+        /// but the premium is greater than `max_premium`.
+        /// - [`TimeToExpiryBelowLimit`](Error::TimeToExpiryBelowLimit): raised when the expiring
+        ///   date is closer than the minimum time to expiry of the base asset.
         ///
         /// # Weight: O(TBD)
         #[transactional]
@@ -1147,9 +2630,11 @@ pub mod pallet {
             from: &Self::AccountId,
             option_amount: Self::Balance,
             option_id: Self::OptionId,
+            max_premium: Self::Balance,
         ) -> Result<(), DispatchError> {
             OptionIdToOption::<T>::try_mutate(option_id, |option| match option {
-                Some(option) => Self::do_buy_option(from, option_amount, option_id, option),
+                Some(option) =>
+                    Self::do_buy_option(from, option_amount, option_id, max_premium, option),
                 None => Err(Error::<T>::OptionDoesNotExists.into()),
             })
         }
@@ -1164,7 +2649,8 @@ pub mod pallet {
         ///
         /// ## Requirements
         /// 1. The option should exist.
-        /// 2. The option should be in exercise phase.
+        /// 2. The option should be in exercise phase, or in purchase phase before expiry if it is
+        /// an American option.
         /// 3. The option amount should not be zero.
         ///
         /// ## Emits
@@ -1193,10 +2679,10 @@ pub mod pallet {
             option_amount: Self::Balance,
             option_id: Self::OptionId,
         ) -> Result<(), DispatchError> {
-            match Self::option_id_to_option(option_id) {
-                Some(option) => Self::do_exercise_option(from, option_amount, option_id, &option),
+            OptionIdToOption::<T>::try_mutate(option_id, |option| match option {
+                Some(option) => Self::do_exercise_option(from, option_amount, option_id, option),
                 None => Err(Error::<T>::OptionDoesNotExists.into()),
-            }
+            })
         }
 
         /// Withdraw the seller's collateral related to the indicated option.
@@ -1208,7 +2694,8 @@ pub mod pallet {
         ///
         /// ## Requirements
         /// 1. The option should exist.
-        /// 2. The option should be in exercise phase.
+        /// 2. The option should be in exercise phase or ended, and ended if it is physically
+        ///    settled. Ended options can be withdrawn from until they are cleaned up.
         ///
         /// ## Emits
         /// - [`Event::WithdrawCollateral`]
@@ -1223,6 +2710,9 @@ pub mod pallet {
         /// - [`NotIntoExerciseWindow`](Error::NotIntoExerciseWindow): raised when trying to
         ///   withdraw collateral,
         /// but it is not exercise phase for that option.
+        /// - [`ExerciseWindowNotOver`](Error::ExerciseWindowNotOver): raised when trying to
+        ///   withdraw the collateral of a physically settled option before the end of its exercise
+        ///   phase.
         /// - [`UserDoesNotHaveSellerPosition`](Error::UserDoesNotHaveSellerPosition): raised when
         ///   trying to withdraw collateral,
         /// but the seller has not a seller position.
@@ -1252,7 +2742,11 @@ pub mod pallet {
         fn do_create_asset_vault(
             config: Validated<
                 VaultConfigOf<T>,
-                (ValidateVaultDoesNotExist<T>, ValidateAssetIsSupported<T>),
+                (
+                    ValidateVaultDoesNotExist<T>,
+                    ValidateAssetIsSupported<T>,
+                    ValidateVaultStrategies<T>,
+                ),
             >,
         ) -> Result<VaultIdOf<T>, DispatchError> {
             // Get pallet account for the asset
@@ -1287,6 +2781,7 @@ pub mod pallet {
                     ValidateOptionDoesNotExist<T>,
                     ValidateOptionAssetVaultsExist<T>,
                     ValidateOptionAttributes<T>,
+                    ValidateOptionRiskLimits<T>,
                 ),
             >,
         ) -> Result<OptionIdOf<T>, DispatchError> {
@@ -1301,6 +2796,7 @@ pub mod pallet {
                 quote_asset_strike_price: option_config.quote_asset_strike_price,
                 option_type: option_config.option_type,
                 exercise_type: option_config.exercise_type,
+                settlement_type: option_config.settlement_type,
                 expiring_date: option_config.expiring_date,
                 epoch: option_config.epoch,
                 status: Status::NotStarted,
@@ -1312,6 +2808,11 @@ pub mod pallet {
                 base_asset_spot_price: option_config.base_asset_spot_price,
                 total_issuance_buyer: option_config.total_issuance_buyer,
                 total_shares_amount: option_config.total_shares_amount,
+                total_exercised_early: BalanceOf::<T>::zero(),
+                total_shares_exercised_early: BalanceOf::<T>::zero(),
+                total_delivered_amount: BalanceOf::<T>::zero(),
+                total_buyback_paid: BalanceOf::<T>::zero(),
+                total_issuance_margined: BalanceOf::<T>::zero(),
             };
 
             let option_hash = option.generate_id();
@@ -1329,34 +2830,80 @@ pub mod pallet {
             Ok(option_id)
         }
 
-        fn do_sell_option(
+        pub(crate) fn do_sell_option(
             from: &AccountIdOf<T>,
             option_amount: BalanceOf<T>,
             option_id: OptionIdOf<T>,
             option: &mut OptionToken<T>,
         ) -> Result<(), DispatchError> {
-            ensure!(
-                option_amount != BalanceOf::<T>::zero(),
-                Error::<T>::CannotPassZeroOptionAmount
-            );
-
             // Check if we are in deposit window
             ensure!(
                 option.status == Status::Deposit,
                 Error::<T>::NotIntoDepositWindow
             );
 
-            // Different behaviors based on Call or Put option
-            let (asset_id, asset_amount) = match option.option_type {
-                // For CALL options it should be `base_asset_amount_per_option` *
-                // `quote_asset_strike_price`
-                OptionType::Call => (option.base_asset_id, option.quote_asset_strike_price),
-                // For PUT options it should be `quote_asset_amount_per_option` *
-                // `base_asset_strike_price`
-                OptionType::Put => (option.quote_asset_id, option.base_asset_strike_price),
+            let collateral_per_option = Self::collateral_per_option(option);
+
+            Self::do_sell_option_with_collateral(
+                from,
+                option_amount,
+                option_id,
+                collateral_per_option,
+                option,
+            )
+        }
+
+        /// Sells `option_amount` options locking `collateral_per_option` for each of them, which
+        /// is less than the full collateral when the sale is covered by other options. The phase
+        /// of the option is checked by the caller.
+        pub(crate) fn do_sell_option_with_collateral(
+            from: &AccountIdOf<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+            collateral_per_option: BalanceOf<T>,
+            option: &mut OptionToken<T>,
+        ) -> Result<(), DispatchError> {
+            ensure!(
+                option_amount != BalanceOf::<T>::zero(),
+                Error::<T>::CannotPassZeroOptionAmount
+            );
+
+            // Checked here so that strategies and rollovers are bound by it too
+            Validated::<_, ValidateOpenInterest<T>>::new((option_id, option_amount))
+                .map_err(|_| Error::<T>::OpenInterestLimitExceeded)?;
+
+            Self::deposit_seller_collateral(
+                from,
+                option_amount,
+                option_id,
+                collateral_per_option,
+                option,
+            )?;
+
+            Self::deposit_event(Event::SellOption {
+                user: from.clone(),
+                option_amount,
+                option_id,
+            });
+
+            Ok(())
+        }
+
+        /// Deposits into the vault the collateral of `option_amount` options sold by `from` and
+        /// adds them to its seller position.
+        pub(crate) fn deposit_seller_collateral(
+            from: &AccountIdOf<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+            collateral_per_option: BalanceOf<T>,
+            option: &mut OptionToken<T>,
+        ) -> Result<(), DispatchError> {
+            let asset_id = match option.option_type {
+                OptionType::Call => option.base_asset_id,
+                OptionType::Put => option.quote_asset_id,
             };
 
-            let asset_amount = asset_amount
+            let asset_amount = collateral_per_option
                 .checked_mul(&option_amount)
                 .ok_or(ArithmeticError::Overflow)?;
 
@@ -1390,6 +2937,7 @@ pub mod pallet {
                         *position = Some(SellerPosition {
                             option_amount,
                             shares_amount,
+                            unsold_collateral_released: false,
                         }),
                 }
                 Ok(())
@@ -1412,13 +2960,7 @@ pub mod pallet {
 
             option.total_issuance_seller = new_total_issuance_seller;
 
-            Self::deposit_event(Event::SellOption {
-                user: from.clone(),
-                option_amount,
-                option_id,
-            });
-
-            Ok(())
+            Self::increase_open_interest(option, option_amount)
         }
 
         fn do_delete_sell_option(
@@ -1444,6 +2986,12 @@ pub mod pallet {
                 .as_mut()
                 .ok_or(Error::<T>::UserDoesNotHaveSellerPosition)?;
 
+            // Netted positions are covered by other options as a whole
+            ensure!(
+                !SpreadPledges::<T>::contains_key(option_id, from),
+                Error::<T>::NettedPositionCannotBeDeleted
+            );
+
             // Different behaviors based on Call or Put option
             let asset_id = match option.option_type {
                 OptionType::Call => option.base_asset_id,
@@ -1495,6 +3043,7 @@ pub mod pallet {
             }
             // Protocol account withdraw from the vault and burn shares_amount
             let protocol_account = Self::account_id(asset_id);
+            Self::recall_strategy_funds(asset_id, &vault_id, asset_amount)?;
             VaultOf::<T>::withdraw(&vault_id, &protocol_account, shares_amount)
                 .map_err(|_| Error::<T>::VaultWithdrawNotAllowed)?;
 
@@ -1508,6 +3057,7 @@ pub mod pallet {
                 .ok_or(ArithmeticError::Overflow)?;
 
             option.total_issuance_seller = new_total_issuance_seller;
+            Self::decrease_open_interest(option, option_amount);
 
             Self::deposit_event(Event::DeleteSellOption {
                 user: from.clone(),
@@ -1517,10 +3067,11 @@ pub mod pallet {
             Ok(())
         }
 
-        fn do_buy_option(
+        pub(crate) fn do_buy_option(
             from: &AccountIdOf<T>,
             option_amount: BalanceOf<T>,
             option_id: OptionIdOf<T>,
+            max_premium: BalanceOf<T>,
             option: &mut OptionToken<T>,
         ) -> Result<(), DispatchError> {
             ensure!(
@@ -1536,59 +3087,67 @@ pub mod pallet {
                 Error::<T>::NotIntoPurchaseWindow
             );
 
-            // Fake call to pricing pallet (to replace with actual call to pricing pallet)
-            // let option_premium = Self::fake_option_price().expect("Error pricing option");
+            // Checked here so that strategies are bound by it too
+            Validated::<_, ValidateTimeToExpiry<T>>::new(option_id)
+                .map_err(|_| Error::<T>::TimeToExpiryBelowLimit)?;
 
-            let bs_params = BlackScholesParams {
-                base_asset_id: option.base_asset_id,
-                base_asset_strike_price: option.base_asset_strike_price,
-                base_asset_spot_price: option.base_asset_spot_price,
-                expiring_date: option.expiring_date,
-                option_type: option.option_type,
-                total_issuance_buyer: option.total_issuance_buyer,
-                total_premium_paid: option.total_premium_paid,
-            };
-
-            // let option_premium =
-            // 	OptionsPricingOf::<T>::calculate_option_price(option_id, bs_params)?;
+            // Check the premium is within the slippage bound set by the user
+            let option_premium = Self::calculate_option_premium(option_id, option_amount, option)?;
 
-            let option_premium = Self::fake_option_price();
-
-            let option_premium = option_premium
-                .checked_mul(&option_amount)
-                .ok_or(ArithmeticError::Overflow)?;
+            ensure!(
+                option_premium <= max_premium,
+                Error::<T>::PremiumExceedsMaxPremium
+            );
 
-            // Check option availability
+            // Check option availability, options exercised early are not available anymore
             let total_issuance_buyer = AssetsOf::<T>::total_issuance(option_id);
 
             let new_total_issuance_buyer = total_issuance_buyer
-                .checked_add(&option_amount)
+                .checked_add(&option.total_exercised_early)
+                .and_then(|amount| amount.checked_add(&option_amount))
                 .ok_or(ArithmeticError::Overflow)?;
 
-            // Check if there are enough options for sale
-            if new_total_issuance_buyer > option.total_issuance_seller {
+            // Check if there are enough options for sale, written with collateral or margin
+            if new_total_issuance_buyer > Self::total_issuance_written(option)? {
                 return Err(DispatchError::from(Error::<T>::NotEnoughOptionsForSale))
             }
 
+            // The protocol fee is taken from the premium, sellers collect the rest
+            let protocol_fee = Self::calculate_premium_fee(option_amount, option_premium);
+            let sellers_premium = option_premium
+                .checked_sub(&protocol_fee)
+                .ok_or(ArithmeticError::Underflow)?;
+
             let new_total_premium_paid = option
                 .total_premium_paid
-                .checked_add(&option_premium)
+                .checked_add(&sellers_premium)
                 .ok_or(ArithmeticError::Overflow)?;
 
             option.total_premium_paid = new_total_premium_paid;
 
             // Transfer premium to protocol account
             let protocol_account = Self::account_id(stablecoin_id);
-            AssetsOf::<T>::transfer(stablecoin_id, from, &protocol_account, option_premium, true)
+            AssetsOf::<T>::transfer(
+                stablecoin_id,
+                from,
+                &protocol_account,
+                sellers_premium,
+                true,
+            )
+            .map_err(|_| Error::<T>::UserHasNotEnoughFundsToDeposit)?;
+
+            Self::pay_protocol_fee(from, from, option_id, stablecoin_id, protocol_fee)
                 .map_err(|_| Error::<T>::UserHasNotEnoughFundsToDeposit)?;
 
             // Mint option token into user's account
             AssetsOf::<T>::mint_into(option_id, from, option_amount)?;
+            Self::add_to_buyer_position(from, option_id, option_amount, option_premium)?;
 
             Self::deposit_event(Event::BuyOption {
                 user: from.clone(),
                 option_amount,
                 option_id,
+                option_premium,
             });
 
             Ok(())
@@ -1611,9 +3170,12 @@ pub mod pallet {
         /// - For each option, updates the [`OptionIdToOption`] storage calculating the exercise
         ///   amount for buyers and saving
         /// the info to calculate the remaining collateral for sellers and their share of premium.
+        /// - For cash settled options, withdraws from the vault the collateral owed to buyers.
+        /// Physically settled options withdraw it when each option is exercised instead.
         ///
         /// ## Errors
-        /// - There should not be errors in any case.
+        /// - [`AssetPriceNotFound`](Error::AssetPriceNotFound): raised when the oracle price of the
+        ///   base asset is not available. The option is then left pending settlement.
         ///
         /// # Weight: O(TBD)
         pub(crate) fn do_settle_option(
@@ -1623,6 +3185,16 @@ pub mod pallet {
             // Get current asset's spot price
             let base_asset_spot_price = Self::get_price(option.base_asset_id)?;
 
+            Self::do_settle_option_at_price(option_id, base_asset_spot_price, option)
+        }
+
+        /// Settle the option specified by `option_id` at `base_asset_spot_price`, as
+        /// `do_settle_option` does with the oracle price.
+        pub(crate) fn do_settle_option_at_price(
+            option_id: OptionIdOf<T>,
+            base_asset_spot_price: BalanceOf<T>,
+            option: &mut OptionToken<T>,
+        ) -> Result<(), DispatchError> {
             // Get total options bought
             let total_issuance_buyer = AssetsOf::<T>::total_issuance(option_id);
 
@@ -1638,115 +3210,481 @@ pub mod pallet {
                 ),
             };
 
-            // Calculate total_collateral to withdraw for buyers and corresponding amount of shares
+            match option.settlement_type {
+                SettlementType::Cash => {
+                    // Calculate total_collateral to withdraw for buyers and corresponding amount
+                    // of shares
+                    let protocol_account = Self::account_id(asset_id);
+
+                    let vault_id = Self::asset_id_to_vault_id(asset_id)
+                        .ok_or(Error::<T>::AssetVaultDoesNotExists)?;
+
+                    // Margined writers pay their part of the options bought from their margin, the
+                    // vault only pays for the rest
+                    let (margined_options_bought, margin_shortfall) =
+                        Self::settle_margin_positions(
+                            option_id,
+                            collateral_for_option,
+                            total_issuance_buyer,
+                            option,
+                        )?;
+
+                    let total_collateral = collateral_for_option
+                        .checked_mul(&total_issuance_buyer.saturating_sub(margined_options_bought))
+                        .ok_or(ArithmeticError::Overflow)?;
+
+                    let total_shares_amount = VaultOf::<T>::amount_of_lp_token_for_added_liquidity(
+                        &vault_id,
+                        total_collateral,
+                    )?;
+
+                    if total_shares_amount != BalanceOf::<T>::zero() {
+                        // Collateral allocated to the strategy is recalled to pay buyers
+                        Self::recall_strategy_funds(asset_id, &vault_id, total_collateral)?;
+
+                        VaultOf::<T>::withdraw(&vault_id, &protocol_account, total_shares_amount)
+                            .map_err(|_| Error::<T>::VaultWithdrawNotAllowed)?;
+                    };
+
+                    // Buyers share the payoff margined writers could not cover
+                    option.exercise_amount = if margin_shortfall.is_zero() {
+                        collateral_for_option
+                    } else {
+                        let total_payoff = collateral_for_option
+                            .checked_mul(&total_issuance_buyer)
+                            .ok_or(ArithmeticError::Overflow)?
+                            .saturating_sub(margin_shortfall);
+
+                        total_payoff
+                            .checked_div(&total_issuance_buyer)
+                            .ok_or(ArithmeticError::DivisionByZero)?
+                    };
+                    option.total_shares_amount = total_shares_amount;
+                },
+                SettlementType::Physical => {
+                    // Only options in the money can be exercised, for the whole collateral
+                    option.exercise_amount = if collateral_for_option != BalanceOf::<T>::zero() {
+                        Self::physical_settlement_amounts(option).1
+                    } else {
+                        BalanceOf::<T>::zero()
+                    };
+                },
+            }
+
+            // Update option to calculate buyers and sellers positions
+            // in exercise_option and withdraw_collateral functions
+            option.base_asset_spot_price = base_asset_spot_price;
+            option.total_issuance_buyer = total_issuance_buyer;
+
+            Self::deposit_event(Event::SettleOption { option_id });
+
+            Ok(())
+        }
+
+        pub(crate) fn do_exercise_option(
+            from: &AccountIdOf<T>,
+            option_amount: BalanceOf<T>,
+            option_id: OptionIdOf<T>,
+            option: &mut OptionToken<T>,
+        ) -> Result<(), DispatchError> {
+            ensure!(
+                option_amount != BalanceOf::<T>::zero(),
+                Error::<T>::CannotPassZeroOptionAmount
+            );
+
+            // Check if we are in exercise window, American options can also be exercised early
+            let is_early_exercise = match option.status {
+                Status::Exercise => false,
+                Status::Purchase
+                    if option.exercise_type == ExerciseType::American &&
+                        T::Time::now() < option.expiring_date =>
+                    true,
+                _ => return Err(Error::<T>::NotIntoExerciseWindow.into()),
+            };
+
+            let (asset_id, total_amount_to_exercise) = match option.settlement_type {
+                SettlementType::Cash if is_early_exercise =>
+                    Self::withdraw_early_exercise_amount(option_amount, option)?,
+                SettlementType::Cash => Self::settled_exercise_amount(option_amount, option)?,
+                SettlementType::Physical =>
+                    Self::do_physical_exercise(from, option_amount, is_early_exercise, option)?,
+            };
+
+            let protocol_account = Self::account_id(asset_id);
+
+            // The protocol fee is taken from the payout of cash settled options
+            let protocol_fee = match option.settlement_type {
+                SettlementType::Cash if total_amount_to_exercise != BalanceOf::<T>::zero() => {
+                    let base_asset_spot_price = if is_early_exercise {
+                        Self::get_price(option.base_asset_id)?
+                    } else {
+                        option.base_asset_spot_price
+                    };
+
+                    Self::calculate_exercise_fee(
+                        option_amount,
+                        total_amount_to_exercise,
+                        base_asset_spot_price,
+                        option,
+                    )?
+                },
+                _ => BalanceOf::<T>::zero(),
+            };
+
+            Self::pay_protocol_fee(&protocol_account, from, option_id, asset_id, protocol_fee)?;
+
+            let total_amount_to_exercise = total_amount_to_exercise
+                .checked_sub(&protocol_fee)
+                .ok_or(ArithmeticError::Underflow)?;
+
+            // Transfer buyer profit to buyer account if option is ITM
+            if total_amount_to_exercise != BalanceOf::<T>::zero() {
+                AssetsOf::<T>::transfer(
+                    asset_id,
+                    &protocol_account,
+                    from,
+                    total_amount_to_exercise,
+                    true,
+                )
+                .map_err(|_| Error::<T>::UserHasNotEnoughOptionTokens)?;
+            }
+
+            // Burn option token from user's account
+            AssetsOf::<T>::burn_from(option_id, from, option_amount)
+                .map_err(|_| Error::<T>::UserHasNotEnoughOptionTokens)?;
+            Self::remove_from_buyer_position(from, option_id, option_amount, true)?;
+
+            Self::deposit_event(Event::ExerciseOption {
+                user: from.clone(),
+                option_amount,
+                option_id,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the collateral asset and the amount owed for exercising `option_amount` options
+        /// at the settlement price. The collateral has already been withdrawn from the vault by
+        /// `do_settle_option`.
+        pub(crate) fn settled_exercise_amount(
+            option_amount: BalanceOf<T>,
+            option: &OptionToken<T>,
+        ) -> Result<(AssetIdOf<T>, BalanceOf<T>), DispatchError> {
+            // Different behaviors based on Call or Put option
+            let asset_id = match option.option_type {
+                OptionType::Call => option.base_asset_id,
+                OptionType::Put => option.quote_asset_id,
+            };
+
+            let total_amount_to_exercise = option
+                .exercise_amount
+                .checked_mul(&option_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            Ok((asset_id, total_amount_to_exercise))
+        }
+
+        /// Withdraws from the vault the payoff of exercising `option_amount` cash settled American
+        /// options at the current spot price, returning the collateral asset and the amount
+        /// withdrawn.
+        fn withdraw_early_exercise_amount(
+            option_amount: BalanceOf<T>,
+            option: &mut OptionToken<T>,
+        ) -> Result<(AssetIdOf<T>, BalanceOf<T>), DispatchError> {
+            // Get current asset's spot price
+            let base_asset_spot_price = Self::get_price(option.base_asset_id)?;
+
+            // Different behaviors based on Call or Put option
+            let (asset_id, collateral_for_option) = match option.option_type {
+                OptionType::Call => (
+                    option.base_asset_id,
+                    Self::call_option_collateral_amount(base_asset_spot_price, option)?,
+                ),
+                OptionType::Put => (
+                    option.quote_asset_id,
+                    Self::put_option_collateral_amount(base_asset_spot_price, option)?,
+                ),
+            };
+
+            let total_collateral = collateral_for_option
+                .checked_mul(&option_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            let (total_amount_to_exercise, shares_amount) =
+                Self::withdraw_collateral_for_buyers(asset_id, total_collateral)?;
+
+            Self::record_early_exercise(option_amount, shares_amount, option)?;
+
+            Ok((asset_id, total_amount_to_exercise))
+        }
+
+        /// Exercises `option_amount` physically settled options: the buyer delivers the strike
+        /// (quote asset for calls, base asset for puts) and receives the whole collateral locked
+        /// for these options, withdrawn from the vault. Returns the collateral asset and the
+        /// amount withdrawn.
+        ///
+        /// Options exercised at expiry are only exercised if they settled in the money, while
+        /// American options exercised early are always exercised.
+        fn do_physical_exercise(
+            from: &AccountIdOf<T>,
+            option_amount: BalanceOf<T>,
+            is_early_exercise: bool,
+            option: &mut OptionToken<T>,
+        ) -> Result<(AssetIdOf<T>, BalanceOf<T>), DispatchError> {
+            let (
+                collateral_asset_id,
+                collateral_per_option,
+                delivery_asset_id,
+                delivery_per_option,
+            ) = Self::physical_settlement_amounts(option);
+
+            if !is_early_exercise && option.exercise_amount == BalanceOf::<T>::zero() {
+                return Ok((collateral_asset_id, BalanceOf::<T>::zero()))
+            }
+
+            // Transfer the asset delivered by the buyer to protocol account, sellers get it back
+            // when withdrawing their collateral
+            let delivery_amount = delivery_per_option
+                .checked_mul(&option_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            AssetsOf::<T>::transfer(
+                delivery_asset_id,
+                from,
+                &Self::account_id(delivery_asset_id),
+                delivery_amount,
+                true,
+            )
+            .map_err(|_| Error::<T>::UserHasNotEnoughFundsToDeposit)?;
+
+            let total_collateral = collateral_per_option
+                .checked_mul(&option_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            let (total_amount_to_exercise, shares_amount) =
+                Self::withdraw_collateral_for_buyers(collateral_asset_id, total_collateral)?;
+
+            option.total_delivered_amount = option
+                .total_delivered_amount
+                .checked_add(&delivery_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            if is_early_exercise {
+                Self::record_early_exercise(option_amount, shares_amount, option)?;
+            } else {
+                option.total_shares_amount = option
+                    .total_shares_amount
+                    .checked_add(&shares_amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+            }
+
+            Ok((collateral_asset_id, total_amount_to_exercise))
+        }
+
+        /// Withdraws `amount` of `asset_id` from its vault into the protocol account to pay
+        /// buyers, returning the amount withdrawn and the shares burnt.
+        fn withdraw_collateral_for_buyers(
+            asset_id: AssetIdOf<T>,
+            amount: BalanceOf<T>,
+        ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
             let protocol_account = Self::account_id(asset_id);
 
             let vault_id =
                 Self::asset_id_to_vault_id(asset_id).ok_or(Error::<T>::AssetVaultDoesNotExists)?;
 
-            let total_collateral = collateral_for_option
-                .checked_mul(&total_issuance_buyer)
-                .ok_or(ArithmeticError::Overflow)?;
+            let shares_amount =
+                VaultOf::<T>::amount_of_lp_token_for_added_liquidity(&vault_id, amount)?;
+
+            if shares_amount == BalanceOf::<T>::zero() {
+                return Ok((BalanceOf::<T>::zero(), BalanceOf::<T>::zero()))
+            }
 
-            let total_shares_amount =
-                VaultOf::<T>::amount_of_lp_token_for_added_liquidity(&vault_id, total_collateral)?;
+            Self::recall_strategy_funds(asset_id, &vault_id, amount)?;
 
-            if total_shares_amount != BalanceOf::<T>::zero() {
-                VaultOf::<T>::withdraw(&vault_id, &protocol_account, total_shares_amount)
+            let withdrawn_amount =
+                VaultOf::<T>::withdraw(&vault_id, &protocol_account, shares_amount)
                     .map_err(|_| Error::<T>::VaultWithdrawNotAllowed)?;
-            };
 
-            // Update option to calculate buyers and sellers positions
-            // in exercise_option and withdraw_collateral functions
-            option.exercise_amount = collateral_for_option;
-            option.base_asset_spot_price = base_asset_spot_price;
-            option.total_issuance_buyer = total_issuance_buyer;
-            option.total_shares_amount = total_shares_amount;
+            Ok((withdrawn_amount, shares_amount))
+        }
 
-            Self::deposit_event(Event::SettleOption { option_id });
+        /// Records the options exercised before expiry and the vault shares withdrawn to pay
+        /// them, so they are accounted for when selling options and when sellers withdraw their
+        /// collateral.
+        fn record_early_exercise(
+            option_amount: BalanceOf<T>,
+            shares_amount: BalanceOf<T>,
+            option: &mut OptionToken<T>,
+        ) -> Result<(), DispatchError> {
+            option.total_exercised_early = option
+                .total_exercised_early
+                .checked_add(&option_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            option.total_shares_exercised_early = option
+                .total_shares_exercised_early
+                .checked_add(&shares_amount)
+                .ok_or(ArithmeticError::Overflow)?;
 
             Ok(())
         }
 
-        fn do_exercise_option(
+        fn do_withdraw_collateral(
+            from: &AccountIdOf<T>,
+            option_id: OptionIdOf<T>,
+            option: &OptionToken<T>,
+            position: &mut Option<SellerPosition<T>>,
+        ) -> Result<(), DispatchError> {
+            // Check if we are in exercise window. Physically settled options can be exercised until
+            // the end of the window, so sellers have to wait for it to know what they were assigned
+            match option.settlement_type {
+                // Sellers can also withdraw during the claim window after the end
+                SettlementType::Cash => ensure!(
+                    option.status == Status::Exercise || option.status == Status::End,
+                    Error::<T>::NotIntoExerciseWindow
+                ),
+                SettlementType::Physical => ensure!(
+                    option.status == Status::End,
+                    Error::<T>::ExerciseWindowNotOver
+                ),
+            }
+
+            Self::release_seller_position(from, from, option_id, option, position)?;
+
+            Self::deposit_event(Event::WithdrawCollateral {
+                user: from.clone(),
+                option_id,
+            });
+
+            Ok(())
+        }
+
+        fn do_release_unsold_collateral(
             from: &AccountIdOf<T>,
-            option_amount: BalanceOf<T>,
             option_id: OptionIdOf<T>,
             option: &OptionToken<T>,
+            position: &mut Option<SellerPosition<T>>,
         ) -> Result<(), DispatchError> {
+            // Options can't be bought, sold back or exercised early after the purchase window, and
+            // the options bought are recorded at settlement
             ensure!(
-                option_amount != BalanceOf::<T>::zero(),
-                Error::<T>::CannotPassZeroOptionAmount
+                matches!(option.status, Status::Exercise | Status::End),
+                Error::<T>::PurchaseWindowNotOver
             );
 
-            // Check if we are in exercise window
+            let seller_position = position
+                .as_mut()
+                .ok_or(Error::<T>::UserDoesNotHaveSellerPosition)?;
+
             ensure!(
-                option.status == Status::Exercise,
-                Error::<T>::NotIntoExerciseWindow
+                !seller_position.unsold_collateral_released,
+                Error::<T>::UnsoldCollateralAlreadyReleased
             );
 
-            // Different behaviors based on Call or Put option
+            let options_bought = option.total_issuance_buyer;
+
+            // ------ Shares calculations for user ------
+            // options_sold = options bought from the vault, including the ones exercised early
+            // user_unsold_shares = user_shares * (total_option_for_sale - options_sold) /
+            // total_option_for_sale
+            let margined_options_bought = Self::convert_and_multiply_by_rational(
+                options_bought,
+                option.total_issuance_margined,
+                Self::total_issuance_written(option)?,
+                Rounding::Down,
+            )?;
+
+            let options_sold = options_bought
+                .saturating_sub(margined_options_bought)
+                .checked_add(&option.total_exercised_early)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            let unsold_shares_amount = Self::convert_and_multiply_by_rational(
+                seller_position.shares_amount,
+                option.total_issuance_seller.saturating_sub(options_sold),
+                option.total_issuance_seller,
+                Rounding::Down,
+            )?;
+
+            // The shares left are the ones withdrawn by release_seller_position
+            seller_position.shares_amount = seller_position
+                .shares_amount
+                .checked_sub(&unsold_shares_amount)
+                .ok_or(ArithmeticError::Underflow)?;
+            seller_position.unsold_collateral_released = true;
+
             let asset_id = match option.option_type {
                 OptionType::Call => option.base_asset_id,
                 OptionType::Put => option.quote_asset_id,
             };
 
-            let protocol_account = Self::account_id(asset_id);
+            let collateral_amount = if unsold_shares_amount.is_zero() {
+                BalanceOf::<T>::zero()
+            } else {
+                let protocol_account = Self::account_id(asset_id);
+                let vault_id = Self::asset_id_to_vault_id(asset_id)
+                    .ok_or(Error::<T>::AssetVaultDoesNotExists)?;
 
-            let total_amount_to_exercise = option
-                .exercise_amount
-                .checked_mul(&option_amount)
-                .ok_or(ArithmeticError::Overflow)?;
+                Self::recall_strategy_funds(
+                    asset_id,
+                    &vault_id,
+                    VaultOf::<T>::lp_share_value(&vault_id, unsold_shares_amount)?,
+                )?;
+
+                let collateral_amount =
+                    VaultOf::<T>::withdraw(&vault_id, &protocol_account, unsold_shares_amount)
+                        .map_err(|_| Error::<T>::VaultWithdrawNotAllowed)?;
 
-            // Transfer buyer profit to buyer account if option is ITM
-            if option.exercise_amount != BalanceOf::<T>::zero() {
                 AssetsOf::<T>::transfer(
                     asset_id,
                     &protocol_account,
                     from,
-                    total_amount_to_exercise,
+                    collateral_amount,
                     true,
-                )
-                .map_err(|_| Error::<T>::UserHasNotEnoughOptionTokens)?;
-            }
+                )?;
 
-            // Burn option token from user's account
-            AssetsOf::<T>::burn_from(option_id, from, option_amount)
-                .map_err(|_| Error::<T>::UserHasNotEnoughOptionTokens)?;
+                collateral_amount
+            };
 
-            Self::deposit_event(Event::ExerciseOption {
+            Self::deposit_event(Event::UnsoldCollateralReleased {
                 user: from.clone(),
-                option_amount,
                 option_id,
+                collateral_amount,
             });
 
             Ok(())
         }
 
-        fn do_withdraw_collateral(
+        /// Pays to `beneficiary` the collateral of the seller `from` left after settlement, its
+        /// part of the premium and of the asset delivered by buyers, then deletes its position.
+        /// Returns the collateral paid.
+        pub(crate) fn release_seller_position(
             from: &AccountIdOf<T>,
+            beneficiary: &AccountIdOf<T>,
             option_id: OptionIdOf<T>,
             option: &OptionToken<T>,
             position: &mut Option<SellerPosition<T>>,
-        ) -> Result<(), DispatchError> {
-            // Check if we are in exercise window
-            ensure!(
-                option.status == Status::Exercise,
-                Error::<T>::NotIntoExerciseWindow
-            );
-
+        ) -> Result<BalanceOf<T>, DispatchError> {
             // Check if user has any collateral and retrieve position
             let seller_position = position
                 .as_mut()
                 .ok_or(Error::<T>::UserDoesNotHaveSellerPosition)?;
 
+            // Netted positions are completed by the payoff of the options covering them
+            Self::redeem_spread_pledge(from, option_id, seller_position)?;
+
             // ------ Shares calculations for user ------
             // shares_per_option = total_shares / total_option_bought
             // option_bought_ratio = total_option_bought / total_option_for_sale
             // user_shares_to_subtract = shares_per_option * option_bought_ratio *
             // user_option_amount
+            // where total_shares also includes the shares withdrawn by early exercises
+            let total_shares_for_buyers = option
+                .total_shares_amount
+                .checked_add(&option.total_shares_exercised_early)
+                .ok_or(ArithmeticError::Overflow)?;
+
             let shares_for_buyers = Self::convert_and_multiply_by_rational(
-                option.total_shares_amount,
+                total_shares_for_buyers,
                 seller_position.option_amount,
                 option.total_issuance_seller,
                 Rounding::Down,
@@ -1769,26 +3707,26 @@ pub mod pallet {
 
             let lp_token_issuance =
                 AssetsOf::<T>::balance(VaultOf::<T>::lp_asset_id(&vault_id)?, &protocol_account);
+            let shares_amount = min(user_shares_amount, lp_token_issuance);
 
-            let asset_amount = VaultOf::<T>::withdraw(
+            Self::recall_strategy_funds(
+                asset_id,
                 &vault_id,
-                &protocol_account,
-                min(user_shares_amount, lp_token_issuance),
-            )
-            .map_err(|_| Error::<T>::VaultWithdrawNotAllowed)?;
+                VaultOf::<T>::lp_share_value(&vault_id, shares_amount)?,
+            )?;
 
-            AssetsOf::<T>::transfer(asset_id, &protocol_account, from, asset_amount, true)?;
+            let asset_amount = VaultOf::<T>::withdraw(&vault_id, &protocol_account, shares_amount)
+                .map_err(|_| Error::<T>::VaultWithdrawNotAllowed)?;
+
+            AssetsOf::<T>::transfer(asset_id, &protocol_account, beneficiary, asset_amount, true)?;
 
             // ------ Premium calculations for user ------
             // premium_per_option = total_premium_paid / total_option_bought
             // option_bought_ratio = total_option_bought / total_option_for_sale
             // user_premium = premium_per_option * option_bought_ratio * user_option_amount
-            let user_premium_amount = Self::convert_and_multiply_by_rational(
-                option.total_premium_paid,
-                seller_position.option_amount,
-                option.total_issuance_seller,
-                Rounding::Down,
-            )?;
+            // where the premium paid to buy options back is subtracted from total_premium_paid
+            // and total_option_for_sale includes the options written with margin
+            let user_premium_amount = Self::premium_share(seller_position.option_amount, option)?;
 
             // Get info to transfer premium to seller
             let stablecoin_id = T::StablecoinAssetId::get();
@@ -1798,30 +3736,72 @@ pub mod pallet {
             AssetsOf::<T>::transfer(
                 stablecoin_id,
                 &stablecoin_protocol_account,
-                from,
+                beneficiary,
                 user_premium_amount,
                 true,
             )?;
 
+            // ------ Delivered asset calculations for user ------
+            // user_delivered_amount = total_delivered_amount * user_option_amount /
+            // total_option_for_sale
+            if option.total_delivered_amount != BalanceOf::<T>::zero() {
+                let (_, _, delivery_asset_id, _) = Self::physical_settlement_amounts(option);
+
+                let user_delivered_amount = Self::convert_and_multiply_by_rational(
+                    option.total_delivered_amount,
+                    seller_position.option_amount,
+                    option.total_issuance_seller,
+                    Rounding::Down,
+                )?;
+
+                AssetsOf::<T>::transfer(
+                    delivery_asset_id,
+                    &Self::account_id(delivery_asset_id),
+                    beneficiary,
+                    user_delivered_amount,
+                    true,
+                )?;
+            }
+
+            Self::decrease_open_interest(option, seller_position.option_amount);
+
             // Delete position
             *position = None;
 
-            Self::deposit_event(Event::WithdrawCollateral {
-                user: from.clone(),
-                option_id,
-            });
-
-            Ok(())
+            Ok(asset_amount)
         }
 
         // ----------------------------------------------------------------------------------------------------
         //		Helper Functions
         // ----------------------------------------------------------------------------------------------------
+        /// Runs `f` in a storage transaction, reverting its changes if it fails. Used by the
+        /// hooks, which are not transactional.
+        pub(crate) fn with_rollback<R>(
+            f: impl FnOnce() -> Result<R, DispatchError>,
+        ) -> Result<R, DispatchError> {
+            with_transaction(|| {
+                let result = f();
+                match result {
+                    Ok(_) => TransactionOutcome::Commit(result),
+                    Err(_) => TransactionOutcome::Rollback(result),
+                }
+            })
+        }
+
         /// Protocol account for a particular asset.
         pub(crate) fn account_id(asset_id: AssetIdOf<T>) -> AccountIdOf<T> {
             T::PalletId::get().into_sub_account_truncating(asset_id)
         }
 
+        /// Returns true if `account` is one of the accounts of the pallet, derived by
+        /// [`account_id`](Self::account_id).
+        pub(crate) fn is_protocol_account(account: &AccountIdOf<T>) -> bool {
+            matches!(
+                PalletId::try_from_sub_account::<AssetIdOf<T>>(account),
+                Some((pallet_id, _)) if pallet_id == T::PalletId::get()
+            )
+        }
+
         /// Calculate the hash of an option providing the required attributes.
         pub(crate) fn generate_id(
             base_asset_id: AssetIdOf<T>,
@@ -1831,6 +3811,7 @@ pub mod pallet {
             option_type: OptionType,
             expiring_date: MomentOf<T>,
             exercise_type: ExerciseType,
+            settlement_type: SettlementType,
         ) -> H256 {
             BlakeTwo256::hash_of(&(
                 base_asset_id,
@@ -1840,6 +3821,7 @@ pub mod pallet {
                 option_type,
                 expiring_date,
                 exercise_type,
+                settlement_type,
             ))
         }
 
@@ -1885,6 +3867,30 @@ pub mod pallet {
             Ok(collateral_for_option)
         }
 
+        /// Returns the collateral asset and the collateral locked by sellers for each option,
+        /// followed by the asset and the amount buyers deliver for each option exercised when the
+        /// option is physically settled.
+        pub(crate) fn physical_settlement_amounts(
+            option: &OptionToken<T>,
+        ) -> (AssetIdOf<T>, BalanceOf<T>, AssetIdOf<T>, BalanceOf<T>) {
+            match option.option_type {
+                // Call buyers pay the strike in quote asset and receive the base asset
+                OptionType::Call => (
+                    option.base_asset_id,
+                    option.quote_asset_strike_price,
+                    option.quote_asset_id,
+                    option.base_asset_strike_price,
+                ),
+                // Put buyers deliver the base asset and receive the strike in quote asset
+                OptionType::Put => (
+                    option.quote_asset_id,
+                    option.base_asset_strike_price,
+                    option.base_asset_id,
+                    option.quote_asset_strike_price,
+                ),
+            }
+        }
+
         pub(crate) fn convert_and_multiply_by_rational(
             a: BalanceOf<T>,
             b: BalanceOf<T>,
@@ -1908,7 +3914,180 @@ pub mod pallet {
             Ok(res)
         }
 
-        fn get_price(asset_id: AssetIdOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        /// Computes the premium to pay for buying `option_amount` options, pricing the option with
+        /// [`OptionsPricing`](Config::OptionsPricing) at the current spot price.
+        pub fn calculate_option_premium(
+            option_id: OptionIdOf<T>,
+            option_amount: BalanceOf<T>,
+            option: &OptionToken<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let bs_params = Self::black_scholes_params(option)?;
+
+            OptionsPricingOf::<T>::calculate_option_price(option_id, bs_params)?
+                .checked_mul(&option_amount)
+                .ok_or_else(|| ArithmeticError::Overflow.into())
+        }
+
+        /// Returns the collateral locked by sellers for each option, in the collateral asset of the
+        /// option.
+        pub(crate) fn collateral_per_option(option: &OptionToken<T>) -> BalanceOf<T> {
+            match option.option_type {
+                // For CALL options it should be `base_asset_amount_per_option` *
+                // `quote_asset_strike_price`
+                OptionType::Call => option.quote_asset_strike_price,
+                // For PUT options it should be `quote_asset_amount_per_option` *
+                // `base_asset_strike_price`
+                OptionType::Put => option.base_asset_strike_price,
+            }
+        }
+
+        /// Adds the options written to the open interest of the base asset of `option`.
+        pub(crate) fn increase_open_interest(
+            option: &OptionToken<T>,
+            option_amount: BalanceOf<T>,
+        ) -> Result<(), DispatchError> {
+            OpenInterest::<T>::try_mutate(option.base_asset_id, |open_interest| {
+                *open_interest = open_interest
+                    .checked_add(&option_amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+
+                Ok(())
+            })
+        }
+
+        /// Removes the options of a released position from the open interest of the base asset
+        /// of `option`.
+        pub(crate) fn decrease_open_interest(option: &OptionToken<T>, option_amount: BalanceOf<T>) {
+            OpenInterest::<T>::mutate(option.base_asset_id, |open_interest| {
+                *open_interest = open_interest.saturating_sub(option_amount)
+            });
+        }
+
+        /// Returns the options for sale, written with collateral or with margin.
+        pub(crate) fn total_issuance_written(
+            option: &OptionToken<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            option
+                .total_issuance_seller
+                .checked_add(&option.total_issuance_margined)
+                .ok_or_else(|| ArithmeticError::Overflow.into())
+        }
+
+        /// Returns the part of the premium of the options collected by sellers, net of buybacks,
+        /// owed to a writer of `option_amount` options.
+        pub(crate) fn premium_share(
+            option_amount: BalanceOf<T>,
+            option: &OptionToken<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let total_premium_for_sellers = option
+                .total_premium_paid
+                .checked_sub(&option.total_buyback_paid)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            Self::convert_and_multiply_by_rational(
+                total_premium_for_sellers,
+                option_amount,
+                Self::total_issuance_written(option)?,
+                Rounding::Down,
+            )
+        }
+
+        /// Returns the greeks of a single option at the current spot price, as computed by
+        /// [`OptionsPricing`](Config::OptionsPricing).
+        pub fn get_option_greeks(option_id: OptionIdOf<T>) -> Result<Greeks, DispatchError> {
+            let option =
+                Self::option_id_to_option(option_id).ok_or(Error::<T>::OptionDoesNotExists)?;
+
+            OptionsPricingOf::<T>::get_option_greeks(Self::black_scholes_params(&option)?)
+        }
+
+        /// Returns the net greeks of the vault of `asset_id`, grouped by the base asset of the
+        /// options it collateralizes.
+        ///
+        /// The vault is short every option bought from it, so its exposure is the opposite of the
+        /// greeks of the options bought that are still in their purchase or exercise window.
+        pub fn get_vault_greeks(
+            asset_id: AssetIdOf<T>,
+        ) -> Result<Vec<(AssetIdOf<T>, Greeks)>, DispatchError> {
+            ensure!(
+                AssetToVault::<T>::contains_key(asset_id),
+                Error::<T>::AssetVaultDoesNotExists
+            );
+
+            let mut vault_greeks: BTreeMap<AssetIdOf<T>, Greeks> = BTreeMap::new();
+
+            for (option_id, option) in OptionIdToOption::<T>::iter() {
+                let collateral_asset_id = match option.option_type {
+                    OptionType::Call => option.base_asset_id,
+                    OptionType::Put => option.quote_asset_id,
+                };
+                if collateral_asset_id != asset_id ||
+                    !matches!(option.status, Status::Purchase | Status::Exercise)
+                {
+                    continue
+                }
+
+                // Options written with margin are not backed by the vault
+                let options_bought = Self::convert_and_multiply_by_rational(
+                    AssetsOf::<T>::total_issuance(option_id),
+                    option.total_issuance_seller,
+                    Self::total_issuance_written(&option)?,
+                    Rounding::Down,
+                )?;
+                if options_bought.is_zero() {
+                    continue
+                }
+
+                let option_greeks =
+                    OptionsPricingOf::<T>::get_option_greeks(Self::black_scholes_params(&option)?)?;
+                let greeks = vault_greeks.entry(option.base_asset_id).or_default();
+                *greeks = Self::add_short_position(*greeks, option_greeks, options_bought)?;
+            }
+
+            Ok(vault_greeks.into_iter().collect())
+        }
+
+        /// Subtracts the greeks of `option_amount` options with `option_greeks` from `greeks`.
+        fn add_short_position(
+            greeks: Greeks,
+            option_greeks: Greeks,
+            option_amount: BalanceOf<T>,
+        ) -> Result<Greeks, DispatchError> {
+            let option_amount =
+                FixedI128::checked_from_rational(option_amount, BalanceOf::<T>::one())
+                    .ok_or(ArithmeticError::Overflow)?;
+            let short = |total: FixedI128, greek: FixedI128| {
+                greek
+                    .checked_mul(&option_amount)
+                    .and_then(|position| total.checked_sub(&position))
+                    .ok_or(ArithmeticError::Overflow)
+            };
+
+            Ok(Greeks {
+                delta: short(greeks.delta, option_greeks.delta)?,
+                gamma: short(greeks.gamma, option_greeks.gamma)?,
+                vega: short(greeks.vega, option_greeks.vega)?,
+                theta: short(greeks.theta, option_greeks.theta)?,
+                rho: short(greeks.rho, option_greeks.rho)?,
+            })
+        }
+
+        pub(crate) fn black_scholes_params(
+            option: &OptionToken<T>,
+        ) -> Result<BlackScholesParams<AssetIdOf<T>, BalanceOf<T>, MomentOf<T>>, DispatchError>
+        {
+            Ok(BlackScholesParams {
+                base_asset_id: option.base_asset_id,
+                base_asset_strike_price: option.base_asset_strike_price,
+                base_asset_spot_price: Self::get_price(option.base_asset_id)?,
+                expiring_date: option.expiring_date,
+                option_type: option.option_type,
+                total_issuance_buyer: option.total_issuance_buyer,
+                total_premium_paid: option.total_premium_paid,
+            })
+        }
+
+        pub(crate) fn get_price(asset_id: AssetIdOf<T>) -> Result<BalanceOf<T>, DispatchError> {
             let unit = T::LocalAssets::unit::<BalanceOf<T>>(asset_id)?;
 
             OracleOf::<T>::get_price(asset_id, unit)
@@ -1916,10 +4095,6 @@ pub mod pallet {
                 .map_err(|_| Error::<T>::AssetPriceNotFound.into())
         }
 
-        pub fn fake_option_price() -> BalanceOf<T> {
-            (1000u128 * 10u128.pow(12)).into()
-        }
-
         fn schedule_option(epoch: Epoch<MomentOf<T>>, option_id: OptionIdOf<T>) {
             <Scheduler<T>>::insert(Swapped::from(epoch.deposit), option_id, Status::Deposit);
             <Scheduler<T>>::insert(Swapped::from(epoch.purchase), option_id, Status::Purchase);
@@ -1927,18 +4102,70 @@ pub mod pallet {
             <Scheduler<T>>::insert(Swapped::from(epoch.end), option_id, Status::End);
         }
 
+        /// Moves the options whose phase change is due at `now` to their next phase, in the order
+        /// of the [`Scheduler`]. Processing stops once `budget` would be exceeded and the
+        /// remaining changes stay scheduled, so they are applied first in the next blocks. At
+        /// least one change is applied each block so the scheduler always makes progress. Returns
+        /// the weight used.
+        fn process_scheduler(now: MomentOf<T>, budget: Weight) -> Weight {
+            let mut used_weight = 0;
+            let mut processed_any = false;
+
+            while let Some((moment_swapped, option_id, moment_type)) = <Scheduler<T>>::iter().next()
+            {
+                used_weight = used_weight.saturating_add(T::DbWeight::get().reads(1));
+                let moment = moment_swapped.into_value();
+
+                if now < moment {
+                    break
+                }
+
+                let weight = Self::option_status_change_weight(option_id, moment_type);
+
+                if processed_any && used_weight.saturating_add(weight) > budget {
+                    break
+                }
+
+                <Scheduler<T>>::remove(moment_swapped, &option_id);
+
+                used_weight = used_weight
+                    .saturating_add(T::DbWeight::get().writes(1))
+                    .saturating_add(Self::option_status_change(option_id, moment_type));
+                processed_any = true;
+            }
+
+            used_weight
+        }
+
+        /// Maximum weight of moving an option to `moment_type`, including the rollover of its
+        /// series and the settlement of its margined writers, used to check the scheduler budget
+        /// before applying the change.
+        fn option_status_change_weight(option_id: OptionIdOf<T>, moment_type: Status) -> Weight {
+            match moment_type {
+                Status::NotStarted | Status::SettlementPending => 0,
+                Status::Deposit => <T as Config>::WeightInfo::option_deposit_start(
+                    Self::rollover_sellers_bound(option_id),
+                ),
+                Status::Purchase => <T as Config>::WeightInfo::option_purchase_start(),
+                Status::Exercise =>
+                    <T as Config>::WeightInfo::option_exercise_start(T::MaxMarginWriters::get()),
+                Status::End =>
+                    <T as Config>::WeightInfo::option_end(Self::rollover_sellers_bound(option_id)),
+            }
+        }
+
         fn option_status_change(option_id: OptionIdOf<T>, moment_type: Status) -> Weight {
             OptionIdToOption::<T>::mutate(option_id, |option| match option {
                 Some(option) => match moment_type {
                     // This variant shouldn't happen because we don't schedule it.
-                    Status::NotStarted => 0,
+                    Status::NotStarted | Status::SettlementPending => 0,
                     Status::Deposit => Self::option_deposit_start(option_id, option),
                     Status::Purchase => Self::option_purchase_start(option_id, option),
                     Status::Exercise => Self::option_exercise_start(option_id, option),
                     Status::End => Self::option_end(option_id, option),
                 },
-                // This variant shouldn't happen because we don't delete options now;
-                // and we'll delete them in the future only after they finish.
+                // This variant shouldn't happen because options are only removed by
+                // `clean_up_option` after they end, once all their phase changes are applied.
                 None => 0,
             })
         }
@@ -1946,32 +4173,34 @@ pub mod pallet {
         fn option_deposit_start(option_id: OptionIdOf<T>, option: &mut OptionToken<T>) -> Weight {
             option.status = Status::Deposit;
             Self::deposit_event(Event::OptionDepositStart { option_id });
-            0
+
+            <T as Config>::WeightInfo::option_deposit_start(Self::deposit_rolled_over_collateral(
+                option_id, option,
+            ))
         }
 
         fn option_purchase_start(option_id: OptionIdOf<T>, option: &mut OptionToken<T>) -> Weight {
             option.status = Status::Purchase;
             Self::deposit_event(Event::OptionPurchaseStart { option_id });
-            0
+
+            <T as Config>::WeightInfo::option_purchase_start()
         }
 
         fn option_exercise_start(option_id: OptionIdOf<T>, option: &mut OptionToken<T>) -> Weight {
             // Check if option is expired is redundant if we trust the Scheduler behavior
-
-            // TODO: Handle the result to address overflow errors or other types of errors.
-            // `do_settle_option` should never return an error, but if happens, it should be
-            // handled.
-            Self::do_settle_option(option_id, option).expect("TODO ERROR ON SETTLE OPTION");
-
-            option.status = Status::Exercise;
-            Self::deposit_event(Event::OptionExerciseStart { option_id });
-            0
+            Self::try_settle_option(option_id, option)
         }
 
         fn option_end(option_id: OptionIdOf<T>, option: &mut OptionToken<T>) -> Weight {
+            // The end is scheduled again once the option is settled
+            if option.status == Status::SettlementPending {
+                return <T as Config>::WeightInfo::option_end(0)
+            }
+
             option.status = Status::End;
             Self::deposit_event(Event::OptionEnd { option_id });
-            0
+
+            <T as Config>::WeightInfo::option_end(Self::roll_over_series(option_id, option))
         }
     }
 }
@@ -0,0 +1,340 @@
+use crate::{
+    types::*, validation::ValidateOpenInterest, AccountIdOf, AssetIdOf, AssetsOf, BalanceOf,
+    Config, Error, Event, MarginPositions, OptionIdOf, OptionIdToOption, OptionsPricingOf, Pallet,
+};
+use composable_support::validation::Validated;
+use frame_support::{pallet_prelude::*, traits::fungibles::Transfer};
+use sp_arithmetic::Rounding;
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedMul, CheckedSub, Convert, Saturating, Zero},
+    ArithmeticError, DispatchError, FixedPointNumber,
+};
+use sp_std::{
+    cmp::{max, min},
+    vec::Vec,
+};
+use traits::{options_pricing::OptionsPricing, tokenized_options::*};
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn do_sell_option_with_margin(
+        from: &AccountIdOf<T>,
+        option_amount: BalanceOf<T>,
+        option_id: OptionIdOf<T>,
+        margin: BalanceOf<T>,
+        option: &mut OptionToken<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            option_amount != BalanceOf::<T>::zero(),
+            Error::<T>::CannotPassZeroOptionAmount
+        );
+
+        ensure!(
+            option.status == Status::Deposit,
+            Error::<T>::NotIntoDepositWindow
+        );
+
+        // Margined writers pay buyers at settlement, so the payoff can't be owed before
+        ensure!(
+            option.exercise_type == ExerciseType::European &&
+                option.settlement_type == SettlementType::Cash,
+            Error::<T>::MarginNotSupported
+        );
+
+        Validated::<_, ValidateOpenInterest<T>>::new((option_id, option_amount))
+            .map_err(|_| Error::<T>::OpenInterestLimitExceeded)?;
+
+        MarginPositions::<T>::try_mutate(option_id, from, |position| {
+            // New writers are bounded, since all of them are settled with the option
+            if position.is_none() {
+                ensure!(
+                    MarginPositions::<T>::iter_key_prefix(option_id).count() <
+                        T::MaxMarginWriters::get() as usize,
+                    Error::<T>::TooManyMarginWriters
+                );
+            }
+
+            let position = position.get_or_insert(MarginPosition {
+                option_amount: BalanceOf::<T>::zero(),
+                margin: BalanceOf::<T>::zero(),
+            });
+
+            position.option_amount = position
+                .option_amount
+                .checked_add(&option_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            position.margin = position
+                .margin
+                .checked_add(&margin)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            ensure!(
+                position.margin >= Self::calculate_required_margin(position.option_amount, option)?,
+                Error::<T>::InsufficientMargin
+            );
+
+            Ok::<_, DispatchError>(())
+        })?;
+
+        let asset_id = Self::collateral_asset_id(option);
+        AssetsOf::<T>::transfer(asset_id, from, &Self::account_id(asset_id), margin, true)
+            .map_err(|_| Error::<T>::UserHasNotEnoughFundsToDeposit)?;
+
+        option.total_issuance_margined = option
+            .total_issuance_margined
+            .checked_add(&option_amount)
+            .ok_or(ArithmeticError::Overflow)?;
+        Self::increase_open_interest(option, option_amount)?;
+
+        Self::deposit_event(Event::SellOptionWithMargin {
+            user: from.clone(),
+            option_amount,
+            option_id,
+            margin,
+        });
+
+        Ok(())
+    }
+
+    pub(crate) fn do_add_margin(
+        from: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+        amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        let option =
+            OptionIdToOption::<T>::get(option_id).ok_or(Error::<T>::OptionDoesNotExists)?;
+
+        ensure!(
+            option.status == Status::Deposit || option.status == Status::Purchase,
+            Error::<T>::NotIntoPurchaseWindow
+        );
+
+        MarginPositions::<T>::try_mutate(option_id, from, |position| {
+            let position = position
+                .as_mut()
+                .ok_or(Error::<T>::UserDoesNotHaveMarginPosition)?;
+
+            position.margin = position
+                .margin
+                .checked_add(&amount)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            Ok::<_, DispatchError>(())
+        })?;
+
+        let asset_id = Self::collateral_asset_id(&option);
+        AssetsOf::<T>::transfer(asset_id, from, &Self::account_id(asset_id), amount, true)
+            .map_err(|_| Error::<T>::UserHasNotEnoughFundsToDeposit)?;
+
+        Self::deposit_event(Event::MarginAdded {
+            user: from.clone(),
+            option_id,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub(crate) fn do_liquidate_margin_position(
+        liquidator: &AccountIdOf<T>,
+        writer: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+        option: &mut OptionToken<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            option.status == Status::Deposit || option.status == Status::Purchase,
+            Error::<T>::NotIntoPurchaseWindow
+        );
+
+        let position = MarginPositions::<T>::get(option_id, writer)
+            .ok_or(Error::<T>::UserDoesNotHaveMarginPosition)?;
+
+        ensure!(
+            position.margin < Self::calculate_required_margin(position.option_amount, option)?,
+            Error::<T>::MarginPositionIsHealthy
+        );
+
+        MarginPositions::<T>::remove(option_id, writer);
+
+        option.total_issuance_margined = option
+            .total_issuance_margined
+            .checked_sub(&position.option_amount)
+            .ok_or(ArithmeticError::Underflow)?;
+        Self::decrease_open_interest(option, position.option_amount);
+
+        // The liquidator backs the options with the full collateral, as any other seller
+        Self::deposit_seller_collateral(
+            liquidator,
+            position.option_amount,
+            option_id,
+            Self::collateral_per_option(option),
+            option,
+        )?;
+
+        let asset_id = Self::collateral_asset_id(option);
+        AssetsOf::<T>::transfer(
+            asset_id,
+            &Self::account_id(asset_id),
+            liquidator,
+            position.margin,
+            true,
+        )?;
+
+        Self::deposit_event(Event::MarginPositionLiquidated {
+            user: writer.clone(),
+            liquidator: liquidator.clone(),
+            option_id,
+            option_amount: position.option_amount,
+            margin_seized: position.margin,
+        });
+
+        Ok(())
+    }
+
+    pub(crate) fn do_withdraw_margin(
+        from: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+    ) -> Result<(), DispatchError> {
+        let option =
+            OptionIdToOption::<T>::get(option_id).ok_or(Error::<T>::OptionDoesNotExists)?;
+
+        ensure!(
+            option.status == Status::Exercise || option.status == Status::End,
+            Error::<T>::NotIntoExerciseWindow
+        );
+
+        let position = MarginPositions::<T>::take(option_id, from)
+            .ok_or(Error::<T>::UserDoesNotHaveMarginPosition)?;
+
+        Self::release_margin_position(from, &option, position)?;
+
+        Self::deposit_event(Event::WithdrawMargin {
+            user: from.clone(),
+            option_id,
+        });
+
+        Ok(())
+    }
+
+    /// Pays to `beneficiary` the margin left after settlement of a margin position and its part
+    /// of the premium.
+    pub(crate) fn release_margin_position(
+        beneficiary: &AccountIdOf<T>,
+        option: &OptionToken<T>,
+        position: MarginPosition<BalanceOf<T>>,
+    ) -> Result<(), DispatchError> {
+        let asset_id = Self::collateral_asset_id(option);
+        AssetsOf::<T>::transfer(
+            asset_id,
+            &Self::account_id(asset_id),
+            beneficiary,
+            position.margin,
+            true,
+        )?;
+
+        let stablecoin_id = T::StablecoinAssetId::get();
+        AssetsOf::<T>::transfer(
+            stablecoin_id,
+            &Self::account_id(stablecoin_id),
+            beneficiary,
+            Self::premium_share(position.option_amount, option)?,
+            true,
+        )?;
+
+        Self::decrease_open_interest(option, position.option_amount);
+
+        Ok(())
+    }
+
+    /// Takes from the margin positions of the option their part of the payoff of the options
+    /// bought, which stays in the protocol account for buyers. Returns the options bought from
+    /// margined writers and the payoff their margin could not cover.
+    pub(crate) fn settle_margin_positions(
+        option_id: OptionIdOf<T>,
+        collateral_for_option: BalanceOf<T>,
+        total_issuance_buyer: BalanceOf<T>,
+        option: &OptionToken<T>,
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        if option.total_issuance_margined.is_zero() {
+            return Ok((BalanceOf::<T>::zero(), BalanceOf::<T>::zero()))
+        }
+
+        let total_issuance_written = Self::total_issuance_written(option)?;
+
+        let margined_options_bought = Self::convert_and_multiply_by_rational(
+            total_issuance_buyer,
+            option.total_issuance_margined,
+            total_issuance_written,
+            Rounding::Down,
+        )?;
+
+        let total_payoff = collateral_for_option
+            .checked_mul(&total_issuance_buyer)
+            .ok_or(ArithmeticError::Overflow)?;
+
+        let positions: Vec<_> = MarginPositions::<T>::iter_prefix(option_id).collect();
+        let mut margin_shortfall = BalanceOf::<T>::zero();
+
+        for (writer, mut position) in positions {
+            // Rounded up, so that margined writers always pay for their part
+            let payoff = Self::convert_and_multiply_by_rational(
+                total_payoff,
+                position.option_amount,
+                total_issuance_written,
+                Rounding::Up,
+            )?;
+            let paid = min(payoff, position.margin);
+
+            margin_shortfall = margin_shortfall
+                .checked_add(&payoff.saturating_sub(paid))
+                .ok_or(ArithmeticError::Overflow)?;
+            position.margin = position.margin.saturating_sub(paid);
+
+            MarginPositions::<T>::insert(option_id, writer, position);
+        }
+
+        Ok((margined_options_bought, margin_shortfall))
+    }
+
+    /// Computes the margin required for writing `option_amount` options at the current spot
+    /// price. The margin of each option is the greater between the full collateral scaled by the
+    /// delta of the option and its payoff after a [`MarginStressShock`](Config::MarginStressShock)
+    /// move of the spot price against the writer, capped at the full collateral.
+    pub fn calculate_required_margin(
+        option_amount: BalanceOf<T>,
+        option: &OptionToken<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let full_collateral = Self::collateral_per_option(option);
+
+        let bs_params = Self::black_scholes_params(option)?;
+        let spot_price = bs_params.base_asset_spot_price;
+        let greeks = OptionsPricingOf::<T>::get_option_greeks(bs_params)?;
+
+        let delta_margin = <T::Convert as Convert<u128, BalanceOf<T>>>::convert(
+            greeks
+                .delta
+                .saturating_abs()
+                .saturating_mul_int(<T::Convert as Convert<BalanceOf<T>, u128>>::convert(
+                    full_collateral,
+                )),
+        );
+
+        let shock = T::MarginStressShock::get().mul_ceil(spot_price);
+        let stress_margin = match option.option_type {
+            OptionType::Call =>
+                Self::call_option_collateral_amount(spot_price.saturating_add(shock), option)?,
+            OptionType::Put =>
+                Self::put_option_collateral_amount(spot_price.saturating_sub(shock), option)?,
+        };
+
+        min(max(delta_margin, stress_margin), full_collateral)
+            .checked_mul(&option_amount)
+            .ok_or_else(|| ArithmeticError::Overflow.into())
+    }
+
+    fn collateral_asset_id(option: &OptionToken<T>) -> AssetIdOf<T> {
+        match option.option_type {
+            OptionType::Call => option.base_asset_id,
+            OptionType::Put => option.quote_asset_id,
+        }
+    }
+}
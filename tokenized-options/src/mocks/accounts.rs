@@ -9,6 +9,8 @@ pub const BOB: AccountId = account_id_from_u64(2);
 pub const CHARLIE: AccountId = account_id_from_u64(3);
 pub const DAVE: AccountId = account_id_from_u64(4);
 pub const EVEN: AccountId = account_id_from_u64(5);
+pub const TREASURY: AccountId = account_id_from_u64(6);
+pub const STRATEGY: AccountId = account_id_from_u64(7);
 
 pub const fn account_id_from_u64(n: u64) -> AccountId {
     let bytes_src = n.to_be_bytes();
@@ -0,0 +1,78 @@
+use crate::mocks::{
+    accounts::*,
+    assets::*,
+    runtime::{Assets, Balance},
+};
+use frame_support::{
+    ensure, parameter_types,
+    traits::fungibles::{Inspect, Mutate, Transfer},
+};
+use sp_runtime::{traits::Zero, DispatchError};
+use std::collections::BTreeMap;
+use traits::collateral_strategy::CollateralStrategy;
+
+parameter_types! {
+    // Kept in storage, so that failed calls revert it together with the transfers
+    pub storage Principal: BTreeMap<AssetId, Balance> = BTreeMap::new();
+}
+
+/// Strategy holding the funds deposited into the `STRATEGY` account. Everything in the account
+/// on top of the principal deposited is yield.
+pub struct MockCollateralStrategy;
+
+impl CollateralStrategy for MockCollateralStrategy {
+    type AccountId = AccountId;
+    type AssetId = AssetId;
+    type Balance = Balance;
+
+    fn deposit(asset_id: AssetId, from: &AccountId, amount: Balance) -> Result<(), DispatchError> {
+        <Assets as Transfer<AccountId>>::transfer(asset_id, from, &STRATEGY, amount, false)?;
+
+        let mut principal = Principal::get();
+        *principal.entry(asset_id).or_default() += amount;
+        Principal::set(&principal);
+
+        Ok(())
+    }
+
+    fn withdraw(asset_id: AssetId, to: &AccountId, amount: Balance) -> Result<(), DispatchError> {
+        ensure!(
+            amount <= Self::balance(asset_id),
+            DispatchError::Other("MockCollateralStrategy")
+        );
+
+        <Assets as Transfer<AccountId>>::transfer(asset_id, &STRATEGY, to, amount, false)?;
+
+        let mut principal = Principal::get();
+        *principal.entry(asset_id).or_default() -= amount;
+        Principal::set(&principal);
+
+        Ok(())
+    }
+
+    fn harvest(asset_id: AssetId, to: &AccountId) -> Result<Balance, DispatchError> {
+        let yield_amount = <Assets as Inspect<AccountId>>::balance(asset_id, &STRATEGY)
+            .saturating_sub(Self::balance(asset_id));
+
+        if !yield_amount.is_zero() {
+            <Assets as Transfer<AccountId>>::transfer(
+                asset_id,
+                &STRATEGY,
+                to,
+                yield_amount,
+                false,
+            )?;
+        }
+
+        Ok(yield_amount)
+    }
+
+    fn balance(asset_id: AssetId) -> Balance {
+        Principal::get().get(&asset_id).copied().unwrap_or_default()
+    }
+}
+
+/// Simulates the yield earned by the funds deposited into the strategy.
+pub fn accrue_strategy_yield(asset_id: AssetId, amount: Balance) {
+    <Assets as Mutate<AccountId>>::mint_into(asset_id, &STRATEGY, amount).unwrap();
+}
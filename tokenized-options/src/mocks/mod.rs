@@ -2,4 +2,5 @@
 
 pub mod accounts;
 pub mod assets;
+pub mod collateral_strategy;
 pub mod runtime;
@@ -1,12 +1,13 @@
 use crate as pallet_tokenized_options;
 use crate::{
-    mocks::{accounts::*, assets::*},
+    mocks::{accounts::*, assets::*, collateral_strategy::MockCollateralStrategy},
     tests::run_to_block,
 };
 use composable_traits::{defi::DeFiComposableConfig, oracle::Price};
 use frame_support::{
     ord_parameter_types, parameter_types,
     traits::{EitherOfDiverse, Everything, GenesisBuild, Hooks},
+    weights::Weight,
     PalletId,
 };
 
@@ -17,6 +18,7 @@ use sp_core::{sr25519::Signature, H256};
 use sp_runtime::{
     testing::{Header, TestXt},
     traits::{ConvertInto, Extrinsic as ExtrinsicT, IdentityLookup, Verify},
+    Permill,
 };
 
 pub type BlockNumber = u64;
@@ -255,6 +257,10 @@ pub fn set_oracle_price(asset_id: AssetId, balance: Balance) {
     pallet_oracle::Prices::<MockRuntime>::insert(asset_id, price);
 }
 
+pub fn remove_oracle_price(asset_id: AssetId) {
+    pallet_oracle::Prices::<MockRuntime>::remove(asset_id);
+}
+
 pub fn get_oracle_price(asset_id: AssetId, amount: Balance) -> Balance {
     <Oracle as composable_traits::oracle::Oracle>::get_price(asset_id, amount)
         .expect("Error retrieving price")
@@ -336,6 +342,11 @@ impl pallet_vault::Config for MockRuntime {
 
 parameter_types! {
     pub const OptionsPricingPalletId: PalletId = PalletId(*b"pricing_");
+    pub const MaxSurfacePoints: u32 = 8;
+    pub const MaxPriceSamples: u32 = 16;
+    pub const PriceSamplingPeriod: Moment = 1000;
+    pub const SnapshotRetentionPeriod: Moment = 20_000;
+    pub const MaxSnapshotWeight: Weight = 2_500;
 }
 
 impl pallet_options_pricing::Config for MockRuntime {
@@ -347,6 +358,13 @@ impl pallet_options_pricing::Config for MockRuntime {
     type Time = Timestamp;
     type ProtocolOrigin =
         EitherOfDiverse<EnsureSignedBy<RootAccount, AccountId>, EnsureRoot<AccountId>>;
+    type VolatilityOrigin =
+        EitherOfDiverse<EnsureSignedBy<RootAccount, AccountId>, EnsureRoot<AccountId>>;
+    type MaxSurfacePoints = MaxSurfacePoints;
+    type MaxPriceSamples = MaxPriceSamples;
+    type PriceSamplingPeriod = PriceSamplingPeriod;
+    type SnapshotRetentionPeriod = SnapshotRetentionPeriod;
+    type MaxSnapshotWeight = MaxSnapshotWeight;
     type Assets = Assets;
 }
 
@@ -357,6 +375,18 @@ impl pallet_options_pricing::Config for MockRuntime {
 parameter_types! {
     pub const TokenizedOptionsPalletId: PalletId = PalletId(*b"options_");
     pub const StablecoinAssetId: AssetId = USDC;
+    pub const BuybackSpread: Permill = Permill::from_percent(5);
+    pub const SettlementGracePeriod: Moment = 3000;
+    pub static MaxSchedulerWeight: Weight = 1_000_000;
+    pub const ClaimWindow: Moment = 5000;
+    pub const UnclaimedFundsAccount: AccountId = TREASURY;
+    pub const MaxCleanupBatch: u32 = 10;
+    pub const MaxStrategyLegs: u32 = 4;
+    pub const MarginStressShock: Permill = Permill::from_percent(20);
+    pub const MaxMarginWriters: u32 = 10;
+    pub const FeeAccount: AccountId = TREASURY;
+    pub const ExerciseKeeperReward: Permill = Permill::from_percent(1);
+    pub const MaxRolloverSellers: u32 = 4;
 }
 
 impl pallet_tokenized_options::Config for MockRuntime {
@@ -375,7 +405,20 @@ impl pallet_tokenized_options::Config for MockRuntime {
     type Assets = Assets;
     type VaultId = VaultId;
     type Vault = Vault;
-    // type OptionsPricing = OptionsPricing;
+    type OptionsPricing = OptionsPricing;
+    type BuybackSpread = BuybackSpread;
+    type SettlementGracePeriod = SettlementGracePeriod;
+    type MaxSchedulerWeight = MaxSchedulerWeight;
+    type ClaimWindow = ClaimWindow;
+    type UnclaimedFundsAccount = UnclaimedFundsAccount;
+    type MaxCleanupBatch = MaxCleanupBatch;
+    type MaxStrategyLegs = MaxStrategyLegs;
+    type MarginStressShock = MarginStressShock;
+    type MaxMarginWriters = MaxMarginWriters;
+    type FeeAccount = FeeAccount;
+    type CollateralStrategy = MockCollateralStrategy;
+    type ExerciseKeeperReward = ExerciseKeeperReward;
+    type MaxRolloverSellers = MaxRolloverSellers;
 }
 
 // ----------------------------------------------------------------------------------------------------
@@ -0,0 +1,90 @@
+use crate::{AccountIdOf, BalanceOf, Buyers, Config, OptionIdOf, Pallet};
+use sp_arithmetic::Rounding;
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedSub},
+    ArithmeticError, DispatchError,
+};
+use sp_std::{cmp::min, vec::Vec};
+use traits::tokenized_options::BuyerPosition;
+
+impl<T: Config> Pallet<T> {
+    /// Adds `option_amount` options bought or received for `premium` to the position of `who`.
+    pub(crate) fn add_to_buyer_position(
+        who: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+        option_amount: BalanceOf<T>,
+        premium: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        Buyers::<T>::try_mutate(who, option_id, |position| -> Result<(), DispatchError> {
+            let position = position.get_or_insert_with(Default::default);
+
+            position.option_amount = position
+                .option_amount
+                .checked_add(&option_amount)
+                .ok_or(ArithmeticError::Overflow)?;
+            position.premium_paid = position
+                .premium_paid
+                .checked_add(&premium)
+                .ok_or(ArithmeticError::Overflow)?;
+
+            Ok(())
+        })
+    }
+
+    /// Removes `option_amount` options from the position of `who` together with their share of
+    /// the premium paid, and counts them as exercised if `exercised` is true. Returns the premium
+    /// removed.
+    ///
+    /// Option tokens are plain assets, so they can also be moved without going through the
+    /// pallet. Options that are not in the position have not been tracked and are removed with no
+    /// premium.
+    pub(crate) fn remove_from_buyer_position(
+        who: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+        option_amount: BalanceOf<T>,
+        exercised: bool,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        Buyers::<T>::try_mutate_exists(who, option_id, |maybe_position| {
+            let mut position = maybe_position.take().unwrap_or_default();
+            let tracked_amount = min(option_amount, position.option_amount);
+
+            let premium = Self::convert_and_multiply_by_rational(
+                position.premium_paid,
+                tracked_amount,
+                position.option_amount,
+                Rounding::Down,
+            )?;
+
+            position.option_amount = position
+                .option_amount
+                .checked_sub(&tracked_amount)
+                .ok_or(ArithmeticError::Underflow)?;
+            position.premium_paid = position
+                .premium_paid
+                .checked_sub(&premium)
+                .ok_or(ArithmeticError::Underflow)?;
+
+            if exercised {
+                position.exercised_amount = position
+                    .exercised_amount
+                    .checked_add(&option_amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+            }
+
+            // Positions are kept after all the options are exercised, so that they still show
+            // the exercised amount
+            if position != BuyerPosition::default() {
+                *maybe_position = Some(position);
+            }
+
+            Ok(premium)
+        })
+    }
+
+    /// Returns the positions of `who` in all the options it bought or received.
+    pub fn get_buyer_positions(
+        who: AccountIdOf<T>,
+    ) -> Vec<(OptionIdOf<T>, BuyerPosition<BalanceOf<T>>)> {
+        Buyers::<T>::iter_prefix(who).collect()
+    }
+}
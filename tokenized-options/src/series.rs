@@ -0,0 +1,209 @@
+use crate::{
+    types::*, validation::ValidateSeriesAttributes, AccountIdOf, BalanceOf, Config, Event,
+    OptionIdOf, OptionIdToSeries, OptionSeriesOf, Pallet, PendingRollovers, RolloverSellers,
+    Sellers, Series, SeriesCount,
+};
+use composable_support::validation::Validated;
+use frame_support::{pallet_prelude::*, traits::Time};
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedDiv, Saturating, Zero},
+    ArithmeticError, DispatchError,
+};
+use sp_std::vec::Vec;
+use traits::tokenized_options::*;
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn do_create_option_series(
+        series: Validated<OptionSeriesOf<T>, ValidateSeriesAttributes<T>>,
+    ) -> Result<SeriesId, DispatchError> {
+        let series = series.value();
+
+        let series_id = SeriesCount::<T>::try_mutate(|count| -> Result<_, DispatchError> {
+            let series_id = *count;
+            *count = count.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+            Ok(series_id)
+        })?;
+
+        Self::create_series_option(series_id, &series)?;
+        Series::<T>::insert(series_id, series.clone());
+
+        Self::deposit_event(Event::CreatedOptionSeries { series_id, series });
+
+        Ok(series_id)
+    }
+
+    /// Creates the option of the series whose deposit phase starts at `series.start`, with the
+    /// strike price selected from the current oracle price of the base asset.
+    fn create_series_option(
+        series_id: SeriesId,
+        series: &OptionSeriesOf<T>,
+    ) -> Result<OptionIdOf<T>, DispatchError> {
+        let base_asset_spot_price = Self::get_price(series.base_asset_id)?;
+
+        let base_asset_strike_price = match series.strike_selection {
+            StrikeSelection::AtTheMoney => base_asset_spot_price,
+            StrikeSelection::AboveSpot(offset) => base_asset_spot_price
+                .checked_add(&offset.mul_floor(base_asset_spot_price))
+                .ok_or(ArithmeticError::Overflow)?,
+            StrikeSelection::BelowSpot(offset) =>
+                base_asset_spot_price.saturating_sub(offset.mul_floor(base_asset_spot_price)),
+        };
+
+        let add = |moment: T::Moment, duration: T::Moment| {
+            moment
+                .checked_add(&duration)
+                .ok_or(ArithmeticError::Overflow)
+        };
+        let purchase = add(series.start, series.deposit_duration)?;
+        let exercise = add(purchase, series.purchase_duration)?;
+        let end = add(exercise, series.exercise_duration)?;
+
+        let option_config = OptionConfig {
+            base_asset_id: series.base_asset_id,
+            quote_asset_id: series.quote_asset_id,
+            base_asset_strike_price,
+            quote_asset_strike_price: series.quote_asset_strike_price,
+            option_type: series.option_type,
+            // Options are settled when the exercise phase starts
+            expiring_date: exercise,
+            exercise_type: series.exercise_type,
+            settlement_type: series.settlement_type,
+            epoch: Epoch {
+                deposit: series.start,
+                purchase,
+                exercise,
+                end,
+            },
+            status: Status::NotStarted,
+            base_asset_amount_per_option: series.base_asset_amount_per_option,
+            quote_asset_amount_per_option: series.quote_asset_amount_per_option,
+            total_issuance_seller: BalanceOf::<T>::zero(),
+            total_premium_paid: BalanceOf::<T>::zero(),
+            exercise_amount: BalanceOf::<T>::zero(),
+            base_asset_spot_price: BalanceOf::<T>::zero(),
+            total_issuance_buyer: BalanceOf::<T>::zero(),
+            total_shares_amount: BalanceOf::<T>::zero(),
+        };
+
+        let option_id = <Self as TokenizedOptions>::create_option(option_config)?;
+        OptionIdToSeries::<T>::insert(option_id, series_id);
+
+        Ok(option_id)
+    }
+
+    /// Creates the next option of the series of the option that just ended, if any, and
+    /// withdraws the position of the sellers that opted in the rollover so that their collateral
+    /// is deposited into the next option. If the next option can't be created, the series is
+    /// stopped and the positions are withdrawn anyway. Returns the number of sellers withdrawn,
+    /// which the weight of [`option_end`](crate::WeightInfo::option_end) is linear in.
+    pub(crate) fn roll_over_series(option_id: OptionIdOf<T>, option: &OptionToken<T>) -> u32 {
+        let series_id = match OptionIdToSeries::<T>::get(option_id) {
+            Some(series_id) => series_id,
+            None => return 0,
+        };
+
+        let mut series = match Series::<T>::get(series_id) {
+            Some(series) => series,
+            // The series has been stopped
+            None => return 0,
+        };
+
+        // Catch up with the current time if blocks were not produced for a while
+        series.start = series
+            .start
+            .saturating_add(series.cadence)
+            .max(T::Time::now());
+
+        let next_option_created =
+            match Self::with_rollback(|| Self::create_series_option(series_id, &series)) {
+                Ok(_) => {
+                    Series::<T>::insert(series_id, series);
+                    true
+                },
+                Err(_) => {
+                    Series::<T>::remove(series_id);
+                    Self::deposit_event(Event::OptionSeriesStopped { series_id });
+                    false
+                },
+            };
+
+        // At most `MaxRolloverSellers` sellers roll over in each series
+        let sellers: Vec<AccountIdOf<T>> = RolloverSellers::<T>::iter_key_prefix(series_id)
+            .filter(|seller| Sellers::<T>::contains_key(option_id, seller))
+            .collect();
+
+        sellers.iter().for_each(|seller| {
+            let released = Self::with_rollback(|| {
+                Sellers::<T>::try_mutate(option_id, seller, |position| {
+                    Self::release_seller_position(seller, seller, option_id, option, position)
+                })
+            });
+
+            if released.is_ok() {
+                Self::deposit_event(Event::WithdrawCollateral {
+                    user: seller.clone(),
+                    option_id,
+                });
+            }
+
+            if let (Ok(collateral), true) = (released, next_option_created) {
+                PendingRollovers::<T>::mutate(series_id, seller, |pending| {
+                    *pending = pending.saturating_add(collateral)
+                });
+            }
+        });
+
+        sellers.len() as u32
+    }
+
+    /// Sells the option of a series whose deposit phase just started on behalf of the sellers
+    /// rolling over their collateral, for as many options as their collateral covers. Sellers
+    /// that don't have that collateral anymore are skipped. Returns the number of sellers
+    /// processed, which the weight of
+    /// [`option_deposit_start`](crate::WeightInfo::option_deposit_start) is linear in.
+    pub(crate) fn deposit_rolled_over_collateral(
+        option_id: OptionIdOf<T>,
+        option: &mut OptionToken<T>,
+    ) -> u32 {
+        let series_id = match OptionIdToSeries::<T>::get(option_id) {
+            Some(series_id) => series_id,
+            None => return 0,
+        };
+
+        let collateral_per_option = Self::collateral_per_option(option);
+
+        // Only sellers in `RolloverSellers` have pending collateral, so there are at most
+        // `MaxRolloverSellers` of them
+        let pending: Vec<(AccountIdOf<T>, BalanceOf<T>)> =
+            PendingRollovers::<T>::drain_prefix(series_id).collect();
+
+        pending.iter().for_each(|(seller, collateral)| {
+            let option_amount = match collateral.checked_div(&collateral_per_option) {
+                Some(option_amount) if !option_amount.is_zero() => option_amount,
+                _ => return,
+            };
+
+            // The option is only updated if the sale succeeds
+            let mut updated_option = option.clone();
+            if Self::with_rollback(|| {
+                Self::do_sell_option(seller, option_amount, option_id, &mut updated_option)
+            })
+            .is_ok()
+            {
+                *option = updated_option;
+            }
+        });
+
+        pending.len() as u32
+    }
+
+    /// Maximum number of sellers rolling over in the series of the given option, used to check
+    /// the scheduler budget before its deposit phase starts or it ends.
+    pub(crate) fn rollover_sellers_bound(option_id: OptionIdOf<T>) -> u32 {
+        if OptionIdToSeries::<T>::contains_key(option_id) {
+            T::MaxRolloverSellers::get()
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    types::*, BalanceOf, Config, Error, Event, MomentOf, OptionIdOf, OptionIdToOption, Pallet,
+    PendingSettlements, PendingSettlementsCursor, Scheduler, WeightInfo,
+};
+use frame_support::{pallet_prelude::*, traits::Time};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    DispatchError, SaturatedConversion,
+};
+use sp_std::vec::Vec;
+use traits::{swap_bytes::Swapped, tokenized_options::*};
+
+impl<T: Config> Pallet<T> {
+    /// Settles the option when its exercise phase starts. If settlement fails, for example because
+    /// the oracle price is not available, the option is marked as pending settlement and retried
+    /// in the next blocks. Returns the weight used.
+    pub(crate) fn try_settle_option(
+        option_id: OptionIdOf<T>,
+        option: &mut OptionToken<T>,
+    ) -> Weight {
+        // The option is only updated if settlement succeeds
+        let mut settled_option = option.clone();
+
+        match Self::with_rollback(|| Self::do_settle_option(option_id, &mut settled_option)) {
+            Ok(()) => {
+                *option = settled_option;
+                Self::complete_settlement(option_id, option);
+            },
+            Err(error) => {
+                option.status = Status::SettlementPending;
+                PendingSettlements::<T>::insert(option_id, T::Time::now());
+                Self::deposit_event(Event::SettlementFailed { option_id, error });
+            },
+        }
+
+        <T as Config>::WeightInfo::option_exercise_start(Self::margin_writers_bound(option))
+    }
+
+    /// Maximum number of margined writers settled with the option, which the weight of the
+    /// settlement is linear in.
+    fn margin_writers_bound(option: &OptionToken<T>) -> u32 {
+        if option.total_issuance_margined.is_zero() {
+            0
+        } else {
+            T::MaxMarginWriters::get()
+        }
+    }
+
+    /// Retries the settlement of the options whose settlement failed in previous blocks, as long
+    /// as `budget` allows. The entries of [`PendingSettlements`] are visited from where the
+    /// previous block stopped, so that options that don't fit are retried in the next blocks.
+    /// Returns the weight used.
+    pub(crate) fn retry_pending_settlements(now: MomentOf<T>, budget: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let mut used_weight = db_weight.reads_writes(1, 1);
+        let retry_weight =
+            <T as Config>::WeightInfo::retry_pending_settlement(T::MaxMarginWriters::get());
+
+        // Each entry visited is read, and retried if it failed in a previous block
+        let max_visits: usize = (budget.saturating_sub(used_weight) /
+            retry_weight.saturating_add(db_weight.reads(1)).max(1))
+        .saturated_into();
+
+        if max_visits.is_zero() {
+            return 0
+        }
+
+        let mut visited: Vec<(OptionIdOf<T>, MomentOf<T>)> =
+            match PendingSettlementsCursor::<T>::get() {
+                Some(cursor) => PendingSettlements::<T>::iter_from(
+                    PendingSettlements::<T>::hashed_key_for(cursor),
+                )
+                .take(max_visits)
+                .collect(),
+                None => Vec::new(),
+            };
+
+        // Wraps around to the start of the map, up to the entries already visited
+        if visited.len() < max_visits {
+            let first_visited = visited.first().map(|(option_id, _)| *option_id);
+            let from_start: Vec<(OptionIdOf<T>, MomentOf<T>)> = PendingSettlements::<T>::iter()
+                .take(max_visits - visited.len())
+                .take_while(|(option_id, _)| Some(*option_id) != first_visited)
+                .collect();
+
+            visited.extend(from_start);
+        }
+
+        // The next block resumes after the last entry visited if the budget ran out
+        match visited.last() {
+            Some((option_id, _)) if visited.len() == max_visits =>
+                PendingSettlementsCursor::<T>::put(option_id),
+            _ => PendingSettlementsCursor::<T>::kill(),
+        }
+
+        visited.into_iter().for_each(|(option_id, failed_at)| {
+            used_weight = used_weight.saturating_add(db_weight.reads(1));
+
+            if failed_at >= now {
+                return
+            }
+
+            OptionIdToOption::<T>::mutate(option_id, |option| {
+                if let Some(option) = option {
+                    let mut settled_option = option.clone();
+
+                    match Self::with_rollback(|| {
+                        Self::do_settle_option(option_id, &mut settled_option)
+                    }) {
+                        Ok(()) => {
+                            *option = settled_option;
+                            Self::complete_settlement(option_id, option);
+                        },
+                        Err(error) =>
+                            Self::deposit_event(Event::SettlementFailed { option_id, error }),
+                    }
+                }
+            });
+
+            used_weight = used_weight.saturating_add(retry_weight);
+        });
+
+        used_weight
+    }
+
+    /// Settles an option pending settlement at `base_asset_spot_price`, once the oracle has been
+    /// unavailable for longer than the [`SettlementGracePeriod`](Config::SettlementGracePeriod).
+    pub(crate) fn do_settle_option_with_price(
+        option_id: OptionIdOf<T>,
+        base_asset_spot_price: BalanceOf<T>,
+        option: &mut OptionToken<T>,
+    ) -> Result<(), DispatchError> {
+        ensure!(
+            option.status == Status::SettlementPending,
+            Error::<T>::OptionNotPendingSettlement
+        );
+
+        let failed_at = PendingSettlements::<T>::get(option_id)
+            .ok_or(Error::<T>::OptionNotPendingSettlement)?;
+
+        ensure!(
+            T::Time::now() >= failed_at.saturating_add(T::SettlementGracePeriod::get()),
+            Error::<T>::SettlementGracePeriodNotOver
+        );
+
+        Self::do_settle_option_at_price(option_id, base_asset_spot_price, option)?;
+        Self::complete_settlement(option_id, option);
+
+        Ok(())
+    }
+
+    /// Opens the exercise phase of a settled option. If settlement happens after the option
+    /// should have ended, the end is moved so that buyers get the whole exercise phase.
+    fn complete_settlement(option_id: OptionIdOf<T>, option: &mut OptionToken<T>) {
+        PendingSettlements::<T>::remove(option_id);
+
+        let now = T::Time::now();
+        if now >= option.epoch.end {
+            let exercise_duration = option.epoch.end.saturating_sub(option.epoch.exercise);
+            option.epoch.end = now.saturating_add(exercise_duration);
+            Scheduler::<T>::insert(Swapped::from(option.epoch.end), option_id, Status::End);
+        }
+
+        option.status = Status::Exercise;
+        Self::deposit_event(Event::OptionExerciseStart { option_id });
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{
+    types::*, validation::ValidateStrategyLegs, AccountIdOf, AssetsOf, BalanceOf, Config, Error,
+    Event, OptionIdOf, OptionIdToOption, Pallet, SpreadPledges, StrategyLegOf, StrategyLegsOf,
+    VaultOf,
+};
+use composable_support::validation::Validated;
+use composable_traits::{currency::LocalAssets, vault::Vault};
+use frame_support::{
+    pallet_prelude::*,
+    traits::fungibles::{Mutate, Transfer},
+};
+use sp_arithmetic::Rounding;
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedSub, Saturating, Zero},
+    ArithmeticError, DispatchError,
+};
+use sp_std::{cmp::min, vec::Vec};
+use traits::tokenized_options::*;
+
+impl<T: Config> Pallet<T> {
+    pub(crate) fn do_execute_strategy(
+        from: &AccountIdOf<T>,
+        legs: Validated<StrategyLegsOf<T>, ValidateStrategyLegs<T>>,
+        max_premium: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        let legs = legs.value();
+
+        // Premiums are computed before executing the legs, which trade different options. The
+        // premium of the sold legs is not received now, but it offsets the one paid in the net
+        // premium bound by `max_premium`
+        let mut premium_paid = BalanceOf::<T>::zero();
+        let mut premium_sold = BalanceOf::<T>::zero();
+        let mut bought_legs = Vec::new();
+
+        for leg in legs.iter() {
+            let premium = Self::calculate_leg_premium(leg)?;
+
+            match leg.side {
+                LegSide::Buy => {
+                    premium_paid = premium_paid
+                        .checked_add(&premium)
+                        .ok_or(ArithmeticError::Overflow)?;
+                    bought_legs.push((leg, premium));
+                },
+                LegSide::Sell =>
+                    premium_sold = premium_sold
+                        .checked_add(&premium)
+                        .ok_or(ArithmeticError::Overflow)?,
+            }
+        }
+
+        ensure!(
+            premium_paid.saturating_sub(premium_sold) <= max_premium,
+            Error::<T>::PremiumExceedsMaxPremium
+        );
+
+        // Options bought by the strategy and the amount still available to cover sold legs
+        let mut protections: Vec<(OptionIdOf<T>, OptionToken<T>, BalanceOf<T>)> = Vec::new();
+
+        for (leg, premium) in bought_legs {
+            let protection = OptionIdToOption::<T>::try_mutate(leg.option_id, |option| {
+                let option = option.as_mut().ok_or(Error::<T>::OptionDoesNotExists)?;
+                Self::do_buy_option(from, leg.option_amount, leg.option_id, premium, option)?;
+                Ok::<_, DispatchError>(option.clone())
+            })?;
+
+            protections.push((leg.option_id, protection, leg.option_amount));
+        }
+
+        for leg in legs.iter().filter(|leg| leg.side == LegSide::Sell) {
+            OptionIdToOption::<T>::try_mutate(leg.option_id, |option| {
+                let option = option.as_mut().ok_or(Error::<T>::OptionDoesNotExists)?;
+
+                let covering = protections.iter_mut().find(|(_, protection, available)| {
+                    *available >= leg.option_amount && Self::is_covered_by(option, protection)
+                });
+
+                match covering {
+                    Some((protection_option_id, protection, available)) => {
+                        // Options sharing an epoch are bought while they can't be sold anymore,
+                        // so covered legs can also be sold while the protection is bought
+                        ensure!(
+                            option.status == Status::Deposit || option.status == Status::Purchase,
+                            Error::<T>::NotIntoDepositWindow
+                        );
+
+                        let collateral_per_option =
+                            Self::spread_collateral_per_option(option, protection)?;
+
+                        Self::do_sell_option_with_collateral(
+                            from,
+                            leg.option_amount,
+                            leg.option_id,
+                            collateral_per_option,
+                            option,
+                        )?;
+                        Self::pledge_protection(
+                            from,
+                            leg.option_id,
+                            *protection_option_id,
+                            leg.option_amount,
+                        )?;
+
+                        *available = available
+                            .checked_sub(&leg.option_amount)
+                            .ok_or(ArithmeticError::Underflow)?;
+                    },
+                    None => Self::do_sell_option(from, leg.option_amount, leg.option_id, option)?,
+                }
+
+                Ok::<_, DispatchError>(())
+            })?;
+        }
+
+        Self::deposit_event(Event::StrategyExecuted {
+            user: from.clone(),
+            legs,
+            premium_paid,
+        });
+
+        Ok(())
+    }
+
+    /// Computes through [`OptionsPricing`](Config::OptionsPricing) the premium of the bought legs
+    /// and of the sold legs of a strategy. The net premium of the strategy is their difference:
+    /// the premium of the bought legs is paid when the strategy is executed, while the premium of
+    /// the sold legs is received by withdrawing the collateral, for the options bought by others.
+    pub fn calculate_strategy_premium(
+        legs: &[StrategyLegOf<T>],
+    ) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+        legs.iter().try_fold(
+            (BalanceOf::<T>::zero(), BalanceOf::<T>::zero()),
+            |(bought, sold), leg| {
+                let premium = Self::calculate_leg_premium(leg)?;
+
+                match leg.side {
+                    LegSide::Buy => Ok((
+                        bought
+                            .checked_add(&premium)
+                            .ok_or(ArithmeticError::Overflow)?,
+                        sold,
+                    )),
+                    LegSide::Sell => Ok((
+                        bought,
+                        sold.checked_add(&premium)
+                            .ok_or(ArithmeticError::Overflow)?,
+                    )),
+                }
+            },
+        )
+    }
+
+    fn calculate_leg_premium(leg: &StrategyLegOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let option =
+            OptionIdToOption::<T>::get(leg.option_id).ok_or(Error::<T>::OptionDoesNotExists)?;
+
+        Self::calculate_option_premium(leg.option_id, leg.option_amount, &option)
+    }
+
+    /// Returns true if selling `option` and holding `protection` is a spread with a defined
+    /// maximum loss. Options exercised early or physically settled can't be covered, because
+    /// the payoff of the protection is only known at settlement.
+    fn is_covered_by(option: &OptionToken<T>, protection: &OptionToken<T>) -> bool {
+        let further_out_of_the_money = match option.option_type {
+            OptionType::Call => protection.base_asset_strike_price > option.base_asset_strike_price,
+            OptionType::Put => protection.base_asset_strike_price < option.base_asset_strike_price,
+        };
+
+        further_out_of_the_money &&
+            option.base_asset_id == protection.base_asset_id &&
+            option.quote_asset_id == protection.quote_asset_id &&
+            option.option_type == protection.option_type &&
+            option.quote_asset_strike_price == protection.quote_asset_strike_price &&
+            option.exercise_type == ExerciseType::European &&
+            option.settlement_type == SettlementType::Cash &&
+            protection.settlement_type == SettlementType::Cash
+    }
+
+    /// Computes the collateral to lock for each option of a spread, which is its maximum loss:
+    /// the difference between the payoffs of the two options when the spot price reaches the
+    /// strike of the protection.
+    fn spread_collateral_per_option(
+        option: &OptionToken<T>,
+        protection: &OptionToken<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        match option.option_type {
+            OptionType::Call => {
+                let diff = protection
+                    .base_asset_strike_price
+                    .checked_sub(&option.base_asset_strike_price)
+                    .ok_or(ArithmeticError::Underflow)?;
+
+                let unit = T::LocalAssets::unit::<BalanceOf<T>>(option.base_asset_id)?;
+
+                let max_loss = Self::convert_and_multiply_by_rational(
+                    diff,
+                    unit,
+                    protection.base_asset_strike_price,
+                    Rounding::Up,
+                )?;
+
+                Ok(min(max_loss, option.quote_asset_strike_price))
+            },
+            OptionType::Put => option
+                .base_asset_strike_price
+                .checked_sub(&protection.base_asset_strike_price)
+                .ok_or_else(|| ArithmeticError::Underflow.into()),
+        }
+    }
+
+    /// Moves the options covering a netted sale to the protocol, which holds them until the
+    /// seller's position is released.
+    fn pledge_protection(
+        from: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+        protection_option_id: OptionIdOf<T>,
+        option_amount: BalanceOf<T>,
+    ) -> Result<(), DispatchError> {
+        SpreadPledges::<T>::try_mutate(option_id, from, |pledge| -> Result<(), DispatchError> {
+            match pledge {
+                // A position can only be covered by a single option
+                Some(pledge) => {
+                    ensure!(
+                        pledge.protection_option_id == protection_option_id,
+                        Error::<T>::StrategyLegsAreInvalid
+                    );
+
+                    pledge.option_amount = pledge
+                        .option_amount
+                        .checked_add(&option_amount)
+                        .ok_or(ArithmeticError::Overflow)?;
+                },
+                None =>
+                    *pledge = Some(SpreadPledge {
+                        protection_option_id,
+                        option_amount,
+                    }),
+            }
+
+            Ok(())
+        })?;
+
+        AssetsOf::<T>::transfer(
+            protection_option_id,
+            from,
+            &Self::account_id(protection_option_id),
+            option_amount,
+            true,
+        )?;
+        Self::remove_from_buyer_position(from, protection_option_id, option_amount, false)?;
+
+        Ok(())
+    }
+
+    /// Exercises the options covering the netted position of `from`, if any, and deposits their
+    /// payoff into the vault on behalf of the position, so that it covers the buyers of the
+    /// option. The covering options must have been settled.
+    pub(crate) fn redeem_spread_pledge(
+        from: &AccountIdOf<T>,
+        option_id: OptionIdOf<T>,
+        seller_position: &mut SellerPosition<T>,
+    ) -> Result<(), DispatchError> {
+        let pledge = match SpreadPledges::<T>::take(option_id, from) {
+            Some(pledge) => pledge,
+            None => return Ok(()),
+        };
+
+        let protection = OptionIdToOption::<T>::get(pledge.protection_option_id)
+            .ok_or(Error::<T>::OptionDoesNotExists)?;
+
+        ensure!(
+            protection.status == Status::Exercise || protection.status == Status::End,
+            Error::<T>::NotIntoExerciseWindow
+        );
+
+        AssetsOf::<T>::burn_from(
+            pledge.protection_option_id,
+            &Self::account_id(pledge.protection_option_id),
+            pledge.option_amount,
+        )?;
+
+        // The payoff is already in the protocol account since the protection was settled
+        let (asset_id, payoff) = Self::settled_exercise_amount(pledge.option_amount, &protection)?;
+
+        if payoff == BalanceOf::<T>::zero() {
+            return Ok(())
+        }
+
+        let vault_id =
+            Self::asset_id_to_vault_id(asset_id).ok_or(Error::<T>::AssetVaultDoesNotExists)?;
+        let shares_amount = VaultOf::<T>::calculate_lp_tokens_to_mint(&vault_id, payoff)?;
+
+        VaultOf::<T>::deposit(&vault_id, &Self::account_id(asset_id), payoff)
+            .map_err(|_| Error::<T>::VaultDepositNotAllowed)?;
+
+        seller_position.shares_amount = seller_position
+            .shares_amount
+            .checked_add(&shares_amount)
+            .ok_or(ArithmeticError::Overflow)?;
+
+        Ok(())
+    }
+}
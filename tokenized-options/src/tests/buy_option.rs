@@ -9,7 +9,10 @@ use crate::{
     tests::{sell_option::sell_option_success_checks, *},
 };
 
-use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::fungibles::{Inspect, Mutate},
+};
 
 use sp_core::sr25519::Public;
 use sp_runtime::ArithmeticError;
@@ -22,7 +25,8 @@ pub fn buy_option_success_checks(option_id: AssetId, option_amount: Balance, who
     let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
 
     let asset_id = USDC;
-    let option_premium = TokenizedOptions::fake_option_price() * option_amount;
+    let option_premium =
+        TokenizedOptions::calculate_option_premium(option_id, option_amount, &option).unwrap();
 
     // ---------------------------
     // |  Data before extrinsic  |
@@ -38,13 +42,15 @@ pub fn buy_option_success_checks(option_id: AssetId, option_amount: Balance, who
     assert_ok!(TokenizedOptions::buy_option(
         Origin::signed(who),
         option_amount,
-        option_id
+        option_id,
+        option_premium
     ));
 
     System::assert_last_event(Event::TokenizedOptions(pallet::Event::BuyOption {
         user: who,
         option_amount,
         option_id,
+        option_premium,
     }));
 
     // ---------------------------
@@ -126,6 +132,7 @@ fn test_buy_option_with_initialization_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -174,6 +181,7 @@ fn test_buy_option_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -214,6 +222,7 @@ fn test_buy_option_multiple_times() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -261,6 +270,7 @@ fn test_buy_option_multiple_users() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -300,7 +310,12 @@ fn test_buy_option_error_option_not_exists() {
         .execute_with(|| {
             assert_noop!(
                 // 10000000000005u128 it's a meaningless number
-                TokenizedOptions::buy_option(Origin::signed(BOB), 1u128, 10000000000005u128),
+                TokenizedOptions::buy_option(
+                    Origin::signed(BOB),
+                    1u128,
+                    10000000000005u128,
+                    Balance::MAX
+                ),
                 Error::<MockRuntime>::OptionDoesNotExists
             );
         });
@@ -330,6 +345,7 @@ fn test_buy_option_error_option_not_exists() {
 //                 option_config.option_type,
 //                 option_config.expiring_date,
 //                 option_config.exercise_type,
+//                 option_config.settlement_type,
 //             );
 
 //             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -387,6 +403,7 @@ fn test_buy_option_error_user_has_not_enough_funds() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -400,15 +417,87 @@ fn test_buy_option_error_user_has_not_enough_funds() {
             run_to_block(3);
 
             let option_id = OptionHashToOptionId::<MockRuntime>::get(option_hash).unwrap();
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+
+            // Leave Alice with enough funds to pay the premium of 3 options, but not of 4
+            let premium = |amount| {
+                TokenizedOptions::calculate_option_premium(option_id, amount, &option).unwrap()
+            };
+            let alice_balance = premium(3u128) + premium(1u128) / 2;
+            assert_ok!(<Assets as Mutate<Public>>::burn_from(
+                USDC,
+                &ALICE,
+                Assets::balance(USDC, &ALICE) - alice_balance
+            ));
 
-            let alice_option_amount = 4u128; // Each option costs 1000 USDC, Alice has 3000
+            let alice_option_amount = 4u128;
 
             assert_noop!(
-                TokenizedOptions::buy_option(Origin::signed(ALICE), alice_option_amount, option_id),
+                TokenizedOptions::buy_option(
+                    Origin::signed(ALICE),
+                    alice_option_amount,
+                    option_id,
+                    Balance::MAX
+                ),
                 Error::<MockRuntime>::UserHasNotEnoughFundsToDeposit
             );
 
-            let alice_option_amount = 3u128; // Each option costs 1000 USDC, Alice has 3000
+            let alice_option_amount = 3u128;
+
+            // Counter test
+            buy_option_success_checks(option_id, alice_option_amount, ALICE);
+        });
+}
+
+#[test]
+fn test_buy_option_error_premium_exceeds_max_premium() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, BTC, 3 * UNIT),
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+            (BOB, USDC, 250000 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .initialize_all_options()
+        .execute_with(|| {
+            let option_config = OptionsConfigBuilder::default().build();
+
+            let option_hash = TokenizedOptions::generate_id(
+                option_config.base_asset_id,
+                option_config.quote_asset_id,
+                option_config.base_asset_strike_price,
+                option_config.quote_asset_strike_price,
+                option_config.option_type,
+                option_config.expiring_date,
+                option_config.exercise_type,
+                option_config.settlement_type,
+            );
+
+            let option_id = OptionHashToOptionId::<MockRuntime>::get(option_hash).unwrap();
+
+            let bob_option_amount = 5u128;
+            sell_option_success_checks(option_id, bob_option_amount, BOB);
+
+            run_to_block(3);
+
+            let alice_option_amount = 3u128;
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            let option_premium =
+                TokenizedOptions::calculate_option_premium(option_id, alice_option_amount, &option)
+                    .unwrap();
+
+            assert_noop!(
+                TokenizedOptions::buy_option(
+                    Origin::signed(ALICE),
+                    alice_option_amount,
+                    option_id,
+                    option_premium - 1
+                ),
+                Error::<MockRuntime>::PremiumExceedsMaxPremium
+            );
 
             // Counter test
             buy_option_success_checks(option_id, alice_option_amount, ALICE);
@@ -439,6 +528,7 @@ fn test_buy_option_error_cannot_buy_zero_options() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -454,7 +544,7 @@ fn test_buy_option_error_cannot_buy_zero_options() {
             let option_id = OptionHashToOptionId::<MockRuntime>::get(option_hash).unwrap();
 
             assert_noop!(
-                TokenizedOptions::buy_option(Origin::signed(ALICE), 0u128, option_id),
+                TokenizedOptions::buy_option(Origin::signed(ALICE), 0u128, option_id, Balance::MAX),
                 Error::<MockRuntime>::CannotPassZeroOptionAmount
             );
         });
@@ -484,6 +574,7 @@ fn test_buy_option_error_overflow_asset_amount() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -499,14 +590,17 @@ fn test_buy_option_error_overflow_asset_amount() {
             let option_id = OptionHashToOptionId::<MockRuntime>::get(option_hash).unwrap();
 
             // Balance: u128 contains until ~4 * 10^38. Considering 12 decimals,
-            // the asset_amount to transfer should overflow with option amount > 3 * 10^26.
-            // The fake option cost right now is fixed at 1000 USDC, so
-            // option amount should be > 3 * 10^23 to cause overflow.
-            // It works until 3 * 10^23.
-            let alice_option_amount = 4 * 10u128.pow(23);
+            // the premium of this at the money option is a few USDC, i.e. more than
+            // 10^12, so the premium overflows with option amount > 4 * 10^26.
+            let alice_option_amount = 10u128.pow(27);
 
             assert_noop!(
-                TokenizedOptions::buy_option(Origin::signed(ALICE), alice_option_amount, option_id),
+                TokenizedOptions::buy_option(
+                    Origin::signed(ALICE),
+                    alice_option_amount,
+                    option_id,
+                    Balance::MAX
+                ),
                 ArithmeticError::Overflow
             );
         });
@@ -536,6 +630,7 @@ fn test_buy_option_error_not_enough_options_for_sale() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -555,7 +650,12 @@ fn test_buy_option_error_not_enough_options_for_sale() {
             let option_id = OptionHashToOptionId::<MockRuntime>::get(option_hash).unwrap();
 
             assert_noop!(
-                TokenizedOptions::buy_option(Origin::signed(ALICE), alice_option_amount, option_id),
+                TokenizedOptions::buy_option(
+                    Origin::signed(ALICE),
+                    alice_option_amount,
+                    option_id,
+                    Balance::MAX
+                ),
                 Error::<MockRuntime>::NotEnoughOptionsForSale
             );
         });
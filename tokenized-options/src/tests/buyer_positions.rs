@@ -0,0 +1,191 @@
+use crate::mocks::runtime::{
+    Assets, Balance, Event, ExtBuilder, MockRuntime, Origin, System, TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, Buyers},
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+
+// ----------------------------------------------------------------------------------------------------
+//		Buyer Positions Tests
+// ----------------------------------------------------------------------------------------------------
+// Bob sells 5 BTC call options and Alice buys 3 of them
+fn sell_and_buy_option() -> AssetId {
+    let option_id = create_option(OptionsConfigBuilder::default().build());
+
+    sell_option_success_checks(option_id, 5u128, BOB);
+    run_to_block(3);
+    buy_option_success_checks(option_id, 3u128, ALICE);
+
+    option_id
+}
+
+fn buyer_position(who: AccountId, option_id: AssetId) -> BuyerPosition<Balance> {
+    Buyers::<MockRuntime>::get(who, option_id).unwrap_or_default()
+}
+
+#[test]
+fn test_buy_option_updates_buyer_position() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+            let first_premium = OptionIdToOption::<MockRuntime>::get(option_id)
+                .unwrap()
+                .total_premium_paid;
+
+            assert_eq!(
+                buyer_position(ALICE, option_id),
+                BuyerPosition {
+                    option_amount: 3u128,
+                    premium_paid: first_premium,
+                    exercised_amount: 0u128,
+                }
+            );
+
+            buy_option_success_checks(option_id, 1u128, ALICE);
+            let total_premium = OptionIdToOption::<MockRuntime>::get(option_id)
+                .unwrap()
+                .total_premium_paid;
+
+            let position = buyer_position(ALICE, option_id);
+            assert_eq!(position.option_amount, 4u128);
+            assert_eq!(position.premium_paid, total_premium);
+
+            assert_eq!(
+                TokenizedOptions::get_buyer_positions(ALICE),
+                vec![(option_id, position)]
+            );
+        });
+}
+
+#[test]
+fn test_transfer_option_moves_buyer_position() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+            let premium_paid = buyer_position(ALICE, option_id).premium_paid;
+
+            assert_ok!(TokenizedOptions::transfer_option(
+                Origin::signed(ALICE),
+                CHARLIE,
+                1u128,
+                option_id
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(pallet::Event::TransferOption {
+                from: ALICE,
+                to: CHARLIE,
+                option_amount: 1u128,
+                option_id,
+            }));
+
+            assert_eq!(Assets::balance(option_id, &ALICE), 2u128);
+            assert_eq!(Assets::balance(option_id, &CHARLIE), 1u128);
+
+            // The premium of the transferred option moves with it
+            let alice_position = buyer_position(ALICE, option_id);
+            let charlie_position = buyer_position(CHARLIE, option_id);
+
+            assert_eq!(alice_position.option_amount, 2u128);
+            assert_eq!(charlie_position.option_amount, 1u128);
+            assert_eq!(charlie_position.premium_paid, premium_paid / 3);
+            assert_eq!(
+                alice_position.premium_paid + charlie_position.premium_paid,
+                premium_paid
+            );
+        });
+}
+
+#[test]
+fn test_exercise_option_updates_buyer_position() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+            let premium_paid = buyer_position(ALICE, option_id).premium_paid;
+
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                3u128,
+                option_id
+            ));
+
+            // The position is kept to show the exercised options
+            assert_eq!(
+                buyer_position(ALICE, option_id),
+                BuyerPosition {
+                    option_amount: 0u128,
+                    premium_paid: 0u128,
+                    exercised_amount: 3u128,
+                }
+            );
+            assert!(premium_paid > 0u128);
+        });
+}
+
+#[test]
+fn test_transfer_option_error_user_has_not_enough_option_tokens() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+
+            assert_noop!(
+                TokenizedOptions::transfer_option(Origin::signed(ALICE), CHARLIE, 4u128, option_id),
+                Error::<MockRuntime>::UserHasNotEnoughOptionTokens
+            );
+        });
+}
+
+#[test]
+fn test_transfer_option_error_zero_option_amount() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+
+            assert_noop!(
+                TokenizedOptions::transfer_option(Origin::signed(ALICE), CHARLIE, 0u128, option_id),
+                Error::<MockRuntime>::CannotPassZeroOptionAmount
+            );
+        });
+}
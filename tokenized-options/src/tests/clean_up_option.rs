@@ -0,0 +1,245 @@
+use crate::mocks::runtime::{
+    Assets, Event, ExtBuilder, MaxCleanupBatch, MockRuntime, Origin, System, TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, Buyers, OptionHashToOptionId, RetiredOptionAssets, Sellers, SpreadPledges},
+    tests::{
+        buy_option::buy_option_success_checks,
+        execute_strategy::{legs, spread_options},
+        sell_option::sell_option_success_checks,
+        *,
+    },
+    types::LegSide,
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect, BoundedVec};
+
+// ----------------------------------------------------------------------------------------------------
+//		Clean Up Option Tests
+// ----------------------------------------------------------------------------------------------------
+pub fn holders(holders: Vec<AccountId>) -> BoundedVec<AccountId, MaxCleanupBatch> {
+    holders.try_into().unwrap()
+}
+
+// Bob sells 5 BTC call options and Alice buys 3 of them. The option ends at block 10 and its
+// claim window is over at block 14.
+fn ended_option() -> AssetId {
+    let option_id = create_option(OptionsConfigBuilder::default().build());
+
+    sell_option_success_checks(option_id, 5u128, BOB);
+    run_to_block(3);
+    buy_option_success_checks(option_id, 3u128, ALICE);
+
+    run_to_block(10);
+
+    option_id
+}
+
+#[test]
+fn test_clean_up_option_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = ended_option();
+            let total_premium_paid = OptionIdToOption::<MockRuntime>::get(option_id)
+                .unwrap()
+                .total_premium_paid;
+
+            run_to_block(14);
+
+            // Anyone can clean up the option
+            assert_ok!(TokenizedOptions::clean_up_option(
+                Origin::signed(CHARLIE),
+                option_id,
+                holders(vec![ALICE])
+            ));
+
+            System::assert_has_event(Event::TokenizedOptions(
+                pallet::Event::UnclaimedCollateralReleased {
+                    user: BOB,
+                    option_id,
+                },
+            ));
+            System::assert_last_event(Event::TokenizedOptions(pallet::Event::OptionCleanedUp {
+                option_id,
+            }));
+
+            // The option expired out of the money, so Bob's collateral and premium are unclaimed
+            assert_eq!(Assets::balance(BTC, &TREASURY), 5u128 * UNIT);
+            assert_eq!(Assets::balance(USDC, &TREASURY), total_premium_paid);
+            assert_eq!(Assets::balance(BTC, &BOB), 0u128);
+
+            assert_eq!(Assets::balance(option_id, &ALICE), 0u128);
+            assert_eq!(Assets::total_issuance(option_id), 0u128);
+
+            assert!(Sellers::<MockRuntime>::get(option_id, BOB).is_none());
+            assert!(Buyers::<MockRuntime>::get(ALICE, option_id).is_none());
+            assert!(OptionIdToOption::<MockRuntime>::get(option_id).is_none());
+            assert_eq!(OptionHashToOptionId::<MockRuntime>::iter().count(), 0);
+            assert!(RetiredOptionAssets::<MockRuntime>::contains_key(option_id));
+        });
+}
+
+#[test]
+fn test_withdraw_collateral_during_claim_window() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = ended_option();
+
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+            assert_eq!(Assets::balance(BTC, &BOB), 5u128 * UNIT);
+
+            run_to_block(14);
+
+            assert_ok!(TokenizedOptions::clean_up_option(
+                Origin::signed(CHARLIE),
+                option_id,
+                holders(vec![ALICE])
+            ));
+
+            // Nothing is left for the unclaimed funds account
+            assert_eq!(Assets::balance(BTC, &TREASURY), 0u128);
+            assert_eq!(Assets::balance(USDC, &TREASURY), 0u128);
+            assert!(OptionIdToOption::<MockRuntime>::get(option_id).is_none());
+        });
+}
+
+#[test]
+fn test_clean_up_option_waits_for_holders() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = ended_option();
+
+            run_to_block(14);
+
+            // Alice's options are still around, so the option is kept
+            assert_ok!(TokenizedOptions::clean_up_option(
+                Origin::signed(CHARLIE),
+                option_id,
+                holders(vec![])
+            ));
+
+            assert!(Sellers::<MockRuntime>::get(option_id, BOB).is_none());
+            assert!(OptionIdToOption::<MockRuntime>::get(option_id).is_some());
+
+            assert_ok!(TokenizedOptions::clean_up_option(
+                Origin::signed(CHARLIE),
+                option_id,
+                holders(vec![ALICE])
+            ));
+
+            assert!(OptionIdToOption::<MockRuntime>::get(option_id).is_none());
+            assert_eq!(Assets::balance(BTC, &TREASURY), 5u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_clean_up_option_after_the_options_it_covers() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (BOB, BTC, 1 * UNIT),
+            (BOB, USDC, 10000 * UNIT),
+            (CHARLIE, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            // Bob sells 2 options covered by 2 protection options, held by the protocol
+            let (option_id, protection_id) = spread_options();
+            let strategy = legs(vec![
+                (option_id, 2u128, LegSide::Sell),
+                (protection_id, 2u128, LegSide::Buy),
+            ]);
+            let (premium_paid, _) =
+                TokenizedOptions::calculate_strategy_premium(&strategy).unwrap();
+            assert_ok!(TokenizedOptions::execute_strategy(
+                Origin::signed(BOB),
+                strategy,
+                premium_paid
+            ));
+
+            run_to_block(14);
+            let pledge_account = TokenizedOptions::account_id(protection_id);
+
+            // Bob's position still needs the protection options
+            assert_noop!(
+                TokenizedOptions::clean_up_option(
+                    Origin::signed(CHARLIE),
+                    protection_id,
+                    holders(vec![pledge_account])
+                ),
+                Error::<MockRuntime>::OptionStillPledged
+            );
+
+            // Cleaning up the option sold releases Bob's position and redeems the protection
+            assert_ok!(TokenizedOptions::clean_up_option(
+                Origin::signed(CHARLIE),
+                option_id,
+                holders(vec![])
+            ));
+            assert!(SpreadPledges::<MockRuntime>::get(option_id, BOB).is_none());
+            assert_eq!(Assets::balance(protection_id, &pledge_account), 0u128);
+
+            // Protocol accounts passed as holders are skipped
+            assert_ok!(TokenizedOptions::clean_up_option(
+                Origin::signed(CHARLIE),
+                protection_id,
+                holders(vec![pledge_account])
+            ));
+            assert!(OptionIdToOption::<MockRuntime>::get(option_id).is_none());
+            assert!(OptionIdToOption::<MockRuntime>::get(protection_id).is_none());
+        });
+}
+
+#[test]
+fn test_clean_up_option_error_claim_window_not_over() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = ended_option();
+
+            run_to_block(13);
+
+            assert_noop!(
+                TokenizedOptions::clean_up_option(
+                    Origin::signed(CHARLIE),
+                    option_id,
+                    holders(vec![ALICE])
+                ),
+                Error::<MockRuntime>::ClaimWindowNotOver
+            );
+        });
+}
@@ -0,0 +1,162 @@
+use crate::mocks::runtime::{
+    set_oracle_price, Assets, Event, ExtBuilder, MockRuntime, Origin, System, TokenizedOptions,
+};
+
+use crate::mocks::{
+    accounts::*,
+    assets::*,
+    collateral_strategy::{accrue_strategy_yield, MockCollateralStrategy},
+};
+
+use crate::{
+    pallet,
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+use traits::collateral_strategy::CollateralStrategy;
+
+// ----------------------------------------------------------------------------------------------------
+//		Collateral Strategy Tests
+// ----------------------------------------------------------------------------------------------------
+// Create the BTC vault allocating `allocation` of its collateral to the strategy, and the USDC
+// vault without strategies
+fn create_vaults(allocation: Perquintill) {
+    let btc_vault_config = VaultConfig {
+        asset_id: BTC,
+        manager: ADMIN,
+        reserved: Perquintill::one() - allocation,
+        strategies: BTreeMap::from([(TokenizedOptions::strategy_account_id(BTC), allocation)]),
+    };
+
+    assert_ok!(TokenizedOptions::create_asset_vault(
+        Origin::signed(ADMIN),
+        btc_vault_config
+    ));
+    assert_ok!(TokenizedOptions::create_asset_vault(
+        Origin::signed(ADMIN),
+        VaultConfigBuilder::default().asset_id(USDC).build()
+    ));
+}
+
+#[test]
+fn test_rebalance_collateral_strategy_yield_accrues_to_sellers() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 10 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            create_vaults(Perquintill::from_percent(50));
+
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+            sell_option_success_checks(option_id, 4u128, BOB);
+
+            // Half of the collateral is allocated to the strategy
+            assert_ok!(TokenizedOptions::rebalance_collateral_strategy(
+                Origin::signed(CHARLIE),
+                BTC
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::CollateralStrategyRebalanced {
+                    asset_id: BTC,
+                    harvested: 0u128,
+                    allocated: 2u128 * UNIT,
+                },
+            ));
+            assert_eq!(Assets::balance(BTC, &STRATEGY), 2u128 * UNIT);
+
+            // The yield is harvested into the vault, and half of it is allocated again
+            accrue_strategy_yield(BTC, UNIT / 10);
+
+            assert_ok!(TokenizedOptions::rebalance_collateral_strategy(
+                Origin::signed(CHARLIE),
+                BTC
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::CollateralStrategyRebalanced {
+                    asset_id: BTC,
+                    harvested: UNIT / 10,
+                    allocated: 2u128 * UNIT + UNIT / 20,
+                },
+            ));
+
+            // No option is bought, and Bob withdraws his collateral with the yield
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+
+            assert_eq!(Assets::balance(BTC, &BOB), 10u128 * UNIT + UNIT / 10);
+            assert_eq!(MockCollateralStrategy::balance(BTC), 0u128);
+        });
+}
+
+#[test]
+fn test_strategy_funds_are_recalled_for_settlement() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 250000 * UNIT),
+            (BOB, BTC, 4 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            create_vaults(Perquintill::from_percent(90));
+
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+            sell_option_success_checks(option_id, 4u128, BOB);
+
+            assert_ok!(TokenizedOptions::rebalance_collateral_strategy(
+                Origin::signed(CHARLIE),
+                BTC
+            ));
+
+            run_to_block(3);
+            buy_option_success_checks(option_id, 4u128, ALICE);
+
+            // The vault only holds 0.4 BTC, less than the payoff of the options
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            // (60000 - 50000) / 60000 BTC for each option, rounded to the nearest
+            let payout = 4u128 * 166_666_666_667u128;
+
+            System::assert_has_event(Event::TokenizedOptions(
+                pallet::Event::StrategyFundsRecalled {
+                    asset_id: BTC,
+                    amount: payout - 4u128 * UNIT / 10,
+                },
+            ));
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                4u128,
+                option_id
+            ));
+            assert_eq!(Assets::balance(BTC, &ALICE), payout);
+        });
+}
+
+#[test]
+fn test_create_asset_vault_error_invalid_strategies() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            let vault_config = VaultConfig {
+                asset_id: BTC,
+                manager: ADMIN,
+                reserved: Perquintill::from_percent(50),
+                strategies: BTreeMap::from([(BOB, Perquintill::from_percent(50))]),
+            };
+
+            assert_noop!(
+                TokenizedOptions::create_asset_vault(Origin::signed(ADMIN), vault_config),
+                Error::<MockRuntime>::VaultStrategiesAreInvalid
+            );
+        });
+}
@@ -46,6 +46,7 @@ fn test_create_option_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Create option and get option id
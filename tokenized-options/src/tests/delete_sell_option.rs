@@ -140,6 +140,7 @@ fn test_delete_sell_option_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -178,6 +179,7 @@ fn test_delete_sell_option_update_position() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -227,6 +229,7 @@ fn test_delete_sell_option_multiple_users() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -309,6 +312,7 @@ fn test_delete_sell_option_error_not_into_deposit_window() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -356,6 +360,7 @@ fn test_delete_sell_option_error_user_has_not_enough_collateral_to_withdraw() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -399,6 +404,7 @@ fn test_delete_sell_option_error_user_has_not_enough_collateral_to_withdraw_upda
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -448,6 +454,7 @@ fn test_delete_sell_option_error_user_does_not_have_position() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -485,6 +492,7 @@ fn test_delete_sell_option_error_cannot_delete_zero_options_sale() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -528,6 +536,7 @@ fn test_delete_sell_option_error_overflow_asset_amount() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -576,6 +585,7 @@ fn test_delete_sell_option_error_withdrawals_not_allowed() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -628,6 +638,7 @@ fn test_delete_sell_option_error_withdrawals_not_allowed_update_position() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -692,6 +703,7 @@ fn test_delete_sell_option_shares_calculation_with_vault_value_accrual_success()
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
                 option_hash
@@ -0,0 +1,253 @@
+use crate::mocks::runtime::{
+    set_oracle_price, Assets, Balance, Event, ExtBuilder, MockRuntime, Origin, System,
+    TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet,
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+
+// ----------------------------------------------------------------------------------------------------
+//		Early Exercise Tests
+// ----------------------------------------------------------------------------------------------------
+fn american_option_config() -> OptionConfig<AssetId, Balance, Moment> {
+    OptionsConfigBuilder::default()
+        .exercise_type(ExerciseType::American)
+        .build()
+}
+
+// Bob sells 5 BTC call options and Alice buys 3 of them
+fn sell_and_buy_american_option() -> AssetId {
+    let option_id = create_option(american_option_config());
+
+    sell_option_success_checks(option_id, 5u128, BOB);
+    run_to_block(3);
+    buy_option_success_checks(option_id, 3u128, ALICE);
+
+    option_id
+}
+
+#[test]
+fn test_early_exercise_american_call_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_american_option();
+
+            // The payoff of each option is (100000 - 50000) / 100000 = 0.5 BTC
+            set_oracle_price(BTC, 100000u128 * UNIT);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(pallet::Event::ExerciseOption {
+                user: ALICE,
+                option_amount: 2u128,
+                option_id,
+            }));
+
+            assert_eq!(Assets::balance(BTC, &ALICE), 1u128 * UNIT);
+            assert_eq!(Assets::balance(option_id, &ALICE), 1u128);
+            assert_eq!(Assets::total_issuance(option_id), 1u128);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_exercised_early, 2u128);
+            assert_eq!(option.total_shares_exercised_early, 1u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_early_exercise_out_of_the_money_pays_nothing() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_american_option();
+
+            set_oracle_price(BTC, 40000u128 * UNIT);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                3u128,
+                option_id
+            ));
+
+            assert_eq!(Assets::balance(BTC, &ALICE), 0u128);
+            assert_eq!(Assets::balance(option_id, &ALICE), 0u128);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_exercised_early, 3u128);
+            assert_eq!(option.total_shares_exercised_early, 0u128);
+        });
+}
+
+#[test]
+fn test_early_exercise_error_european_option() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+
+            sell_option_success_checks(option_id, 5u128, BOB);
+            run_to_block(3);
+            buy_option_success_checks(option_id, 3u128, ALICE);
+
+            assert_noop!(
+                TokenizedOptions::exercise_option(Origin::signed(ALICE), 1u128, option_id),
+                Error::<MockRuntime>::NotIntoExerciseWindow
+            );
+        });
+}
+
+#[test]
+fn test_early_exercise_error_after_expiry() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            // The purchase window lasts after the expiring date
+            let option_id = create_option(
+                OptionsConfigBuilder::default()
+                    .exercise_type(ExerciseType::American)
+                    .epoch(Epoch {
+                        deposit: 0u64,
+                        purchase: 2000u64,
+                        exercise: 7000u64,
+                        end: 9000u64,
+                    })
+                    .build(),
+            );
+
+            sell_option_success_checks(option_id, 5u128, BOB);
+            run_to_block(3);
+            buy_option_success_checks(option_id, 3u128, ALICE);
+
+            run_to_block(6);
+
+            assert_noop!(
+                TokenizedOptions::exercise_option(Origin::signed(ALICE), 1u128, option_id),
+                Error::<MockRuntime>::NotIntoExerciseWindow
+            );
+        });
+}
+
+#[test]
+fn test_early_exercise_error_user_has_not_enough_option_tokens() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_american_option();
+
+            assert_noop!(
+                TokenizedOptions::exercise_option(Origin::signed(ALICE), 4u128, option_id),
+                Error::<MockRuntime>::UserHasNotEnoughOptionTokens
+            );
+        });
+}
+
+#[test]
+fn test_early_exercised_options_cannot_be_bought_again() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+            (CHARLIE, USDC, 150000 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_american_option();
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id
+            ));
+
+            // 1 option is still held by Alice and 2 have been exercised
+            assert_noop!(
+                TokenizedOptions::buy_option(Origin::signed(CHARLIE), 3u128, option_id, u128::MAX),
+                Error::<MockRuntime>::NotEnoughOptionsForSale
+            );
+
+            buy_option_success_checks(option_id, 2u128, CHARLIE);
+        });
+}
+
+#[test]
+fn test_withdraw_collateral_after_early_exercise() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_american_option();
+
+            set_oracle_price(BTC, 100000u128 * UNIT);
+
+            // 2 options exercised early for 0.5 BTC each
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id
+            ));
+
+            // The last option is settled at expiry for 0.5 BTC
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                1u128,
+                option_id
+            ));
+            assert_eq!(Assets::balance(BTC, &ALICE), 3u128 * UNIT / 2);
+
+            // Bob gets back the collateral not paid to Alice
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+            assert_eq!(Assets::balance(BTC, &BOB), 7u128 * UNIT / 2);
+        });
+}
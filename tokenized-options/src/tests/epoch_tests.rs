@@ -33,6 +33,7 @@ fn generate_option_and_get_id(
 		option_config.option_type,
 		option_config.expiring_date,
 		option_config.exercise_type,
+		option_config.settlement_type,
 	);
 
 	OptionHashToOptionId::<MockRuntime>::get(option_hash).unwrap()
@@ -70,7 +71,7 @@ fn test_epoch_basic_example_totally_sold() {
 
 			run_to_block(3);
 
-			assert_ok!(TokenizedOptions::buy_option(Origin::signed(CHARLIE), 5u128, option_id1));
+			assert_ok!(TokenizedOptions::buy_option(Origin::signed(CHARLIE), 5u128, option_id1, Balance::MAX));
 
 			set_oracle_price(BTC, 55000u128 * UNIT);
 
@@ -161,7 +162,7 @@ fn test_epoch_basic_example_no_totally_sold() {
 
 			run_to_block(3);
 
-			assert_ok!(TokenizedOptions::buy_option(Origin::signed(CHARLIE), 4u128, option_id1));
+			assert_ok!(TokenizedOptions::buy_option(Origin::signed(CHARLIE), 4u128, option_id1, Balance::MAX));
 
 			set_oracle_price(BTC, 100000u128 * UNIT);
 
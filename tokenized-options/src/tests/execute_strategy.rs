@@ -0,0 +1,295 @@
+use crate::mocks::runtime::{
+    set_oracle_price, Assets, Balance, Event, ExtBuilder, MaxStrategyLegs, MockRuntime, Origin,
+    System, TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, SpreadPledges},
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+    types::{LegSide, SpreadPledge, StrategyLeg},
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect, BoundedVec};
+
+// ----------------------------------------------------------------------------------------------------
+//		Execute Strategy Tests
+// ----------------------------------------------------------------------------------------------------
+pub fn legs(
+    legs: Vec<(AssetId, Balance, LegSide)>,
+) -> BoundedVec<StrategyLeg<AssetId, Balance>, MaxStrategyLegs> {
+    legs.into_iter()
+        .map(|(option_id, option_amount, side)| StrategyLeg {
+            option_id,
+            option_amount,
+            side,
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+// Creates a 55000 BTC call that Charlie sells, and a 50000 BTC call sharing its epoch. At block 3
+// both options are in purchase phase, so the second one can only be sold covered by the first one.
+pub fn spread_options() -> (AssetId, AssetId) {
+    let protection_id = create_option_not_started(
+        OptionsConfigBuilder::default()
+            .base_asset_strike_price(55000u128 * UNIT)
+            .build(),
+    );
+    let option_id = create_option_not_started(OptionsConfigBuilder::default().build());
+
+    run_to_block(2);
+    sell_option_success_checks(protection_id, 5u128, CHARLIE);
+    run_to_block(3);
+
+    (option_id, protection_id)
+}
+
+#[test]
+fn test_execute_strategy_call_spread_with_netted_collateral() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 1 * UNIT),
+            (BOB, USDC, 10000 * UNIT),
+            (CHARLIE, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let (option_id, protection_id) = spread_options();
+            let strategy = legs(vec![
+                (option_id, 2u128, LegSide::Sell),
+                (protection_id, 2u128, LegSide::Buy),
+            ]);
+
+            let (premium_paid, premium_sold) =
+                TokenizedOptions::calculate_strategy_premium(&strategy).unwrap();
+
+            // The premium of the sold legs offsets the one paid
+            assert_ok!(TokenizedOptions::execute_strategy(
+                Origin::signed(BOB),
+                strategy.clone(),
+                premium_paid.saturating_sub(premium_sold)
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(pallet::Event::StrategyExecuted {
+                user: BOB,
+                legs: strategy,
+                premium_paid,
+            }));
+
+            // The maximum loss is (55000 - 50000) / 55000 BTC for each option, rounded up
+            let max_loss = 2u128 * 90_909_090_910u128;
+            assert_eq!(Assets::balance(BTC, &BOB), 1u128 * UNIT - max_loss);
+
+            // The protection is held by the protocol
+            assert_eq!(
+                SpreadPledges::<MockRuntime>::get(option_id, BOB),
+                Some(SpreadPledge {
+                    protection_option_id: protection_id,
+                    option_amount: 2u128,
+                })
+            );
+            assert_eq!(Assets::balance(protection_id, &BOB), 0u128);
+            assert_eq!(
+                Assets::balance(protection_id, &TokenizedOptions::account_id(protection_id)),
+                2u128
+            );
+
+            // Alice buys the options sold by Bob and both options expire in the money
+            run_to_block(4);
+            buy_option_success_checks(option_id, 2u128, ALICE);
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id
+            ));
+
+            // Bob pays (60000 - 50000) / 60000 BTC for each option and receives (60000 - 55000) /
+            // 60000 BTC from the protection
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+
+            let payoff = 2u128 * 83_333_333_333u128;
+            let owed = 2u128 * 166_666_666_667u128;
+            assert_eq!(
+                Assets::balance(BTC, &BOB),
+                1u128 * UNIT - max_loss + (max_loss + payoff - owed)
+            );
+
+            assert!(SpreadPledges::<MockRuntime>::get(option_id, BOB).is_none());
+            assert_eq!(Assets::total_issuance(protection_id), 0u128);
+        });
+}
+
+#[test]
+fn test_execute_strategy_uncovered_sale_locks_full_collateral() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (BOB, BTC, 1 * UNIT),
+            (BOB, USDC, 10000 * UNIT),
+            (CHARLIE, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+
+            assert_ok!(TokenizedOptions::execute_strategy(
+                Origin::signed(BOB),
+                legs(vec![(option_id, 1u128, LegSide::Sell)]),
+                0u128
+            ));
+
+            assert_eq!(Assets::balance(BTC, &BOB), 0u128);
+            assert!(SpreadPledges::<MockRuntime>::get(option_id, BOB).is_none());
+        });
+}
+
+#[test]
+fn test_execute_strategy_is_atomic() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (BOB, BTC, 1 * UNIT),
+            (BOB, USDC, 10000 * UNIT),
+            (CHARLIE, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let (option_id, protection_id) = spread_options();
+
+            // Only one of the options sold is covered, and the other can't be sold in purchase
+            // phase, so the option bought is not bought either
+            assert_noop!(
+                TokenizedOptions::execute_strategy(
+                    Origin::signed(BOB),
+                    legs(vec![
+                        (option_id, 2u128, LegSide::Sell),
+                        (protection_id, 1u128, LegSide::Buy),
+                    ]),
+                    10000u128 * UNIT
+                ),
+                Error::<MockRuntime>::NotIntoDepositWindow
+            );
+        });
+}
+
+#[test]
+fn test_delete_sell_option_error_netted_position() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (BOB, BTC, 1 * UNIT),
+            (BOB, USDC, 10000 * UNIT),
+            (CHARLIE, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let (option_id, protection_id) = spread_options();
+
+            assert_ok!(TokenizedOptions::execute_strategy(
+                Origin::signed(BOB),
+                legs(vec![
+                    (option_id, 1u128, LegSide::Sell),
+                    (protection_id, 1u128, LegSide::Buy),
+                ]),
+                10000u128 * UNIT
+            ));
+
+            assert_noop!(
+                TokenizedOptions::delete_sell_option(Origin::signed(BOB), 1u128, option_id),
+                Error::<MockRuntime>::NettedPositionCannotBeDeleted
+            );
+        });
+}
+
+#[test]
+fn test_execute_strategy_error_premium_exceeds_max_premium() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (BOB, BTC, 1 * UNIT),
+            (BOB, USDC, 10000 * UNIT),
+            (CHARLIE, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let (_, protection_id) = spread_options();
+
+            assert_noop!(
+                TokenizedOptions::execute_strategy(
+                    Origin::signed(BOB),
+                    legs(vec![(protection_id, 1u128, LegSide::Buy)]),
+                    0u128
+                ),
+                Error::<MockRuntime>::PremiumExceedsMaxPremium
+            );
+        });
+}
+
+#[test]
+fn test_execute_strategy_error_invalid_legs() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (BOB, BTC, 1 * UNIT),
+            (BOB, USDC, 10000 * UNIT),
+            (CHARLIE, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let (option_id, protection_id) = spread_options();
+            let other_expiry_id = create_option_not_started(
+                OptionsConfigBuilder::default()
+                    .expiring_date(7000u64)
+                    .build(),
+            );
+
+            // Same option twice
+            assert_noop!(
+                TokenizedOptions::execute_strategy(
+                    Origin::signed(BOB),
+                    legs(vec![
+                        (option_id, 1u128, LegSide::Sell),
+                        (option_id, 1u128, LegSide::Buy),
+                    ]),
+                    10000u128 * UNIT
+                ),
+                Error::<MockRuntime>::StrategyLegsAreInvalid
+            );
+
+            // Different expiring dates
+            assert_noop!(
+                TokenizedOptions::execute_strategy(
+                    Origin::signed(BOB),
+                    legs(vec![
+                        (protection_id, 1u128, LegSide::Buy),
+                        (other_expiry_id, 1u128, LegSide::Buy),
+                    ]),
+                    10000u128 * UNIT
+                ),
+                Error::<MockRuntime>::StrategyLegsAreInvalid
+            );
+
+            // No legs
+            assert_noop!(
+                TokenizedOptions::execute_strategy(Origin::signed(BOB), legs(vec![]), 0u128),
+                Error::<MockRuntime>::StrategyLegsAreInvalid
+            );
+        });
+}
@@ -0,0 +1,139 @@
+use crate::mocks::runtime::{
+    set_oracle_price, Assets, Event, ExtBuilder, MockRuntime, Origin, System, TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, AutoExerciseOptOut},
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+
+// ----------------------------------------------------------------------------------------------------
+//		Exercise For Tests
+// ----------------------------------------------------------------------------------------------------
+// Bob sells 2 BTC call options and Alice buys them
+fn bought_option() -> AssetId {
+    let option_id = create_option(OptionsConfigBuilder::default().build());
+
+    sell_option_success_checks(option_id, 2u128, BOB);
+    run_to_block(3);
+    buy_option_success_checks(option_id, 2u128, ALICE);
+
+    option_id
+}
+
+#[test]
+fn test_exercise_for_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = bought_option();
+
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::exercise_for(
+                Origin::signed(CHARLIE),
+                ALICE,
+                option_id
+            ));
+
+            // (60000 - 50000) / 60000 BTC for each option, rounded to the nearest
+            let payout = 2u128 * 166_666_666_667u128;
+            let reward = payout / 100;
+
+            System::assert_last_event(Event::TokenizedOptions(pallet::Event::ExercisedFor {
+                keeper: CHARLIE,
+                user: ALICE,
+                option_id,
+                option_amount: 2u128,
+                reward,
+            }));
+
+            assert_eq!(Assets::balance(BTC, &ALICE), payout - reward);
+            assert_eq!(Assets::balance(BTC, &CHARLIE), reward);
+            assert_eq!(Assets::balance(option_id, &ALICE), 0u128);
+        });
+}
+
+#[test]
+fn test_exercise_for_error_auto_exercise_disabled() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = bought_option();
+
+            assert_ok!(TokenizedOptions::update_auto_exercise(
+                Origin::signed(ALICE),
+                false
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::AutoExerciseUpdated {
+                    user: ALICE,
+                    enabled: false,
+                },
+            ));
+            assert!(AutoExerciseOptOut::<MockRuntime>::contains_key(ALICE));
+
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            assert_noop!(
+                TokenizedOptions::exercise_for(Origin::signed(CHARLIE), ALICE, option_id),
+                Error::<MockRuntime>::AutoExerciseDisabled
+            );
+
+            // Alice can still exercise the options herself
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id
+            ));
+            assert_eq!(Assets::balance(BTC, &ALICE), 2u128 * 166_666_666_667u128);
+        });
+}
+
+#[test]
+fn test_exercise_for_error_option_out_of_the_money() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = bought_option();
+
+            // The option can't be exercised for Alice before expiry
+            assert_noop!(
+                TokenizedOptions::exercise_for(Origin::signed(CHARLIE), ALICE, option_id),
+                Error::<MockRuntime>::NotIntoExerciseWindow
+            );
+
+            set_oracle_price(BTC, 40000u128 * UNIT);
+            run_to_block(6);
+
+            assert_noop!(
+                TokenizedOptions::exercise_for(Origin::signed(CHARLIE), ALICE, option_id),
+                Error::<MockRuntime>::OptionCannotBeAutoExercised
+            );
+        });
+}
@@ -128,6 +128,7 @@ fn test_exercise_option_call_with_initialization_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -158,13 +159,15 @@ fn test_exercise_option_call_with_initialization_success() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -238,6 +241,7 @@ fn test_exercise_option_put_with_initialization_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -268,13 +272,15 @@ fn test_exercise_option_put_with_initialization_success() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -326,6 +332,7 @@ fn test_exercise_option_call_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -358,13 +365,15 @@ fn test_exercise_option_call_success() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -414,6 +423,7 @@ fn test_exercise_option_put_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -446,13 +456,15 @@ fn test_exercise_option_put_success() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -502,6 +514,7 @@ fn test_exercise_option_call_multiple_times() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -534,13 +547,15 @@ fn test_exercise_option_call_multiple_times() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -592,6 +607,7 @@ fn test_exercise_option_put_multiple_times() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -624,13 +640,15 @@ fn test_exercise_option_put_multiple_times() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -680,6 +698,7 @@ fn test_exercise_option_out_of_money_tokens_burned() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -704,7 +723,8 @@ fn test_exercise_option_out_of_money_tokens_burned() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 45k, buyers are in profit
@@ -764,6 +784,7 @@ fn test_exercise_option_error_not_into_exercise_window() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -788,7 +809,8 @@ fn test_exercise_option_error_not_into_exercise_window() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -836,6 +858,7 @@ fn test_exercise_option_error_cannot_exercise_zero_options() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -860,7 +883,8 @@ fn test_exercise_option_error_cannot_exercise_zero_options() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -904,6 +928,7 @@ fn test_exercise_option_error_cannot_exercise_too_much_options() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -928,7 +953,8 @@ fn test_exercise_option_error_cannot_exercise_too_much_options() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 45k, buyers are in profit
@@ -974,6 +1000,7 @@ fn test_exercise_option_error_overflow() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -998,7 +1025,8 @@ fn test_exercise_option_error_overflow() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -0,0 +1,135 @@
+use crate::mocks::runtime::{ExtBuilder, MockRuntime, OptionsPricing, TokenizedOptions};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, OptionHashToOptionId},
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::assert_noop;
+use sp_runtime::{FixedI128, FixedPointNumber};
+use traits::options_pricing::{BlackScholesParams, Greeks, OptionsPricing as OptionsPricingTrait};
+
+// ----------------------------------------------------------------------------------------------------
+//		Greeks Tests
+// ----------------------------------------------------------------------------------------------------
+
+fn default_option_id() -> AssetId {
+    let option_config = OptionsConfigBuilder::default().build();
+
+    let option_hash = TokenizedOptions::generate_id(
+        option_config.base_asset_id,
+        option_config.quote_asset_id,
+        option_config.base_asset_strike_price,
+        option_config.quote_asset_strike_price,
+        option_config.option_type,
+        option_config.expiring_date,
+        option_config.exercise_type,
+        option_config.settlement_type,
+    );
+
+    OptionHashToOptionId::<MockRuntime>::get(option_hash).unwrap()
+}
+
+#[test]
+fn test_get_option_greeks_success() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .initialize_all_options()
+        .execute_with(|| {
+            let option_id = default_option_id();
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+
+            let expected_greeks =
+                <OptionsPricing as OptionsPricingTrait>::get_option_greeks(BlackScholesParams {
+                    base_asset_id: option.base_asset_id,
+                    base_asset_strike_price: option.base_asset_strike_price,
+                    base_asset_spot_price: get_oracle_price(option.base_asset_id, UNIT),
+                    expiring_date: option.expiring_date,
+                    option_type: option.option_type,
+                    total_issuance_buyer: option.total_issuance_buyer,
+                    total_premium_paid: option.total_premium_paid,
+                })
+                .unwrap();
+
+            assert_eq!(
+                TokenizedOptions::get_option_greeks(option_id),
+                Ok(expected_greeks)
+            );
+            // At the money call option
+            assert!(expected_greeks.delta > FixedI128::saturating_from_rational(1, 2));
+        });
+}
+
+#[test]
+fn test_get_option_greeks_error_option_not_exists() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            assert_noop!(
+                TokenizedOptions::get_option_greeks(10000000000005u128),
+                Error::<MockRuntime>::OptionDoesNotExists
+            );
+        });
+}
+
+#[test]
+fn test_get_vault_greeks_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .initialize_all_options()
+        .execute_with(|| {
+            let option_id = default_option_id();
+
+            sell_option_success_checks(option_id, 5u128, BOB);
+            run_to_block(3);
+
+            // No options bought yet
+            assert_eq!(TokenizedOptions::get_vault_greeks(BTC), Ok(vec![]));
+
+            let alice_option_amount = 3u128;
+            buy_option_success_checks(option_id, alice_option_amount, ALICE);
+
+            // The BTC vault is short the options bought by Alice
+            let option_greeks = TokenizedOptions::get_option_greeks(option_id).unwrap();
+            let amount = FixedI128::saturating_from_integer(alice_option_amount);
+            let short = |greek: FixedI128| -(greek * amount);
+            let expected_greeks = Greeks {
+                delta: short(option_greeks.delta),
+                gamma: short(option_greeks.gamma),
+                vega: short(option_greeks.vega),
+                theta: short(option_greeks.theta),
+                rho: short(option_greeks.rho),
+            };
+
+            assert_eq!(
+                TokenizedOptions::get_vault_greeks(BTC),
+                Ok(vec![(BTC, expected_greeks)])
+            );
+            // Call options are not collateralized by the USDC vault
+            assert_eq!(TokenizedOptions::get_vault_greeks(USDC), Ok(vec![]));
+        });
+}
+
+#[test]
+fn test_get_vault_greeks_error_vault_not_exists() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .execute_with(|| {
+            assert_noop!(
+                TokenizedOptions::get_vault_greeks(ETH),
+                Error::<MockRuntime>::AssetVaultDoesNotExists
+            );
+        });
+}
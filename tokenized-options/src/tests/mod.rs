@@ -5,11 +5,11 @@ use crate::{
         accounts::*,
         assets::*,
         runtime::{
-            get_oracle_price, set_oracle_price, Assets, Balance, Moment, OptionId, Origin, System,
-            Timestamp, TokenizedOptions, Vault, VaultId,
+            get_oracle_price, set_oracle_price, Assets, Balance, MockRuntime, Moment, OptionId,
+            Origin, System, Timestamp, TokenizedOptions, Vault, VaultId,
         },
     },
-    pallet::{AssetToVault, Error, OptionIdToOption},
+    pallet::{AssetToVault, Error, OptionHashToOptionId, OptionIdToOption},
 };
 use composable_traits::vault::{Vault as VaultTrait, VaultConfig};
 
@@ -31,12 +31,28 @@ use sp_runtime::{DispatchError, Perquintill};
 use std::collections::BTreeMap;
 
 pub mod buy_option;
+pub mod buyer_positions;
+pub mod clean_up_option;
+pub mod collateral_strategy;
 pub mod create_option;
 pub mod create_vault;
 pub mod delete_sell_option;
+pub mod early_exercise;
+pub mod execute_strategy;
+pub mod exercise_for;
 // mod epoch_tests;
 // pub mod exercise_option;
+pub mod get_greeks;
+pub mod option_series;
+pub mod physical_settlement;
+pub mod protocol_fees;
+pub mod release_unsold_collateral;
+pub mod risk_limits;
+pub mod scheduler;
+pub mod sell_back_option;
 pub mod sell_option;
+pub mod sell_option_with_margin;
+pub mod settlement;
 // pub mod settle_options;
 // mod time_management;
 // mod withdraw_collateral;
@@ -177,6 +193,7 @@ struct OptionsConfigBuilder {
     pub quote_asset_strike_price: Balance,
     pub option_type: OptionType,
     pub exercise_type: ExerciseType,
+    pub settlement_type: SettlementType,
     pub expiring_date: Moment,
     pub epoch: Epoch<Moment>,
     pub status: Status,
@@ -199,6 +216,7 @@ impl Default for OptionsConfigBuilder {
             quote_asset_strike_price: 1u128 * UNIT,
             option_type: OptionType::Call,
             exercise_type: ExerciseType::European,
+            settlement_type: SettlementType::Cash,
             expiring_date: 6000u64,
             // Use this when https://github.com/paritytech/substrate/pull/10128 is merged
             // epoch: Epoch { deposit: 0u64, purchase: 3000u64, exercise: 6000u64, end: 9000u64 },
@@ -230,6 +248,7 @@ impl OptionsConfigBuilder {
             quote_asset_strike_price: self.quote_asset_strike_price,
             option_type: self.option_type,
             exercise_type: self.exercise_type,
+            settlement_type: self.settlement_type,
             expiring_date: self.expiring_date,
             epoch: self.epoch,
             status: self.status,
@@ -279,6 +298,11 @@ impl OptionsConfigBuilder {
         self
     }
 
+    fn settlement_type(mut self, settlement_type: SettlementType) -> Self {
+        self.settlement_type = settlement_type;
+        self
+    }
+
     fn total_issuance_seller(mut self, total_issuance_seller: Balance) -> Self {
         self.total_issuance_seller = total_issuance_seller;
         self
@@ -556,6 +580,38 @@ pub fn trait_create_asset_vault(
     Ok(vault_id)
 }
 
+// Call extrinsic `create_option` and return the id of the created option
+pub fn create_option_not_started(
+    option_config: OptionConfig<AssetId, Balance, Moment>,
+) -> OptionId {
+    assert_ok!(TokenizedOptions::create_option(
+        Origin::signed(ADMIN),
+        option_config.clone()
+    ));
+
+    let option_hash = TokenizedOptions::generate_id(
+        option_config.base_asset_id,
+        option_config.quote_asset_id,
+        option_config.base_asset_strike_price,
+        option_config.quote_asset_strike_price,
+        option_config.option_type,
+        option_config.expiring_date,
+        option_config.exercise_type,
+        option_config.settlement_type,
+    );
+
+    OptionHashToOptionId::<MockRuntime>::get(option_hash).unwrap()
+}
+
+// Call extrinsic `create_option` and make the option go from NotStarted to Deposit phase
+pub fn create_option(option_config: OptionConfig<AssetId, Balance, Moment>) -> OptionId {
+    let option_id = create_option_not_started(option_config);
+
+    run_to_block(2);
+
+    option_id
+}
+
 // Simulate extrinsic call `create_option`, but returning values
 pub fn trait_create_option(
     origin: Origin,
@@ -0,0 +1,432 @@
+use crate::mocks::runtime::{
+    set_oracle_price, Assets, Balance, Event, ExtBuilder, MockRuntime, Origin, System,
+    TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{
+        self, OptionIdToSeries, PendingRollovers, RolloverSellersCount, Sellers, Series,
+        SeriesCount,
+    },
+    tests::{sell_option::sell_option_success_checks, *},
+    types::{OptionSeries, RiskLimits, SeriesId, StrikeSelection},
+};
+
+use frame_support::{assert_noop, assert_ok, error::BadOrigin, traits::fungibles::Inspect};
+use sp_runtime::{Percent, Permill};
+
+// ----------------------------------------------------------------------------------------------------
+//		Helper functions
+// ----------------------------------------------------------------------------------------------------
+// Each option lasts 9 seconds (deposit 0-2000, purchase 2000-5000, exercise 5000-9000) and the
+// next one starts as soon as the previous one ends
+fn series_template(strike_selection: StrikeSelection) -> OptionSeries<AssetId, Balance, Moment> {
+    OptionSeries {
+        base_asset_id: BTC,
+        quote_asset_id: USDC,
+        quote_asset_strike_price: 1u128 * UNIT,
+        option_type: OptionType::Call,
+        exercise_type: ExerciseType::European,
+        settlement_type: SettlementType::Cash,
+        strike_selection,
+        base_asset_amount_per_option: 1u128 * UNIT,
+        quote_asset_amount_per_option: 1u128 * UNIT,
+        start: 0u64,
+        cadence: 9000u64,
+        deposit_duration: 2000u64,
+        purchase_duration: 3000u64,
+        exercise_duration: 4000u64,
+    }
+}
+
+fn create_series(series: OptionSeries<AssetId, Balance, Moment>) -> SeriesId {
+    let series_id = SeriesCount::<MockRuntime>::get();
+
+    assert_ok!(TokenizedOptions::create_option_series(
+        Origin::signed(ADMIN),
+        series.clone()
+    ));
+
+    System::assert_last_event(Event::TokenizedOptions(
+        pallet::Event::CreatedOptionSeries { series_id, series },
+    ));
+
+    series_id
+}
+
+// Options of the series, in order of creation
+fn series_options(series_id: SeriesId) -> Vec<AssetId> {
+    let mut options: Vec<AssetId> = OptionIdToSeries::<MockRuntime>::iter()
+        .filter(|(_, option_series_id)| *option_series_id == series_id)
+        .map(|(option_id, _)| option_id)
+        .collect();
+    options.sort();
+    options
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Create Series Tests
+// ----------------------------------------------------------------------------------------------------
+#[test]
+fn test_create_option_series_success() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let series_id = create_series(series_template(StrikeSelection::AtTheMoney));
+
+            assert_eq!(SeriesCount::<MockRuntime>::get(), 1);
+            assert!(Series::<MockRuntime>::contains_key(series_id));
+
+            let options = series_options(series_id);
+            assert_eq!(options.len(), 1);
+
+            let option = OptionIdToOption::<MockRuntime>::get(options[0]).unwrap();
+            assert_eq!(option.base_asset_strike_price, 50000u128 * UNIT);
+            assert_eq!(option.expiring_date, 5000u64);
+            assert_eq!(
+                option.epoch,
+                Epoch {
+                    deposit: 0u64,
+                    purchase: 2000u64,
+                    exercise: 5000u64,
+                    end: 9000u64,
+                }
+            );
+        });
+}
+
+#[test]
+fn test_create_option_series_strike_relative_to_spot() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let above = create_series(series_template(StrikeSelection::AboveSpot(
+                Percent::from_percent(10),
+            )));
+            let below = create_series(series_template(StrikeSelection::BelowSpot(
+                Percent::from_percent(10),
+            )));
+
+            let strike = |series_id| {
+                OptionIdToOption::<MockRuntime>::get(series_options(series_id)[0])
+                    .unwrap()
+                    .base_asset_strike_price
+            };
+
+            assert_eq!(strike(above), 55000u128 * UNIT);
+            assert_eq!(strike(below), 45000u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_create_option_series_error_invalid_attributes() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            // Each option would overlap with the next one
+            let series = OptionSeries {
+                cadence: 5000u64,
+                ..series_template(StrikeSelection::AtTheMoney)
+            };
+
+            assert_noop!(
+                TokenizedOptions::create_option_series(Origin::signed(ADMIN), series),
+                Error::<MockRuntime>::OptionSeriesAttributesAreInvalid
+            );
+
+            let series = OptionSeries {
+                purchase_duration: 0u64,
+                ..series_template(StrikeSelection::AtTheMoney)
+            };
+
+            assert_noop!(
+                TokenizedOptions::create_option_series(Origin::signed(ADMIN), series),
+                Error::<MockRuntime>::OptionSeriesAttributesAreInvalid
+            );
+        });
+}
+
+#[test]
+fn test_create_option_series_error_not_protocol_origin() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            assert_noop!(
+                TokenizedOptions::create_option_series(
+                    Origin::signed(ALICE),
+                    series_template(StrikeSelection::AtTheMoney)
+                ),
+                BadOrigin
+            );
+        });
+}
+
+// ----------------------------------------------------------------------------------------------------
+//		Rollover Tests
+// ----------------------------------------------------------------------------------------------------
+#[test]
+fn test_next_option_created_when_previous_ends() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let series_id = create_series(series_template(StrikeSelection::AtTheMoney));
+
+            run_to_block(9);
+            assert_eq!(series_options(series_id).len(), 1);
+
+            // The strike of the next option follows the oracle price at rollover
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(10);
+
+            let options = series_options(series_id);
+            assert_eq!(options.len(), 2);
+
+            let previous = OptionIdToOption::<MockRuntime>::get(options[0]).unwrap();
+            assert_eq!(previous.status, Status::End);
+
+            let next = OptionIdToOption::<MockRuntime>::get(options[1]).unwrap();
+            assert_eq!(next.base_asset_strike_price, 60000u128 * UNIT);
+            assert_eq!(next.epoch.deposit, 9000u64);
+            assert_eq!(next.epoch.end, 18000u64);
+            assert_eq!(next.status, Status::Deposit);
+            assert_eq!(
+                Series::<MockRuntime>::get(series_id).unwrap().start,
+                9000u64
+            );
+        });
+}
+
+#[test]
+fn test_stop_option_series_success() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let series_id = create_series(series_template(StrikeSelection::AtTheMoney));
+
+            assert_ok!(TokenizedOptions::stop_option_series(
+                Origin::signed(ADMIN),
+                series_id
+            ));
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::OptionSeriesStopped { series_id },
+            ));
+
+            // The current option runs until its end, but it is the last one
+            run_to_block(10);
+            let options = series_options(series_id);
+            assert_eq!(options.len(), 1);
+            assert_eq!(
+                OptionIdToOption::<MockRuntime>::get(options[0])
+                    .unwrap()
+                    .status,
+                Status::End
+            );
+
+            assert_noop!(
+                TokenizedOptions::stop_option_series(Origin::signed(ADMIN), series_id),
+                Error::<MockRuntime>::OptionSeriesDoesNotExist
+            );
+        });
+}
+
+#[test]
+fn test_seller_collateral_rolled_over() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 5 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let series_id = create_series(series_template(StrikeSelection::AtTheMoney));
+
+            assert_ok!(TokenizedOptions::update_collateral_rollover(
+                Origin::signed(BOB),
+                series_id,
+                true
+            ));
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::CollateralRolloverUpdated {
+                    user: BOB,
+                    series_id,
+                    enabled: true,
+                },
+            ));
+
+            run_to_block(2);
+            let first_option_id = series_options(series_id)[0];
+            sell_option_success_checks(first_option_id, 5u128, BOB);
+
+            run_to_block(10);
+            let next_option_id = series_options(series_id)[1];
+
+            // Bob's collateral left the first option and was used to sell the next one
+            assert!(Sellers::<MockRuntime>::get(first_option_id, BOB).is_none());
+            assert_eq!(
+                Sellers::<MockRuntime>::get(next_option_id, BOB)
+                    .unwrap()
+                    .option_amount,
+                5u128
+            );
+            assert_eq!(
+                OptionIdToOption::<MockRuntime>::get(next_option_id)
+                    .unwrap()
+                    .total_issuance_seller,
+                5u128
+            );
+            assert_eq!(Assets::balance(BTC, &BOB), 0u128);
+            assert_eq!(PendingRollovers::<MockRuntime>::get(series_id, BOB), 0u128);
+        });
+}
+
+#[test]
+fn test_rolled_over_collateral_bound_by_open_interest_limit() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 5 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let series_id = create_series(series_template(StrikeSelection::AtTheMoney));
+
+            assert_ok!(TokenizedOptions::update_collateral_rollover(
+                Origin::signed(BOB),
+                series_id,
+                true
+            ));
+
+            run_to_block(2);
+            let first_option_id = series_options(series_id)[0];
+            sell_option_success_checks(first_option_id, 5u128, BOB);
+
+            assert_ok!(TokenizedOptions::update_risk_limits(
+                Origin::signed(ADMIN),
+                BTC,
+                Some(RiskLimits {
+                    max_open_interest: 4u128,
+                    min_epoch_length: 1000u64,
+                    max_epoch_length: 10000u64,
+                    max_strike_deviation: Permill::from_percent(10),
+                    min_time_to_expiry: 1000u64,
+                })
+            ));
+
+            run_to_block(10);
+            let next_option_id = series_options(series_id)[1];
+
+            // The rolled over collateral would exceed the open interest limit, so it goes back to
+            // Bob
+            assert!(Sellers::<MockRuntime>::get(next_option_id, BOB).is_none());
+            assert_eq!(Assets::balance(BTC, &BOB), 5u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_seller_collateral_not_rolled_over_after_opting_out() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 5 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let series_id = create_series(series_template(StrikeSelection::AtTheMoney));
+
+            assert_ok!(TokenizedOptions::update_collateral_rollover(
+                Origin::signed(BOB),
+                series_id,
+                true
+            ));
+            assert_ok!(TokenizedOptions::update_collateral_rollover(
+                Origin::signed(BOB),
+                series_id,
+                false
+            ));
+
+            run_to_block(2);
+            let first_option_id = series_options(series_id)[0];
+            sell_option_success_checks(first_option_id, 5u128, BOB);
+
+            run_to_block(10);
+            let next_option_id = series_options(series_id)[1];
+
+            assert!(Sellers::<MockRuntime>::get(first_option_id, BOB).is_some());
+            assert!(Sellers::<MockRuntime>::get(next_option_id, BOB).is_none());
+        });
+}
+
+#[test]
+fn test_update_collateral_rollover_error_series_does_not_exist() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            assert_noop!(
+                TokenizedOptions::update_collateral_rollover(Origin::signed(BOB), 0, true),
+                Error::<MockRuntime>::OptionSeriesDoesNotExist
+            );
+        });
+}
+
+#[test]
+fn test_update_collateral_rollover_error_too_many_rollover_sellers() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let series_id = create_series(series_template(StrikeSelection::AtTheMoney));
+
+            for seller in [ALICE, BOB, CHARLIE, DAVE] {
+                assert_ok!(TokenizedOptions::update_collateral_rollover(
+                    Origin::signed(seller),
+                    series_id,
+                    true
+                ));
+            }
+
+            assert_noop!(
+                TokenizedOptions::update_collateral_rollover(Origin::signed(EVEN), series_id, true),
+                Error::<MockRuntime>::TooManyRolloverSellers
+            );
+
+            // Sellers already rolling over can opt in again, and opting out frees a slot
+            assert_ok!(TokenizedOptions::update_collateral_rollover(
+                Origin::signed(ALICE),
+                series_id,
+                true
+            ));
+            assert_ok!(TokenizedOptions::update_collateral_rollover(
+                Origin::signed(ALICE),
+                series_id,
+                false
+            ));
+            assert_ok!(TokenizedOptions::update_collateral_rollover(
+                Origin::signed(EVEN),
+                series_id,
+                true
+            ));
+            assert_eq!(RolloverSellersCount::<MockRuntime>::get(series_id), 4);
+
+            // Opting out without rolling over doesn't free a slot
+            assert_ok!(TokenizedOptions::update_collateral_rollover(
+                Origin::signed(ALICE),
+                series_id,
+                false
+            ));
+            assert_eq!(RolloverSellersCount::<MockRuntime>::get(series_id), 4);
+        });
+}
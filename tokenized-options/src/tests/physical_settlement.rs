@@ -0,0 +1,246 @@
+use crate::mocks::runtime::{
+    set_oracle_price, Assets, Balance, Event, ExtBuilder, MockRuntime, Origin, System,
+    TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet,
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+use sp_core::sr25519::Public;
+
+// ----------------------------------------------------------------------------------------------------
+//		Physical Settlement Tests
+// ----------------------------------------------------------------------------------------------------
+fn physical_option_config(option_type: OptionType) -> OptionsConfigBuilder {
+    OptionsConfigBuilder::default()
+        .option_type(option_type)
+        .settlement_type(SettlementType::Physical)
+}
+
+// Bob sells 5 BTC physically settled call options with strike 50000 USDC and Alice buys 3
+fn sell_and_buy_physical_call(exercise_type: ExerciseType) -> AssetId {
+    let option_id = create_option(
+        physical_option_config(OptionType::Call)
+            .exercise_type(exercise_type)
+            .build(),
+    );
+
+    sell_option_success_checks(option_id, 5u128, BOB);
+    run_to_block(3);
+    buy_option_success_checks(option_id, 3u128, ALICE);
+
+    option_id
+}
+
+fn exercise_option(option_id: AssetId, option_amount: Balance, who: Public) {
+    assert_ok!(TokenizedOptions::exercise_option(
+        Origin::signed(who),
+        option_amount,
+        option_id
+    ));
+
+    System::assert_last_event(Event::TokenizedOptions(pallet::Event::ExerciseOption {
+        user: who,
+        option_amount,
+        option_id,
+    }));
+}
+
+#[test]
+fn test_physical_call_exercise_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 500000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_physical_call(ExerciseType::European);
+
+            // Settle in the money
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.exercise_amount, 1u128 * UNIT);
+
+            let initial_usdc_balance = Assets::balance(USDC, &ALICE);
+            exercise_option(option_id, 2u128, ALICE);
+
+            // Alice pays the strike and receives the whole BTC for each option
+            assert_eq!(
+                Assets::balance(USDC, &ALICE),
+                initial_usdc_balance - 100000u128 * UNIT
+            );
+            assert_eq!(Assets::balance(BTC, &ALICE), 2u128 * UNIT);
+            assert_eq!(Assets::balance(option_id, &ALICE), 1u128);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_delivered_amount, 100000u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_physical_call_exercise_out_of_the_money() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 500000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_physical_call(ExerciseType::European);
+
+            // Settle out of the money
+            set_oracle_price(BTC, 40000u128 * UNIT);
+            run_to_block(6);
+
+            let initial_usdc_balance = Assets::balance(USDC, &ALICE);
+            exercise_option(option_id, 3u128, ALICE);
+
+            // Nothing is exchanged, options are just burned
+            assert_eq!(Assets::balance(USDC, &ALICE), initial_usdc_balance);
+            assert_eq!(Assets::balance(BTC, &ALICE), 0u128);
+            assert_eq!(Assets::balance(option_id, &ALICE), 0u128);
+        });
+}
+
+#[test]
+fn test_physical_call_exercise_error_not_enough_funds_to_pay_strike() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_physical_call(ExerciseType::European);
+
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            assert_noop!(
+                TokenizedOptions::exercise_option(Origin::signed(ALICE), 3u128, option_id),
+                Error::<MockRuntime>::UserHasNotEnoughFundsToDeposit
+            );
+        });
+}
+
+#[test]
+fn test_physical_put_exercise_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (ALICE, BTC, 1 * UNIT),
+            (BOB, USDC, 100000 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(physical_option_config(OptionType::Put).build());
+
+            sell_option_success_checks(option_id, 2u128, BOB);
+            run_to_block(3);
+            buy_option_success_checks(option_id, 1u128, ALICE);
+
+            // Settle in the money
+            set_oracle_price(BTC, 40000u128 * UNIT);
+            run_to_block(6);
+
+            let initial_usdc_balance = Assets::balance(USDC, &ALICE);
+            exercise_option(option_id, 1u128, ALICE);
+
+            // Alice delivers the BTC and receives the strike
+            assert_eq!(Assets::balance(BTC, &ALICE), 0u128);
+            assert_eq!(
+                Assets::balance(USDC, &ALICE),
+                initial_usdc_balance + 50000u128 * UNIT
+            );
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_delivered_amount, 1u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_physical_american_call_early_exercise_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 500000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_physical_call(ExerciseType::American);
+
+            let initial_usdc_balance = Assets::balance(USDC, &ALICE);
+            exercise_option(option_id, 1u128, ALICE);
+
+            assert_eq!(
+                Assets::balance(USDC, &ALICE),
+                initial_usdc_balance - 50000u128 * UNIT
+            );
+            assert_eq!(Assets::balance(BTC, &ALICE), 1u128 * UNIT);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_exercised_early, 1u128);
+            assert_eq!(option.total_shares_exercised_early, 1u128 * UNIT);
+            assert_eq!(option.total_delivered_amount, 50000u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_physical_call_withdraw_collateral_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 500000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_physical_call(ExerciseType::European);
+
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            exercise_option(option_id, 2u128, ALICE);
+
+            // Sellers have to wait for the end of the exercise window
+            assert_noop!(
+                TokenizedOptions::withdraw_collateral(Origin::signed(BOB), option_id),
+                Error::<MockRuntime>::ExerciseWindowNotOver
+            );
+
+            run_to_block(10);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+
+            // Bob gets the strike of the exercised options, the collateral of the others and the
+            // premium
+            assert_eq!(Assets::balance(BTC, &BOB), 3u128 * UNIT);
+            assert_eq!(
+                Assets::balance(USDC, &BOB),
+                100000u128 * UNIT + option.total_premium_paid
+            );
+        });
+}
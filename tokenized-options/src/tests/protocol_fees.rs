@@ -0,0 +1,228 @@
+use crate::mocks::runtime::{
+    set_oracle_price, Assets, Balance, Event, ExtBuilder, MockRuntime, Origin, System,
+    TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, ProtocolFees},
+    tests::{sell_option::sell_option_success_checks, *},
+    types::FeeConfig,
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+use sp_runtime::{DispatchError, Permill};
+
+// ----------------------------------------------------------------------------------------------------
+//		Protocol Fees Tests
+// ----------------------------------------------------------------------------------------------------
+fn set_fees(premium_fee: Permill, exercise_fee: Permill, max_fee_per_option: Balance) {
+    assert_ok!(TokenizedOptions::update_protocol_fees(
+        Origin::signed(ADMIN),
+        FeeConfig {
+            premium_fee,
+            exercise_fee,
+            max_fee_per_option,
+        }
+    ));
+}
+
+// Bob sells 2 BTC call options and Alice buys them, paying the premium returned
+fn bought_option() -> (AssetId, Balance) {
+    let option_id = create_option(OptionsConfigBuilder::default().build());
+
+    sell_option_success_checks(option_id, 2u128, BOB);
+    run_to_block(3);
+
+    let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+    let option_premium =
+        TokenizedOptions::calculate_option_premium(option_id, 2u128, &option).unwrap();
+
+    assert_ok!(TokenizedOptions::buy_option(
+        Origin::signed(ALICE),
+        2u128,
+        option_id,
+        option_premium
+    ));
+
+    (option_id, option_premium)
+}
+
+#[test]
+fn test_update_protocol_fees_success() {
+    ExtBuilder::default().build().execute_with(|| {
+        let fees = FeeConfig {
+            premium_fee: Permill::from_percent(10),
+            exercise_fee: Permill::from_percent(1),
+            max_fee_per_option: 100u128 * UNIT,
+        };
+
+        assert_eq!(ProtocolFees::<MockRuntime>::get(), FeeConfig::default());
+
+        assert_ok!(TokenizedOptions::update_protocol_fees(
+            Origin::signed(ADMIN),
+            fees
+        ));
+
+        System::assert_last_event(Event::TokenizedOptions(
+            pallet::Event::ProtocolFeesUpdated { fees },
+        ));
+        assert_eq!(ProtocolFees::<MockRuntime>::get(), fees);
+    });
+}
+
+#[test]
+fn test_update_protocol_fees_error_not_protocol_origin() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            TokenizedOptions::update_protocol_fees(Origin::signed(BOB), FeeConfig::default()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn test_premium_fee_is_taken_from_sellers_premium() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            set_fees(
+                Permill::from_percent(10),
+                Permill::zero(),
+                100000u128 * UNIT,
+            );
+
+            let (option_id, option_premium) = bought_option();
+            let protocol_fee = option_premium / 10;
+
+            System::assert_has_event(Event::TokenizedOptions(pallet::Event::ProtocolFeePaid {
+                user: ALICE,
+                option_id,
+                asset_id: USDC,
+                amount: protocol_fee,
+            }));
+
+            // Alice pays the premium, the fee goes to the treasury
+            assert_eq!(
+                Assets::balance(USDC, &ALICE),
+                150000u128 * UNIT - option_premium
+            );
+            assert_eq!(Assets::balance(USDC, &TREASURY), protocol_fee);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_premium_paid, option_premium - protocol_fee);
+
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+            assert_eq!(Assets::balance(USDC, &BOB), option_premium - protocol_fee);
+        });
+}
+
+#[test]
+fn test_premium_fee_is_capped_per_option() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            set_fees(Permill::from_percent(10), Permill::zero(), 10u128 * UNIT);
+
+            let (option_id, option_premium) = bought_option();
+
+            assert!(option_premium / 10 > 20u128 * UNIT);
+            assert_eq!(Assets::balance(USDC, &TREASURY), 20u128 * UNIT);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_premium_paid, option_premium - 20u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_exercise_fee_is_taken_from_payout() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let (option_id, _) = bought_option();
+
+            set_fees(
+                Permill::zero(),
+                Permill::from_percent(10),
+                100000u128 * UNIT,
+            );
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id
+            ));
+
+            // (60000 - 50000) / 60000 BTC for each option, rounded to the nearest
+            let payout = 2u128 * 166_666_666_667u128;
+            let protocol_fee = payout / 10;
+
+            System::assert_has_event(Event::TokenizedOptions(pallet::Event::ProtocolFeePaid {
+                user: ALICE,
+                option_id,
+                asset_id: BTC,
+                amount: protocol_fee,
+            }));
+
+            assert_eq!(Assets::balance(BTC, &ALICE), payout - protocol_fee);
+            assert_eq!(Assets::balance(BTC, &TREASURY), protocol_fee);
+        });
+}
+
+#[test]
+fn test_exercise_fee_is_capped_per_option() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let (option_id, _) = bought_option();
+
+            set_fees(Permill::zero(), Permill::from_percent(10), 100u128 * UNIT);
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id
+            ));
+
+            // The cap of 100 USDC is worth 100 / 60000 BTC at the settlement price
+            let payout = 2u128 * 166_666_666_667u128;
+            let protocol_fee = 2u128 * 1_666_666_666u128;
+
+            assert_eq!(Assets::balance(BTC, &ALICE), payout - protocol_fee);
+            assert_eq!(Assets::balance(BTC, &TREASURY), protocol_fee);
+        });
+}
@@ -0,0 +1,179 @@
+use crate::mocks::runtime::{
+    remove_oracle_price, set_oracle_price, Assets, Event, ExtBuilder, MockRuntime, Origin, System,
+    TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, Sellers},
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+
+// ----------------------------------------------------------------------------------------------------
+//		Release Unsold Collateral Tests
+// ----------------------------------------------------------------------------------------------------
+// Bob sells 3 BTC call options and Charlie 1, then Alice buys 2 of them
+fn half_sold_option() -> AssetId {
+    let option_id = create_option(OptionsConfigBuilder::default().build());
+
+    sell_option_success_checks(option_id, 3u128, BOB);
+    sell_option_success_checks(option_id, 1u128, CHARLIE);
+    run_to_block(3);
+    buy_option_success_checks(option_id, 2u128, ALICE);
+
+    option_id
+}
+
+#[test]
+fn test_release_unsold_collateral_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 3 * UNIT),
+            (CHARLIE, BTC, 1 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = half_sold_option();
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::release_unsold_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+
+            // Half of the options were not bought, so half of Bob's collateral is released
+            let collateral_amount = 3u128 * UNIT / 2;
+
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::UnsoldCollateralReleased {
+                    user: BOB,
+                    option_id,
+                    collateral_amount,
+                },
+            ));
+
+            assert_eq!(Assets::balance(BTC, &BOB), collateral_amount);
+
+            let position = Sellers::<MockRuntime>::get(option_id, BOB).unwrap();
+            assert_eq!(position.option_amount, 3u128);
+            assert!(position.unsold_collateral_released);
+
+            assert_noop!(
+                TokenizedOptions::release_unsold_collateral(Origin::signed(BOB), option_id),
+                Error::<MockRuntime>::UnsoldCollateralAlreadyReleased
+            );
+
+            // The option expired at the money, so both sellers get back all their collateral and
+            // their part of the premium
+            let total_premium_paid = OptionIdToOption::<MockRuntime>::get(option_id)
+                .unwrap()
+                .total_premium_paid;
+
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(CHARLIE),
+                option_id
+            ));
+
+            assert_eq!(Assets::balance(BTC, &BOB), 3u128 * UNIT);
+            assert_eq!(Assets::balance(BTC, &CHARLIE), 1u128 * UNIT);
+            assert_eq!(
+                Assets::balance(USDC, &BOB),
+                total_premium_paid * 3u128 / 4u128
+            );
+            assert_eq!(Assets::balance(USDC, &CHARLIE), total_premium_paid / 4u128);
+        });
+}
+
+#[test]
+fn test_release_unsold_collateral_error_settlement_pending() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 3 * UNIT),
+            (CHARLIE, BTC, 1 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = half_sold_option();
+
+            remove_oracle_price(BTC);
+            run_to_block(6);
+
+            assert_eq!(
+                OptionIdToOption::<MockRuntime>::get(option_id)
+                    .unwrap()
+                    .status,
+                Status::SettlementPending
+            );
+
+            // The options bought are only recorded at settlement
+            assert_noop!(
+                TokenizedOptions::release_unsold_collateral(Origin::signed(CHARLIE), option_id),
+                Error::<MockRuntime>::PurchaseWindowNotOver
+            );
+
+            set_oracle_price(BTC, 50000u128 * UNIT);
+            run_to_block(7);
+
+            assert_ok!(TokenizedOptions::release_unsold_collateral(
+                Origin::signed(CHARLIE),
+                option_id
+            ));
+            assert_eq!(Assets::balance(BTC, &CHARLIE), 1u128 * UNIT / 2);
+        });
+}
+
+#[test]
+fn test_release_unsold_collateral_error_purchase_window_not_over() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 3 * UNIT),
+            (CHARLIE, BTC, 1 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = half_sold_option();
+
+            assert_noop!(
+                TokenizedOptions::release_unsold_collateral(Origin::signed(BOB), option_id),
+                Error::<MockRuntime>::PurchaseWindowNotOver
+            );
+        });
+}
+
+#[test]
+fn test_release_unsold_collateral_error_no_seller_position() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 3 * UNIT),
+            (CHARLIE, BTC, 1 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = half_sold_option();
+            run_to_block(6);
+
+            assert_noop!(
+                TokenizedOptions::release_unsold_collateral(Origin::signed(ALICE), option_id),
+                Error::<MockRuntime>::UserDoesNotHaveSellerPosition
+            );
+        });
+}
@@ -0,0 +1,317 @@
+use crate::mocks::runtime::{
+    Balance, Event, ExtBuilder, MockRuntime, Moment, Origin, System, TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, AssetRiskLimits, OpenInterest},
+    tests::{execute_strategy::legs, sell_option::sell_option_success_checks, *},
+    types::{LegSide, RiskLimits},
+};
+
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{DispatchError, Permill};
+
+// ----------------------------------------------------------------------------------------------------
+//		Risk Limits Tests
+// ----------------------------------------------------------------------------------------------------
+// The default option is within these limits
+fn default_limits() -> RiskLimits<Balance, Moment> {
+    RiskLimits {
+        max_open_interest: 5u128,
+        min_epoch_length: 1000u64,
+        max_epoch_length: 10000u64,
+        max_strike_deviation: Permill::from_percent(10),
+        min_time_to_expiry: 1000u64,
+    }
+}
+
+fn set_limits(limits: RiskLimits<Balance, Moment>) {
+    assert_ok!(TokenizedOptions::update_risk_limits(
+        Origin::signed(ADMIN),
+        BTC,
+        Some(limits)
+    ));
+}
+
+#[test]
+fn test_update_risk_limits_success() {
+    ExtBuilder::default().build().execute_with(|| {
+        let limits = default_limits();
+
+        assert_ok!(TokenizedOptions::update_risk_limits(
+            Origin::signed(ADMIN),
+            BTC,
+            Some(limits)
+        ));
+
+        System::assert_last_event(Event::TokenizedOptions(pallet::Event::RiskLimitsUpdated {
+            asset_id: BTC,
+            limits: Some(limits),
+        }));
+        assert_eq!(AssetRiskLimits::<MockRuntime>::get(BTC), Some(limits));
+
+        assert_ok!(TokenizedOptions::update_risk_limits(
+            Origin::signed(ADMIN),
+            BTC,
+            None
+        ));
+        assert!(AssetRiskLimits::<MockRuntime>::get(BTC).is_none());
+    });
+}
+
+#[test]
+fn test_update_risk_limits_error_invalid_limits() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            TokenizedOptions::update_risk_limits(
+                Origin::signed(ADMIN),
+                BTC,
+                Some(RiskLimits {
+                    min_epoch_length: 20000u64,
+                    ..default_limits()
+                })
+            ),
+            Error::<MockRuntime>::RiskLimitsAreInvalid
+        );
+
+        assert_noop!(
+            TokenizedOptions::update_risk_limits(Origin::signed(BOB), BTC, Some(default_limits())),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn test_create_option_error_outside_risk_limits() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            set_limits(default_limits());
+
+            // The strike is 20% above the oracle price
+            assert_noop!(
+                TokenizedOptions::create_option(
+                    Origin::signed(ADMIN),
+                    OptionsConfigBuilder::default()
+                        .base_asset_strike_price(60000u128 * UNIT)
+                        .build()
+                ),
+                Error::<MockRuntime>::OptionOutsideRiskLimits
+            );
+
+            // The epoch is longer than the maximum
+            set_limits(RiskLimits {
+                max_epoch_length: 5000u64,
+                ..default_limits()
+            });
+
+            assert_noop!(
+                TokenizedOptions::create_option(
+                    Origin::signed(ADMIN),
+                    OptionsConfigBuilder::default().build()
+                ),
+                Error::<MockRuntime>::OptionOutsideRiskLimits
+            );
+
+            // The option expires too soon
+            set_limits(RiskLimits {
+                min_time_to_expiry: 7000u64,
+                ..default_limits()
+            });
+
+            assert_noop!(
+                TokenizedOptions::create_option(
+                    Origin::signed(ADMIN),
+                    OptionsConfigBuilder::default().build()
+                ),
+                Error::<MockRuntime>::OptionOutsideRiskLimits
+            );
+
+            // Options on other assets are not restricted
+            set_limits(RiskLimits {
+                max_epoch_length: 5000u64,
+                ..default_limits()
+            });
+
+            assert_ok!(TokenizedOptions::create_option(
+                Origin::signed(ADMIN),
+                OptionsConfigBuilder::default()
+                    .base_asset_id(LAYR)
+                    .base_asset_strike_price(1u128 * UNIT)
+                    .build()
+            ));
+        });
+}
+
+#[test]
+fn test_sell_option_error_open_interest_limit_exceeded() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 10 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            set_limits(default_limits());
+
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+
+            sell_option_success_checks(option_id, 3u128, BOB);
+
+            assert_noop!(
+                TokenizedOptions::sell_option(Origin::signed(BOB), 3u128, option_id),
+                Error::<MockRuntime>::OpenInterestLimitExceeded
+            );
+
+            // Options written with margin count towards the open interest
+            assert_noop!(
+                TokenizedOptions::sell_option_with_margin(
+                    Origin::signed(BOB),
+                    3u128,
+                    option_id,
+                    3u128 * UNIT
+                ),
+                Error::<MockRuntime>::OpenInterestLimitExceeded
+            );
+
+            sell_option_success_checks(option_id, 2u128, BOB);
+        });
+}
+
+#[test]
+fn test_open_interest_is_aggregated_across_options() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 10 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            set_limits(default_limits());
+
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+            let other_option_id = create_option(
+                OptionsConfigBuilder::default()
+                    .base_asset_strike_price(55000u128 * UNIT)
+                    .build(),
+            );
+
+            sell_option_success_checks(option_id, 3u128, BOB);
+
+            // The limit is shared by all the options on BTC
+            assert_noop!(
+                TokenizedOptions::sell_option(Origin::signed(BOB), 3u128, other_option_id),
+                Error::<MockRuntime>::OpenInterestLimitExceeded
+            );
+
+            assert_ok!(TokenizedOptions::delete_sell_option(
+                Origin::signed(BOB),
+                2u128,
+                option_id
+            ));
+            assert_eq!(OpenInterest::<MockRuntime>::get(BTC), 1u128);
+
+            sell_option_success_checks(other_option_id, 3u128, BOB);
+            assert_eq!(OpenInterest::<MockRuntime>::get(BTC), 4u128);
+
+            // Options are counted until their position is released
+            run_to_block(6);
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+            assert_eq!(OpenInterest::<MockRuntime>::get(BTC), 3u128);
+        });
+}
+
+#[test]
+fn test_buy_option_error_time_to_expiry_below_limit() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            set_limits(default_limits());
+
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+
+            sell_option_success_checks(option_id, 2u128, BOB);
+
+            // The option expires 3 seconds after the start of the purchase phase
+            set_limits(RiskLimits {
+                min_time_to_expiry: 4000u64,
+                ..default_limits()
+            });
+            run_to_block(3);
+
+            assert_noop!(
+                TokenizedOptions::buy_option(
+                    Origin::signed(ALICE),
+                    1u128,
+                    option_id,
+                    100000u128 * UNIT
+                ),
+                Error::<MockRuntime>::TimeToExpiryBelowLimit
+            );
+
+            set_limits(default_limits());
+
+            assert_ok!(TokenizedOptions::buy_option(
+                Origin::signed(ALICE),
+                1u128,
+                option_id,
+                100000u128 * UNIT
+            ));
+        });
+}
+
+#[test]
+fn test_execute_strategy_bound_by_risk_limits() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 10 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            set_limits(default_limits());
+
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+
+            sell_option_success_checks(option_id, 3u128, BOB);
+
+            // Options sold by a strategy count towards the open interest
+            assert_noop!(
+                TokenizedOptions::execute_strategy(
+                    Origin::signed(BOB),
+                    legs(vec![(option_id, 3u128, LegSide::Sell)]),
+                    0u128
+                ),
+                Error::<MockRuntime>::OpenInterestLimitExceeded
+            );
+
+            // Options bought by a strategy are bound by the minimum time to expiry
+            set_limits(RiskLimits {
+                min_time_to_expiry: 4000u64,
+                ..default_limits()
+            });
+            run_to_block(3);
+
+            assert_noop!(
+                TokenizedOptions::execute_strategy(
+                    Origin::signed(ALICE),
+                    legs(vec![(option_id, 1u128, LegSide::Buy)]),
+                    100000u128 * UNIT
+                ),
+                Error::<MockRuntime>::TimeToExpiryBelowLimit
+            );
+        });
+}
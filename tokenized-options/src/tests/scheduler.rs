@@ -0,0 +1,171 @@
+use crate::mocks::runtime::{
+    remove_oracle_price, set_oracle_price, Balance, Event, ExtBuilder, MaxSchedulerWeight,
+    MockRuntime, System,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, PendingSettlements},
+    tests::{sell_option::sell_option_success_checks, *},
+};
+
+// ----------------------------------------------------------------------------------------------------
+//		Scheduler Tests
+// ----------------------------------------------------------------------------------------------------
+// Creates BTC call options with the same epoch and the given strike prices, without moving to
+// the next block
+fn create_options(strike_prices: Vec<Balance>) -> Vec<AssetId> {
+    strike_prices
+        .into_iter()
+        .map(|strike_price| {
+            let option_config = OptionsConfigBuilder::default()
+                .base_asset_strike_price(strike_price * UNIT)
+                .build();
+
+            create_option_not_started(option_config)
+        })
+        .collect()
+}
+
+fn count_options_with_status(option_ids: &[AssetId], status: Status) -> usize {
+    option_ids
+        .iter()
+        .filter(|option_id| {
+            OptionIdToOption::<MockRuntime>::get(option_id)
+                .unwrap()
+                .status ==
+                status
+        })
+        .count()
+}
+
+// Options whose settlement was retried and failed in the current block
+fn failed_settlements() -> Vec<AssetId> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            Event::TokenizedOptions(pallet::Event::SettlementFailed { option_id, .. }) =>
+                Some(option_id),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_scheduler_respects_max_weight() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_ids = create_options(vec![50000u128, 55000u128, 60000u128]);
+
+            // Each phase change weighs 1000 in the mock
+            MaxSchedulerWeight::set(&2000);
+
+            run_to_block(2);
+            assert_eq!(count_options_with_status(&option_ids, Status::Deposit), 2);
+            assert_eq!(
+                count_options_with_status(&option_ids, Status::NotStarted),
+                1
+            );
+
+            // The leftover deposit start is applied before the purchase starts
+            run_to_block(3);
+            assert_eq!(count_options_with_status(&option_ids, Status::Deposit), 2);
+            assert_eq!(count_options_with_status(&option_ids, Status::Purchase), 1);
+
+            run_to_block(4);
+            assert_eq!(count_options_with_status(&option_ids, Status::Purchase), 3);
+        });
+}
+
+#[test]
+fn test_scheduler_applies_one_change_per_block_when_over_budget() {
+    ExtBuilder::default()
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_ids = create_options(vec![50000u128, 55000u128]);
+
+            MaxSchedulerWeight::set(&0);
+
+            run_to_block(2);
+            assert_eq!(count_options_with_status(&option_ids, Status::Deposit), 1);
+
+            run_to_block(3);
+            assert_eq!(count_options_with_status(&option_ids, Status::Deposit), 2);
+        });
+}
+
+#[test]
+fn test_pending_settlements_retried_within_budget() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 10 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_ids = create_options(vec![50000u128, 55000u128]);
+
+            run_to_block(2);
+            option_ids
+                .iter()
+                .for_each(|&option_id| sell_option_success_checks(option_id, 5u128, BOB));
+
+            remove_oracle_price(BTC);
+            run_to_block(6);
+            assert_eq!(
+                count_options_with_status(&option_ids, Status::SettlementPending),
+                2
+            );
+
+            // Only one settlement fits in the budget of a block
+            MaxSchedulerWeight::set(&1000);
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(7);
+            assert_eq!(PendingSettlements::<MockRuntime>::iter().count(), 1);
+
+            run_to_block(8);
+            assert_eq!(PendingSettlements::<MockRuntime>::iter().count(), 0);
+            assert_eq!(count_options_with_status(&option_ids, Status::Exercise), 2);
+        });
+}
+
+#[test]
+fn test_pending_settlements_retried_in_turn() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 10 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_ids = create_options(vec![50000u128, 55000u128]);
+
+            run_to_block(2);
+            option_ids
+                .iter()
+                .for_each(|&option_id| sell_option_success_checks(option_id, 5u128, BOB));
+
+            remove_oracle_price(BTC);
+            run_to_block(6);
+
+            // Only one settlement fits in the budget of a block and both keep failing, but the
+            // second one is not starved by the first one
+            MaxSchedulerWeight::set(&1000);
+            run_to_block(7);
+            let first_retried = failed_settlements();
+
+            run_to_block(8);
+            let second_retried = failed_settlements();
+
+            assert_eq!(first_retried.len(), 1);
+            assert_eq!(second_retried.len(), 1);
+            assert_ne!(first_retried, second_retried);
+
+            run_to_block(9);
+            assert_eq!(failed_settlements(), first_retried);
+        });
+}
@@ -0,0 +1,188 @@
+use crate::mocks::runtime::{
+    Assets, Balance, Event, ExtBuilder, MockRuntime, Origin, System, TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet,
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+
+// ----------------------------------------------------------------------------------------------------
+//		Sell Back Tests
+// ----------------------------------------------------------------------------------------------------
+// Bob sells 5 BTC call options and Alice buys 3 of them
+fn sell_and_buy_option() -> AssetId {
+    let option_id = create_option(OptionsConfigBuilder::default().build());
+
+    sell_option_success_checks(option_id, 5u128, BOB);
+    run_to_block(3);
+    buy_option_success_checks(option_id, 3u128, ALICE);
+
+    option_id
+}
+
+fn buyback_proceeds(option_id: AssetId, option_amount: Balance) -> Balance {
+    let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+    TokenizedOptions::calculate_buyback_proceeds(option_id, option_amount, &option).unwrap()
+}
+
+#[test]
+fn test_sell_back_option_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            let premium =
+                TokenizedOptions::calculate_option_premium(option_id, 2u128, &option).unwrap();
+            let proceeds = buyback_proceeds(option_id, 2u128);
+
+            // The spread is kept by sellers
+            assert!(proceeds < premium);
+
+            let initial_usdc_balance = Assets::balance(USDC, &ALICE);
+
+            assert_ok!(TokenizedOptions::sell_back_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id,
+                proceeds
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(pallet::Event::SellBackOption {
+                user: ALICE,
+                option_amount: 2u128,
+                option_id,
+                proceeds,
+            }));
+
+            assert_eq!(Assets::balance(option_id, &ALICE), 1u128);
+            assert_eq!(Assets::total_issuance(option_id), 1u128);
+            assert_eq!(
+                Assets::balance(USDC, &ALICE),
+                initial_usdc_balance + proceeds
+            );
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_buyback_paid, proceeds);
+
+            // Options sold back can be bought again
+            buy_option_success_checks(option_id, 1u128, ALICE);
+        });
+}
+
+#[test]
+fn test_withdraw_collateral_after_sell_back() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+
+            assert_ok!(TokenizedOptions::sell_back_option(
+                Origin::signed(ALICE),
+                3u128,
+                option_id,
+                0u128
+            ));
+
+            run_to_block(6);
+
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(BOB),
+                option_id
+            ));
+
+            // Bob only gets the premium not paid back to Alice
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(
+                Assets::balance(USDC, &BOB),
+                option.total_premium_paid - option.total_buyback_paid
+            );
+            assert_eq!(Assets::balance(BTC, &BOB), 5u128 * UNIT);
+        });
+}
+
+#[test]
+fn test_sell_back_option_error_not_into_purchase_window() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+
+            run_to_block(6);
+
+            assert_noop!(
+                TokenizedOptions::sell_back_option(Origin::signed(ALICE), 1u128, option_id, 0u128),
+                Error::<MockRuntime>::NotIntoPurchaseWindow
+            );
+        });
+}
+
+#[test]
+fn test_sell_back_option_error_user_has_not_enough_option_tokens() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+
+            assert_noop!(
+                TokenizedOptions::sell_back_option(Origin::signed(ALICE), 4u128, option_id, 0u128),
+                Error::<MockRuntime>::UserHasNotEnoughOptionTokens
+            );
+        });
+}
+
+#[test]
+fn test_sell_back_option_error_proceeds_below_min_proceeds() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = sell_and_buy_option();
+            let proceeds = buyback_proceeds(option_id, 1u128);
+
+            assert_noop!(
+                TokenizedOptions::sell_back_option(
+                    Origin::signed(ALICE),
+                    1u128,
+                    option_id,
+                    proceeds + 1
+                ),
+                Error::<MockRuntime>::ProceedsBelowMinProceeds
+            );
+        });
+}
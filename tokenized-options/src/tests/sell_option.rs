@@ -146,6 +146,7 @@ fn test_sell_option_with_initialization_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -184,6 +185,7 @@ fn test_sell_option_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -220,6 +222,7 @@ fn test_sell_option_update_position() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -262,6 +265,7 @@ fn test_sell_option_multiple_users() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
                 option_hash
@@ -323,6 +327,7 @@ fn test_sell_option_error_not_into_deposit_window() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -360,6 +365,7 @@ fn test_sell_option_error_user_has_not_enough_funds() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -397,6 +403,7 @@ fn test_sell_option_error_user_has_not_enough_funds_update_position() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -440,6 +447,7 @@ fn test_sell_option_error_cannot_sell_zero_options() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -477,6 +485,7 @@ fn test_sell_option_error_overflow_asset_amount() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -519,6 +528,7 @@ fn test_sell_option_error_deposits_not_allowed() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -565,6 +575,7 @@ fn test_sell_option_error_deposits_not_allowed_update_position() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
@@ -623,6 +634,7 @@ fn test_sell_option_shares_calculation_with_vault_value_accrual_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
             assert!(OptionHashToOptionId::<MockRuntime>::contains_key(
                 option_hash
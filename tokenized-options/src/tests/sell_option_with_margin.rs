@@ -0,0 +1,326 @@
+use crate::mocks::runtime::{
+    set_oracle_price, Assets, Balance, Event, ExtBuilder, MockRuntime, Origin, System,
+    TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, MarginPositions, Sellers},
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+    types::MarginPosition,
+};
+
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+
+// ----------------------------------------------------------------------------------------------------
+//		Sell Option With Margin Tests
+// ----------------------------------------------------------------------------------------------------
+fn required_margin(option_id: AssetId, option_amount: Balance) -> Balance {
+    let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+
+    TokenizedOptions::calculate_required_margin(option_amount, &option).unwrap()
+}
+
+#[test]
+fn test_sell_option_with_margin_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 250000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+            (CHARLIE, BTC, 1 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+            let margin = required_margin(option_id, 2u128);
+
+            // The margin of an at the money call is lower than the full collateral
+            assert!(margin < 2u128 * UNIT);
+
+            assert_ok!(TokenizedOptions::sell_option_with_margin(
+                Origin::signed(BOB),
+                2u128,
+                option_id,
+                margin
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::SellOptionWithMargin {
+                    user: BOB,
+                    option_amount: 2u128,
+                    option_id,
+                    margin,
+                },
+            ));
+
+            assert_eq!(
+                MarginPositions::<MockRuntime>::get(option_id, BOB),
+                Some(MarginPosition {
+                    option_amount: 2u128,
+                    margin,
+                })
+            );
+            assert_eq!(Assets::balance(BTC, &BOB), 2u128 * UNIT - margin);
+            assert_eq!(
+                Assets::balance(BTC, &TokenizedOptions::account_id(BTC)),
+                margin
+            );
+
+            // Options written with margin are sold together with the ones backed by the vault
+            sell_option_success_checks(option_id, 1u128, CHARLIE);
+            run_to_block(3);
+            buy_option_success_checks(option_id, 3u128, ALICE);
+
+            assert_noop!(
+                TokenizedOptions::buy_option(
+                    Origin::signed(ALICE),
+                    1u128,
+                    option_id,
+                    100000u128 * UNIT
+                ),
+                Error::<MockRuntime>::NotEnoughOptionsForSale
+            );
+        });
+}
+
+#[test]
+fn test_sell_option_with_margin_error_insufficient_margin() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 2 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+            let margin = required_margin(option_id, 2u128);
+
+            assert_noop!(
+                TokenizedOptions::sell_option_with_margin(
+                    Origin::signed(BOB),
+                    2u128,
+                    option_id,
+                    margin - 1
+                ),
+                Error::<MockRuntime>::InsufficientMargin
+            );
+        });
+}
+
+#[test]
+fn test_sell_option_with_margin_error_margin_not_supported() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([(BOB, BTC, 2 * UNIT)]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(
+                OptionsConfigBuilder::default()
+                    .exercise_type(ExerciseType::American)
+                    .build(),
+            );
+
+            assert_noop!(
+                TokenizedOptions::sell_option_with_margin(
+                    Origin::signed(BOB),
+                    1u128,
+                    option_id,
+                    1u128 * UNIT
+                ),
+                Error::<MockRuntime>::MarginNotSupported
+            );
+        });
+}
+
+#[test]
+fn test_liquidate_margin_position_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+            (CHARLIE, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+            // Bob posts more than the margin required
+            let margin = required_margin(option_id, 2u128) * 11 / 10;
+
+            assert_ok!(TokenizedOptions::sell_option_with_margin(
+                Origin::signed(BOB),
+                2u128,
+                option_id,
+                margin
+            ));
+
+            run_to_block(3);
+            buy_option_success_checks(option_id, 2u128, ALICE);
+
+            // The position is healthy at the price it was opened
+            assert_noop!(
+                TokenizedOptions::liquidate_margin_position(
+                    Origin::signed(CHARLIE),
+                    option_id,
+                    BOB
+                ),
+                Error::<MockRuntime>::MarginPositionIsHealthy
+            );
+
+            // The call goes deep in the money, so its delta and the margin required increase
+            set_oracle_price(BTC, 100000u128 * UNIT);
+            assert!(required_margin(option_id, 2u128) > margin);
+
+            assert_ok!(TokenizedOptions::liquidate_margin_position(
+                Origin::signed(CHARLIE),
+                option_id,
+                BOB
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::MarginPositionLiquidated {
+                    user: BOB,
+                    liquidator: CHARLIE,
+                    option_id,
+                    option_amount: 2u128,
+                    margin_seized: margin,
+                },
+            ));
+
+            // Charlie backs the options with the full collateral and seizes Bob's margin
+            assert_eq!(Assets::balance(BTC, &CHARLIE), margin);
+            assert_eq!(Assets::balance(BTC, &BOB), 2u128 * UNIT - margin);
+            assert!(MarginPositions::<MockRuntime>::get(option_id, BOB).is_none());
+            assert_eq!(
+                Sellers::<MockRuntime>::get(option_id, CHARLIE)
+                    .unwrap()
+                    .option_amount,
+                2u128
+            );
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.total_issuance_margined, 0u128);
+            assert_eq!(option.total_issuance_seller, 2u128);
+        });
+}
+
+#[test]
+fn test_withdraw_margin_after_settlement() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 250000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+            (CHARLIE, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+
+            sell_option_success_checks(option_id, 2u128, CHARLIE);
+            assert_ok!(TokenizedOptions::sell_option_with_margin(
+                Origin::signed(BOB),
+                2u128,
+                option_id,
+                2u128 * UNIT
+            ));
+
+            run_to_block(3);
+            buy_option_success_checks(option_id, 4u128, ALICE);
+
+            assert_noop!(
+                TokenizedOptions::withdraw_margin(Origin::signed(BOB), option_id),
+                Error::<MockRuntime>::NotIntoExerciseWindow
+            );
+
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(6);
+
+            // (60000 - 50000) / 60000 BTC for each option, rounded to the nearest
+            let payoff = 166_666_666_667u128;
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.exercise_amount, payoff);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                4u128,
+                option_id
+            ));
+            assert_eq!(Assets::balance(BTC, &ALICE), 4u128 * payoff);
+
+            // Both writers pay for two options and get half of the premium
+            let premium = option.total_premium_paid / 2;
+
+            assert_ok!(TokenizedOptions::withdraw_margin(
+                Origin::signed(BOB),
+                option_id
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(pallet::Event::WithdrawMargin {
+                user: BOB,
+                option_id,
+            }));
+
+            assert_eq!(Assets::balance(BTC, &BOB), 2u128 * UNIT - 2u128 * payoff);
+            assert_eq!(Assets::balance(USDC, &BOB), premium);
+            assert!(MarginPositions::<MockRuntime>::get(option_id, BOB).is_none());
+
+            assert_ok!(TokenizedOptions::withdraw_collateral(
+                Origin::signed(CHARLIE),
+                option_id
+            ));
+            assert_eq!(Assets::balance(USDC, &CHARLIE), premium);
+        });
+}
+
+#[test]
+fn test_margin_shortfall_is_shared_by_buyers() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 2 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+            let margin = required_margin(option_id, 2u128);
+
+            assert_ok!(TokenizedOptions::sell_option_with_margin(
+                Origin::signed(BOB),
+                2u128,
+                option_id,
+                margin
+            ));
+
+            run_to_block(3);
+            buy_option_success_checks(option_id, 2u128, ALICE);
+
+            // The price jumps before the position can be liquidated, and the payoff of the
+            // options is greater than the margin
+            set_oracle_price(BTC, 1000000u128 * UNIT);
+            run_to_block(6);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.exercise_amount, margin / 2);
+            assert_eq!(
+                MarginPositions::<MockRuntime>::get(option_id, BOB)
+                    .unwrap()
+                    .margin,
+                0u128
+            );
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                2u128,
+                option_id
+            ));
+            assert_eq!(Assets::balance(BTC, &ALICE), margin / 2 * 2);
+        });
+}
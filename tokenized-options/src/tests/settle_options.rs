@@ -121,6 +121,7 @@ fn test_settle_options_call_with_initialization_success() {
 				option_config.option_type,
 				option_config.expiring_date,
 				option_config.exercise_type,
+				option_config.settlement_type,
 			);
 
 			// Check creation ended correctly
@@ -143,7 +144,8 @@ fn test_settle_options_call_with_initialization_success() {
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id
+				option_id,
+				Balance::MAX
 			));
 
 			// BTC price moves from 50k to 55k, buyers are in profit
@@ -206,6 +208,7 @@ fn test_settle_options_put_with_initialization_success() {
 				option_config.option_type,
 				option_config.expiring_date,
 				option_config.exercise_type,
+				option_config.settlement_type,
 			);
 
 			// Check creation ended correctly
@@ -228,7 +231,8 @@ fn test_settle_options_put_with_initialization_success() {
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id
+				option_id,
+				Balance::MAX
 			));
 
 			// BTC price moves from 50k to 55k, buyers are in profit
@@ -277,6 +281,7 @@ fn test_settle_options_call_success_multiple_options_sold() {
 				option_config_1.option_type,
 				option_config_1.expiring_date,
 				option_config_1.exercise_type,
+				option_config_1.settlement_type,
 			);
 
 			let option_hash_2 = TokenizedOptions::generate_id(
@@ -287,6 +292,7 @@ fn test_settle_options_call_success_multiple_options_sold() {
 				option_config_2.option_type,
 				option_config_2.expiring_date,
 				option_config_2.exercise_type,
+				option_config_2.settlement_type,
 			);
 
 			let option_id_1 = OptionHashToOptionId::<MockRuntime>::get(option_hash_1).unwrap();
@@ -328,25 +334,29 @@ fn test_settle_options_call_success_multiple_options_sold() {
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id_1
+				option_id_1,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(DAVE),
 				dave_option_amount,
-				option_id_1
+				option_id_1,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id_2
+				option_id_2,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(DAVE),
 				dave_option_amount,
-				option_id_2
+				option_id_2,
+				Balance::MAX
 			));
 
 			// BTC price moves from 50k to 60k, all buyers are in profit
@@ -395,6 +405,7 @@ fn test_settle_options_put_success_multiple_options_sold() {
 				option_config_1.option_type,
 				option_config_1.expiring_date,
 				option_config_1.exercise_type,
+				option_config_1.settlement_type,
 			);
 
 			let option_hash_2 = TokenizedOptions::generate_id(
@@ -405,6 +416,7 @@ fn test_settle_options_put_success_multiple_options_sold() {
 				option_config_2.option_type,
 				option_config_2.expiring_date,
 				option_config_2.exercise_type,
+				option_config_2.settlement_type,
 			);
 
 			let option_id_1 = OptionHashToOptionId::<MockRuntime>::get(option_hash_1).unwrap();
@@ -446,25 +458,29 @@ fn test_settle_options_put_success_multiple_options_sold() {
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id_1
+				option_id_1,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(DAVE),
 				dave_option_amount,
-				option_id_1
+				option_id_1,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id_2
+				option_id_2,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(DAVE),
 				dave_option_amount,
-				option_id_2
+				option_id_2,
+				Balance::MAX
 			));
 
 			// BTC price moves from 50k to 40k, all buyers are in profit
@@ -513,6 +529,7 @@ fn test_settle_options_call_success_multiple_options_not_totally_sold() {
 				option_config_1.option_type,
 				option_config_1.expiring_date,
 				option_config_1.exercise_type,
+				option_config_1.settlement_type,
 			);
 
 			let option_hash_2 = TokenizedOptions::generate_id(
@@ -523,6 +540,7 @@ fn test_settle_options_call_success_multiple_options_not_totally_sold() {
 				option_config_2.option_type,
 				option_config_2.expiring_date,
 				option_config_2.exercise_type,
+				option_config_2.settlement_type,
 			);
 
 			let option_id_1 = OptionHashToOptionId::<MockRuntime>::get(option_hash_1).unwrap();
@@ -564,25 +582,29 @@ fn test_settle_options_call_success_multiple_options_not_totally_sold() {
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id_1
+				option_id_1,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(DAVE),
 				dave_option_amount,
-				option_id_1
+				option_id_1,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id_2
+				option_id_2,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(DAVE),
 				dave_option_amount,
-				option_id_2
+				option_id_2,
+				Balance::MAX
 			));
 
 			// BTC price moves from 50k to 60k, all buyers are in profit
@@ -631,6 +653,7 @@ fn test_settle_options_put_success_multiple_options_not_totally_sold() {
 				option_config_1.option_type,
 				option_config_1.expiring_date,
 				option_config_1.exercise_type,
+				option_config_1.settlement_type,
 			);
 
 			let option_hash_2 = TokenizedOptions::generate_id(
@@ -641,6 +664,7 @@ fn test_settle_options_put_success_multiple_options_not_totally_sold() {
 				option_config_2.option_type,
 				option_config_2.expiring_date,
 				option_config_2.exercise_type,
+				option_config_2.settlement_type,
 			);
 
 			let option_id_1 = OptionHashToOptionId::<MockRuntime>::get(option_hash_1).unwrap();
@@ -682,25 +706,29 @@ fn test_settle_options_put_success_multiple_options_not_totally_sold() {
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id_1
+				option_id_1,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(DAVE),
 				dave_option_amount,
-				option_id_1
+				option_id_1,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id_2
+				option_id_2,
+				Balance::MAX
 			));
 
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(DAVE),
 				dave_option_amount,
-				option_id_2
+				option_id_2,
+				Balance::MAX
 			));
 
 			// BTC price moves from 50k to 60k, all buyers are in profit
@@ -739,6 +767,7 @@ fn test_settle_options_error_overflow() {
 				option_config.option_type,
 				option_config.expiring_date,
 				option_config.exercise_type,
+				option_config.settlement_type,
 			);
 
 			// Check creation ended correctly
@@ -765,7 +794,8 @@ fn test_settle_options_error_overflow() {
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id
+				option_id,
+				Balance::MAX
 			));
 
 			// BTC price moves from as far as it can
@@ -804,6 +834,7 @@ fn test_settle_options_error_overflow_with_value_accrual() {
 				option_config.option_type,
 				option_config.expiring_date,
 				option_config.exercise_type,
+				option_config.settlement_type,
 			);
 
 			// Check creation ended correctly
@@ -830,7 +861,8 @@ fn test_settle_options_error_overflow_with_value_accrual() {
 			assert_ok!(TokenizedOptions::buy_option(
 				Origin::signed(CHARLIE),
 				charlie_option_amount,
-				option_id
+				option_id,
+				Balance::MAX
 			));
 
 			// Add as most BTC as I can to the vault to simulate vault value accrual
@@ -0,0 +1,251 @@
+use crate::mocks::runtime::{
+    remove_oracle_price, set_oracle_price, Event, ExtBuilder, MockRuntime, Origin, System,
+    TokenizedOptions,
+};
+
+use crate::mocks::{accounts::*, assets::*};
+
+use crate::{
+    pallet::{self, PendingSettlements},
+    tests::{buy_option::buy_option_success_checks, sell_option::sell_option_success_checks, *},
+};
+
+use frame_support::{assert_noop, assert_ok, error::BadOrigin};
+
+// ----------------------------------------------------------------------------------------------------
+//		Settlement Tests
+// ----------------------------------------------------------------------------------------------------
+// Bob sells 5 BTC call options, Alice buys 3 of them and the oracle is down at expiry
+fn option_with_failed_settlement() -> AssetId {
+    let option_id = create_option(OptionsConfigBuilder::default().build());
+
+    sell_option_success_checks(option_id, 5u128, BOB);
+    run_to_block(3);
+    buy_option_success_checks(option_id, 3u128, ALICE);
+
+    remove_oracle_price(BTC);
+    run_to_block(6);
+
+    option_id
+}
+
+fn option_status(option_id: AssetId) -> Status {
+    OptionIdToOption::<MockRuntime>::get(option_id)
+        .unwrap()
+        .status
+}
+
+#[test]
+fn test_failed_settlement_is_pending() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = option_with_failed_settlement();
+
+            assert_eq!(option_status(option_id), Status::SettlementPending);
+            assert_eq!(
+                PendingSettlements::<MockRuntime>::get(option_id),
+                Some(5000u64)
+            );
+            System::assert_has_event(Event::TokenizedOptions(pallet::Event::SettlementFailed {
+                option_id,
+                error: Error::<MockRuntime>::AssetPriceNotFound.into(),
+            }));
+
+            // Buyers and sellers wait for the settlement
+            assert_noop!(
+                TokenizedOptions::exercise_option(Origin::signed(ALICE), 1u128, option_id),
+                Error::<MockRuntime>::NotIntoExerciseWindow
+            );
+            assert_noop!(
+                TokenizedOptions::withdraw_collateral(Origin::signed(BOB), option_id),
+                Error::<MockRuntime>::NotIntoExerciseWindow
+            );
+
+            // Settlement is retried and fails again
+            run_to_block(7);
+            assert_eq!(option_status(option_id), Status::SettlementPending);
+
+            let failures = System::events()
+                .into_iter()
+                .filter(|record| {
+                    matches!(
+                        record.event,
+                        Event::TokenizedOptions(pallet::Event::SettlementFailed { .. })
+                    )
+                })
+                .count();
+            assert_eq!(failures, 2);
+        });
+}
+
+#[test]
+fn test_pending_settlement_retried_when_oracle_is_back() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = option_with_failed_settlement();
+
+            set_oracle_price(BTC, 60000u128 * UNIT);
+            run_to_block(7);
+
+            assert_eq!(option_status(option_id), Status::Exercise);
+            assert_eq!(PendingSettlements::<MockRuntime>::get(option_id), None);
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.base_asset_spot_price, 60000u128 * UNIT);
+            assert_eq!(option.epoch.end, 9000u64);
+
+            assert_ok!(TokenizedOptions::exercise_option(
+                Origin::signed(ALICE),
+                3u128,
+                option_id
+            ));
+        });
+}
+
+#[test]
+fn test_end_postponed_when_settled_after_it() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = option_with_failed_settlement();
+
+            // The option can't end before being settled
+            run_to_block(10);
+            assert_eq!(option_status(option_id), Status::SettlementPending);
+
+            set_oracle_price(BTC, 50000u128 * UNIT);
+            run_to_block(11);
+
+            // Buyers get the whole exercise phase
+            assert_eq!(option_status(option_id), Status::Exercise);
+            assert_eq!(
+                OptionIdToOption::<MockRuntime>::get(option_id)
+                    .unwrap()
+                    .epoch
+                    .end,
+                14000u64
+            );
+
+            run_to_block(14);
+            assert_eq!(option_status(option_id), Status::Exercise);
+
+            run_to_block(15);
+            assert_eq!(option_status(option_id), Status::End);
+        });
+}
+
+#[test]
+fn test_settle_option_with_price_success() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = option_with_failed_settlement();
+
+            // The grace period lasts until 8000
+            run_to_block(7);
+            assert_noop!(
+                TokenizedOptions::settle_option_with_price(
+                    Origin::signed(ADMIN),
+                    option_id,
+                    60000u128 * UNIT
+                ),
+                Error::<MockRuntime>::SettlementGracePeriodNotOver
+            );
+
+            run_to_block(8);
+            assert_ok!(TokenizedOptions::settle_option_with_price(
+                Origin::signed(ADMIN),
+                option_id,
+                60000u128 * UNIT
+            ));
+
+            System::assert_last_event(Event::TokenizedOptions(
+                pallet::Event::OptionExerciseStart { option_id },
+            ));
+
+            let option = OptionIdToOption::<MockRuntime>::get(option_id).unwrap();
+            assert_eq!(option.status, Status::Exercise);
+            assert_eq!(option.base_asset_spot_price, 60000u128 * UNIT);
+            // (60000 - 50000) / 60000 BTC, rounded to the nearest
+            assert_eq!(option.exercise_amount, 166_666_666_667u128);
+            assert_eq!(PendingSettlements::<MockRuntime>::get(option_id), None);
+        });
+}
+
+#[test]
+fn test_settle_option_with_price_error_not_pending_settlement() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = create_option(OptionsConfigBuilder::default().build());
+
+            run_to_block(6);
+
+            assert_noop!(
+                TokenizedOptions::settle_option_with_price(
+                    Origin::signed(ADMIN),
+                    option_id,
+                    60000u128 * UNIT
+                ),
+                Error::<MockRuntime>::OptionNotPendingSettlement
+            );
+        });
+}
+
+#[test]
+fn test_settle_option_with_price_error_not_protocol_origin() {
+    ExtBuilder::default()
+        .initialize_balances(Vec::from([
+            (ALICE, USDC, 150000 * UNIT),
+            (BOB, BTC, 5 * UNIT),
+        ]))
+        .build()
+        .initialize_oracle_prices()
+        .initialize_all_vaults()
+        .execute_with(|| {
+            let option_id = option_with_failed_settlement();
+
+            run_to_block(8);
+
+            assert_noop!(
+                TokenizedOptions::settle_option_with_price(
+                    Origin::signed(ALICE),
+                    option_id,
+                    60000u128 * UNIT
+                ),
+                BadOrigin
+            );
+        });
+}
@@ -163,6 +163,7 @@ fn test_withdraw_collateral_call_with_initialization_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -185,7 +186,8 @@ fn test_withdraw_collateral_call_with_initialization_success() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -252,6 +254,7 @@ fn test_withdraw_collateral_put_with_initialization_success() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -274,7 +277,8 @@ fn test_withdraw_collateral_put_with_initialization_success() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -324,6 +328,7 @@ fn test_withdraw_collateral_call_multiple_times() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -354,13 +359,15 @@ fn test_withdraw_collateral_call_multiple_times() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -416,6 +423,7 @@ fn test_withdraw_collateral_put_multiple_times() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -446,13 +454,15 @@ fn test_withdraw_collateral_put_multiple_times() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -522,6 +532,7 @@ fn test_withdraw_collateral_error_not_into_exercise_window() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -552,13 +563,15 @@ fn test_withdraw_collateral_error_not_into_exercise_window() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -610,6 +623,7 @@ fn test_withdraw_collateral_error_user_does_not_have_position() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -640,13 +654,15 @@ fn test_withdraw_collateral_error_user_does_not_have_position() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -695,6 +711,7 @@ fn test_withdraw_collateral_call_out_of_money_multiple_times() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -725,13 +742,15 @@ fn test_withdraw_collateral_call_out_of_money_multiple_times() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -787,6 +806,7 @@ fn test_withdraw_collateral_put_out_of_money_multiple_times() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -817,13 +837,15 @@ fn test_withdraw_collateral_put_out_of_money_multiple_times() {
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(CHARLIE),
                 charlie_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             assert_ok!(TokenizedOptions::buy_option(
                 Origin::signed(DAVE),
                 dave_option_amount,
-                option_id
+                option_id,
+                Balance::MAX
             ));
 
             // BTC price moves from 50k to 55k, buyers are in profit
@@ -869,6 +891,7 @@ fn test_withdraw_collateral_dust_issue() {
                 option_config.option_type,
                 option_config.expiring_date,
                 option_config.exercise_type,
+                option_config.settlement_type,
             );
 
             // Check creation ended correctly
@@ -909,7 +932,8 @@ fn test_withdraw_collateral_dust_issue() {
                 assert_ok!(TokenizedOptions::buy_option(
                     Origin::signed(account_id_from_u64(i)),
                     buyer_option_number,
-                    option_id
+                    option_id,
+                    Balance::MAX
                 ));
             }
 
@@ -2,7 +2,10 @@ use crate::Config;
 use frame_support::pallet_prelude::*;
 
 use sp_core::H256;
-use sp_runtime::traits::{BlakeTwo256, Hash, Zero};
+use sp_runtime::{
+    traits::{BlakeTwo256, Hash, Zero},
+    Percent, Permill,
+};
 use sp_std::fmt::Debug;
 use traits::tokenized_options::*;
 
@@ -19,6 +22,7 @@ pub struct OptionToken<T: Config> {
     pub option_type: OptionType,
     pub expiring_date: T::Moment,
     pub exercise_type: ExerciseType,
+    pub settlement_type: SettlementType,
 
     // Helper attributes
     pub epoch: Epoch<T::Moment>,
@@ -31,6 +35,16 @@ pub struct OptionToken<T: Config> {
     pub base_asset_spot_price: T::Balance,
     pub total_issuance_buyer: T::Balance,
     pub total_shares_amount: T::Balance,
+    // Options exercised before expiry (American options only) and the vault shares withdrawn
+    // to pay them
+    pub total_exercised_early: T::Balance,
+    pub total_shares_exercised_early: T::Balance,
+    // Asset delivered by buyers exercising physically settled options, owed to sellers
+    pub total_delivered_amount: T::Balance,
+    // Premium paid back to buyers selling their options back to the protocol
+    pub total_buyback_paid: T::Balance,
+    // Options written by sellers posting margin instead of full collateral
+    pub total_issuance_margined: T::Balance,
 }
 
 impl<T: Config> OptionToken<T> {
@@ -43,6 +57,7 @@ impl<T: Config> OptionToken<T> {
             self.option_type,
             self.expiring_date,
             self.exercise_type,
+            self.settlement_type,
         ))
     }
 }
@@ -53,6 +68,9 @@ impl<T: Config> OptionToken<T> {
 pub struct SellerPosition<T: Config> {
     pub option_amount: T::Balance,
     pub shares_amount: T::Balance,
+    // Whether the collateral of the options not bought has been released after the purchase
+    // window, so it is not released twice
+    pub unsold_collateral_released: bool,
 }
 
 impl<T: Config> Default for SellerPosition<T> {
@@ -60,6 +78,103 @@ impl<T: Config> Default for SellerPosition<T> {
         SellerPosition {
             option_amount: T::Balance::zero(),
             shares_amount: T::Balance::zero(),
+            unsold_collateral_released: false,
         }
     }
 }
+
+/// Identifier of an option series.
+pub type SeriesId = u64;
+
+/// Rule used to pick the strike price of each option of a series from the oracle price of the
+/// base asset when the option is created.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub enum StrikeSelection {
+    /// Strike at the oracle price.
+    AtTheMoney,
+    /// Strike the given percentage above the oracle price.
+    AboveSpot(Percent),
+    /// Strike the given percentage below the oracle price.
+    BelowSpot(Percent),
+}
+
+/// Template of a recurring option series. A new option is created from the template every
+/// `cadence`, when the previous option of the series ends.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct OptionSeries<AssetId, Balance, Moment> {
+    pub base_asset_id: AssetId,
+    pub quote_asset_id: AssetId,
+    pub quote_asset_strike_price: Balance,
+    pub option_type: OptionType,
+    pub exercise_type: ExerciseType,
+    pub settlement_type: SettlementType,
+    pub strike_selection: StrikeSelection,
+    pub base_asset_amount_per_option: Balance,
+    pub quote_asset_amount_per_option: Balance,
+    // Start of the deposit phase of the latest option of the series
+    pub start: Moment,
+    // Time between the start of two consecutive options of the series
+    pub cadence: Moment,
+    // Durations of the deposit, purchase and exercise phases of each option
+    pub deposit_duration: Moment,
+    pub purchase_duration: Moment,
+    pub exercise_duration: Moment,
+}
+
+/// Side of a leg of a strategy.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub enum LegSide {
+    Buy,
+    Sell,
+}
+
+/// Option bought or sold as part of a multi-leg strategy.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct StrategyLeg<OptionId, Balance> {
+    pub option_id: OptionId,
+    pub option_amount: Balance,
+    pub side: LegSide,
+}
+
+/// Options bought in a strategy and held by the protocol to cover options sold in the same
+/// strategy, for which the seller only posted the maximum loss as collateral.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct SpreadPledge<OptionId, Balance> {
+    pub protection_option_id: OptionId,
+    pub option_amount: Balance,
+}
+
+/// Options written by a seller that posted margin instead of the full collateral. The margin is
+/// held by the protocol and the payoff owed to buyers is taken from it at settlement.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct MarginPosition<Balance> {
+    pub option_amount: Balance,
+    pub margin: Balance,
+}
+
+/// Fees taken by the protocol on the options traded, paid to the
+/// [`FeeAccount`](crate::Config::FeeAccount).
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct FeeConfig<Balance> {
+    // Share of the premium paid by buyers, taken from the premium collected by sellers
+    pub premium_fee: Permill,
+    // Share of the payout of cash settled options exercised in the money
+    pub exercise_fee: Permill,
+    // Maximum fee taken for each option by each fee, in quote asset
+    pub max_fee_per_option: Balance,
+}
+
+/// Limits on the options created and traded for an underlying asset, set by the protocol origin.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct RiskLimits<Balance, Moment> {
+    // Maximum amount of options written across all the options on the asset, until the
+    // positions writing them are released
+    pub max_open_interest: Balance,
+    // Minimum and maximum time between the start of the deposit phase and the end of an option
+    pub min_epoch_length: Moment,
+    pub max_epoch_length: Moment,
+    // Maximum distance of the strike price from the oracle price at creation
+    pub max_strike_deviation: Permill,
+    // Minimum time to the expiring date when an option is created or bought
+    pub min_time_to_expiry: Moment,
+}
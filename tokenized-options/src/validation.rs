@@ -1,13 +1,15 @@
 use crate::pallet::{
-    AssetToVault, BalanceOf, Config, OptionConfigOf, OptionHashToOptionId, OracleOf, Pallet,
-    VaultConfigOf,
+    AssetRiskLimits, AssetToVault, BalanceOf, Config, MomentOf, OpenInterest, OptionConfigOf,
+    OptionHashToOptionId, OptionIdOf, OptionIdToOption, OptionSeriesOf, OracleOf, Pallet,
+    StrategyLegsOf, VaultConfigOf,
 };
 
 use composable_support::validation::Validate;
 
 use composable_traits::oracle::Oracle;
 use core::marker::PhantomData;
-use sp_runtime::traits::Zero;
+use frame_support::traits::Time;
+use sp_runtime::traits::{CheckedAdd, CheckedSub, Saturating, Zero};
 
 // -----------------------------------------------------------------------------------------------
 //		ValidateVaultDoesNotExist
@@ -51,6 +53,34 @@ impl<T: Config> Validate<VaultConfigOf<T>, ValidateAssetIsSupported<T>>
     }
 }
 
+// -----------------------------------------------------------------------------------------------
+//		ValidateVaultStrategies
+// -----------------------------------------------------------------------------------------------
+/// Validate that the vault specified by `input` only allocates funds to the strategy account of
+/// its asset, since strategies can withdraw the collateral allocated to them.
+#[derive(Clone, Copy)]
+pub struct ValidateVaultStrategies<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Config> Validate<VaultConfigOf<T>, ValidateVaultStrategies<T>>
+    for ValidateVaultStrategies<T>
+{
+    fn validate(vault_config: VaultConfigOf<T>) -> Result<VaultConfigOf<T>, &'static str> {
+        let strategy_account = Pallet::<T>::strategy_account_id(vault_config.asset_id);
+
+        if vault_config
+            .strategies
+            .keys()
+            .any(|account| *account != strategy_account)
+        {
+            return Err("ValidateVaultStrategies")
+        }
+
+        Ok(vault_config)
+    }
+}
+
 // // -----------------------------------------------------------------------------------------------
 // //		ValidateOptionDoesNotExist
 // // -----------------------------------------------------------------------------------------------
@@ -72,6 +102,7 @@ impl<T: Config> Validate<OptionConfigOf<T>, ValidateOptionDoesNotExist<T>>
             input.option_type,
             input.expiring_date,
             input.exercise_type,
+            input.settlement_type,
         );
         if OptionHashToOptionId::<T>::contains_key(hash) {
             return Err("ValidateOptionDoesNotExist")
@@ -141,3 +172,204 @@ impl<T: Config> Validate<OptionConfigOf<T>, ValidateOptionAttributes<T>>
         Ok(input)
     }
 }
+
+// -----------------------------------------------------------------------------------------------
+//		ValidateOptionRiskLimits
+// -----------------------------------------------------------------------------------------------
+/// Validate that the epoch length, the strike price and the time to expiry of the option
+/// specified by `input` are within the risk limits of its base asset, if any.
+#[derive(Clone, Copy)]
+pub struct ValidateOptionRiskLimits<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Config> Validate<OptionConfigOf<T>, ValidateOptionRiskLimits<T>>
+    for ValidateOptionRiskLimits<T>
+{
+    fn validate(input: OptionConfigOf<T>) -> Result<OptionConfigOf<T>, &'static str> {
+        let limits = match AssetRiskLimits::<T>::get(input.base_asset_id) {
+            Some(limits) => limits,
+            None => return Ok(input),
+        };
+
+        let epoch_length = input
+            .epoch
+            .end
+            .checked_sub(&input.epoch.deposit)
+            .ok_or("ValidateOptionRiskLimits")?;
+
+        if epoch_length < limits.min_epoch_length || epoch_length > limits.max_epoch_length {
+            return Err("ValidateOptionRiskLimits")
+        }
+
+        if input.expiring_date < T::Time::now().saturating_add(limits.min_time_to_expiry) {
+            return Err("ValidateOptionRiskLimits")
+        }
+
+        let spot_price =
+            Pallet::<T>::get_price(input.base_asset_id).map_err(|_| "ValidateOptionRiskLimits")?;
+        let max_deviation = limits.max_strike_deviation.mul_floor(spot_price);
+
+        if input.base_asset_strike_price < spot_price.saturating_sub(max_deviation) ||
+            input.base_asset_strike_price > spot_price.saturating_add(max_deviation)
+        {
+            return Err("ValidateOptionRiskLimits")
+        }
+
+        Ok(input)
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+//		ValidateOpenInterest
+// -----------------------------------------------------------------------------------------------
+/// Validate that writing the amount of the option specified by `input` does not exceed the
+/// maximum open interest of its base asset, if any, which bounds the options written across all
+/// the options on the asset. Options that do not exist are left to the caller to report.
+#[derive(Clone, Copy)]
+pub struct ValidateOpenInterest<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Config> Validate<(OptionIdOf<T>, BalanceOf<T>), ValidateOpenInterest<T>>
+    for ValidateOpenInterest<T>
+{
+    fn validate(
+        input: (OptionIdOf<T>, BalanceOf<T>),
+    ) -> Result<(OptionIdOf<T>, BalanceOf<T>), &'static str> {
+        let (option_id, option_amount) = input;
+
+        let option = match OptionIdToOption::<T>::get(option_id) {
+            Some(option) => option,
+            None => return Ok(input),
+        };
+
+        if let Some(limits) = AssetRiskLimits::<T>::get(option.base_asset_id) {
+            let open_interest = OpenInterest::<T>::get(option.base_asset_id)
+                .checked_add(&option_amount)
+                .ok_or("ValidateOpenInterest")?;
+
+            if open_interest > limits.max_open_interest {
+                return Err("ValidateOpenInterest")
+            }
+        }
+
+        Ok(input)
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+//		ValidateTimeToExpiry
+// -----------------------------------------------------------------------------------------------
+/// Validate that the option specified by `input` expires no sooner than the minimum time to
+/// expiry of its base asset, if any. Options that do not exist are left to the caller to report.
+#[derive(Clone, Copy)]
+pub struct ValidateTimeToExpiry<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Config> Validate<OptionIdOf<T>, ValidateTimeToExpiry<T>> for ValidateTimeToExpiry<T> {
+    fn validate(input: OptionIdOf<T>) -> Result<OptionIdOf<T>, &'static str> {
+        let option = match OptionIdToOption::<T>::get(input) {
+            Some(option) => option,
+            None => return Ok(input),
+        };
+
+        if let Some(limits) = AssetRiskLimits::<T>::get(option.base_asset_id) {
+            if option.expiring_date < T::Time::now().saturating_add(limits.min_time_to_expiry) {
+                return Err("ValidateTimeToExpiry")
+            }
+        }
+
+        Ok(input)
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+//		ValidateSeriesAttributes
+// -----------------------------------------------------------------------------------------------
+/// Validate that the options of the series specified by `input` can be created and that each of
+/// them ends before the next one starts.
+#[derive(Clone, Copy)]
+pub struct ValidateSeriesAttributes<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Config> Validate<OptionSeriesOf<T>, ValidateSeriesAttributes<T>>
+    for ValidateSeriesAttributes<T>
+{
+    fn validate(input: OptionSeriesOf<T>) -> Result<OptionSeriesOf<T>, &'static str> {
+        if input.base_asset_id == input.quote_asset_id {
+            return Err("ValidateSeriesAttributes")
+        }
+
+        if !(AssetToVault::<T>::contains_key(input.base_asset_id) &&
+            AssetToVault::<T>::contains_key(input.quote_asset_id))
+        {
+            return Err("ValidateSeriesAttributes")
+        }
+
+        if input.deposit_duration == MomentOf::<T>::zero() ||
+            input.purchase_duration == MomentOf::<T>::zero() ||
+            input.exercise_duration == MomentOf::<T>::zero()
+        {
+            return Err("ValidateSeriesAttributes")
+        }
+
+        let epoch_duration = input
+            .deposit_duration
+            .checked_add(&input.purchase_duration)
+            .and_then(|duration| duration.checked_add(&input.exercise_duration))
+            .ok_or("ValidateSeriesAttributes")?;
+
+        if input.cadence < epoch_duration {
+            return Err("ValidateSeriesAttributes")
+        }
+
+        Ok(input)
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+//		ValidateStrategyLegs
+// -----------------------------------------------------------------------------------------------
+/// Validate that the legs of the strategy specified by `input` trade different existing options
+/// sharing the same expiring date, each for a nonzero amount.
+#[derive(Clone, Copy)]
+pub struct ValidateStrategyLegs<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Config> Validate<StrategyLegsOf<T>, ValidateStrategyLegs<T>> for ValidateStrategyLegs<T> {
+    fn validate(input: StrategyLegsOf<T>) -> Result<StrategyLegsOf<T>, &'static str> {
+        let mut expiring_date = None;
+
+        for (index, leg) in input.iter().enumerate() {
+            if leg.option_amount == BalanceOf::<T>::zero() {
+                return Err("ValidateStrategyLegs")
+            }
+
+            if input
+                .iter()
+                .skip(index + 1)
+                .any(|other| other.option_id == leg.option_id)
+            {
+                return Err("ValidateStrategyLegs")
+            }
+
+            let option = OptionIdToOption::<T>::get(leg.option_id).ok_or("ValidateStrategyLegs")?;
+
+            match expiring_date {
+                None => expiring_date = Some(option.expiring_date),
+                Some(date) if date != option.expiring_date => return Err("ValidateStrategyLegs"),
+                Some(_) => {},
+            }
+        }
+
+        if expiring_date.is_none() {
+            return Err("ValidateStrategyLegs")
+        }
+
+        Ok(input)
+    }
+}
@@ -18,6 +18,29 @@ pub trait WeightInfo {
     fn buy_option() -> Weight;
     fn exercise_option() -> Weight;
     fn withdraw_collateral() -> Weight;
+    fn sell_back_option() -> Weight;
+    fn transfer_option() -> Weight;
+    fn settle_option_with_price() -> Weight;
+    fn create_option_series() -> Weight;
+    fn stop_option_series() -> Weight;
+    fn update_collateral_rollover() -> Weight;
+    fn option_deposit_start(r: u32) -> Weight;
+    fn option_purchase_start() -> Weight;
+    fn option_exercise_start(m: u32) -> Weight;
+    fn option_end(r: u32) -> Weight;
+    fn retry_pending_settlement(m: u32) -> Weight;
+    fn clean_up_option(s: u32, h: u32) -> Weight;
+    fn execute_strategy(l: u32) -> Weight;
+    fn sell_option_with_margin() -> Weight;
+    fn add_margin() -> Weight;
+    fn liquidate_margin_position() -> Weight;
+    fn withdraw_margin() -> Weight;
+    fn release_unsold_collateral() -> Weight;
+    fn update_protocol_fees() -> Weight;
+    fn update_risk_limits() -> Weight;
+    fn rebalance_collateral_strategy() -> Weight;
+    fn exercise_for() -> Weight;
+    fn update_auto_exercise() -> Weight;
 }
 
 /// Weights for pallet_tokenized_options using the Substrate node and recommended hardware.
@@ -103,6 +126,281 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(15 as Weight))
             .saturating_add(T::DbWeight::get().writes(7 as Weight))
     }
+
+    // TODO: depends on pricing pallet weights (TBD)
+    fn sell_back_option() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:0)
+    // Storage: Tokens Accounts (r:2 w:2)
+    // Storage: TokenizedOptions Buyers (r:2 w:2)
+    fn transfer_option() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: TokenizedOptions PendingSettlements (r:1 w:1)
+    // Storage: TokenizedOptions AssetToVault (r:1 w:0)
+    // Storage: Tokens TotalIssuance (r:1 w:0)
+    // Storage: Vault withdraw() (r:8 w:4)
+    // Storage: TokenizedOptions Scheduler (r:0 w:1)
+    fn settle_option_with_price() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+
+    // Storage: TokenizedOptions SeriesCount (r:1 w:1)
+    // Storage: TokenizedOptions AssetToVault (r:2 w:0)
+    // Storage: Oracle Prices (r:1 w:0)
+    // Storage: TokenizedOptions create_option() (r:4 w:8)
+    // Storage: TokenizedOptions OptionIdToSeries (r:0 w:1)
+    // Storage: TokenizedOptions Series (r:0 w:1)
+    fn create_option_series() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+
+    // Storage: TokenizedOptions Series (r:1 w:1)
+    fn stop_option_series() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    // Storage: TokenizedOptions Series (r:1 w:0)
+    // Storage: TokenizedOptions RolloverSellers (r:1 w:1)
+    // Storage: TokenizedOptions RolloverSellersCount (r:1 w:1)
+    // Storage: TokenizedOptions PendingRollovers (r:0 w:1)
+    fn update_collateral_rollover() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+
+    // Storage: TokenizedOptions Scheduler (r:1 w:1)
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: TokenizedOptions OptionIdToSeries (r:1 w:0)
+    // Storage: TokenizedOptions PendingRollovers (r:1 w:1)
+    // Storage: TokenizedOptions AssetRiskLimits (r:1 w:0)
+    // Storage: TokenizedOptions OpenInterest (r:1 w:1)
+    // Storage: TokenizedOptions AssetToVault (r:1 w:0)
+    // Storage: Vault Vaults (r:2 w:0)
+    // Storage: Tokens TotalIssuance (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: TokenizedOptions Sellers (r:1 w:1)
+    // Storage: Vault deposit() (r:9 w:5)
+    fn option_deposit_start(r: u32) -> Weight {
+        (24_718_000 as Weight)
+            .saturating_add((97_436_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((18 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(r as Weight)))
+    }
+
+    // Storage: TokenizedOptions Scheduler (r:1 w:1)
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    fn option_purchase_start() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+
+    // Storage: TokenizedOptions Scheduler (r:1 w:1)
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: Oracle Prices (r:1 w:0)
+    // Storage: Assets TotalIssuance (r:1 w:0)
+    // Storage: TokenizedOptions AssetToVault (r:1 w:0)
+    // Storage: Assets Account (r:1 w:0)
+    // Storage: Vault withdraw() (r:8 w:4)
+    // Storage: TokenizedOptions PendingSettlements (r:0 w:1)
+    // Storage: TokenizedOptions MarginPositions (r:1 w:1)
+    fn option_exercise_start(m: u32) -> Weight {
+        (63_904_000 as Weight)
+            .saturating_add((6_583_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+    }
+
+    // Storage: TokenizedOptions Scheduler (r:1 w:1)
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: TokenizedOptions OptionIdToSeries (r:1 w:1)
+    // Storage: TokenizedOptions Series (r:1 w:1)
+    // Storage: Oracle Prices (r:1 w:0)
+    // Storage: TokenizedOptions create_option() (r:4 w:8)
+    // Storage: TokenizedOptions RolloverSellers (r:1 w:0)
+    // Storage: TokenizedOptions Sellers (r:1 w:1)
+    // Storage: TokenizedOptions SpreadPledges (r:1 w:0)
+    // Storage: TokenizedOptions OpenInterest (r:1 w:1)
+    // Storage: TokenizedOptions AssetToVault (r:1 w:0)
+    // Storage: Vault Vaults (r:1 w:0)
+    // Storage: Assets Account (r:1 w:0)
+    // Storage: Vault withdraw() (r:8 w:4)
+    // Storage: System Account (r:2 w:2)
+    // Storage: TokenizedOptions PendingRollovers (r:1 w:1)
+    fn option_end(r: u32) -> Weight {
+        (88_352_000 as Weight)
+            .saturating_add((84_217_000 as Weight).saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().reads((18 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(r as Weight)))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: Oracle Prices (r:1 w:0)
+    // Storage: Assets TotalIssuance (r:1 w:0)
+    // Storage: TokenizedOptions AssetToVault (r:1 w:0)
+    // Storage: Assets Account (r:1 w:0)
+    // Storage: Vault withdraw() (r:8 w:4)
+    // Storage: TokenizedOptions PendingSettlements (r:0 w:1)
+    // Storage: TokenizedOptions Scheduler (r:0 w:1)
+    // Storage: TokenizedOptions MarginPositions (r:1 w:1)
+    fn retry_pending_settlement(m: u32) -> Weight {
+        (61_275_000 as Weight)
+            .saturating_add((6_590_000 as Weight).saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(13 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: Assets Account (r:4 w:4)
+    // Storage: TokenizedOptions Sellers (r:2 w:1)
+    // Storage: TokenizedOptions SpreadPledges (r:1 w:1)
+    // Storage: TokenizedOptions AssetToVault (r:1 w:0)
+    // Storage: Vault Vaults (r:1 w:0)
+    // Storage: Vault withdraw() (r:8 w:4)
+    // Storage: System Account (r:2 w:2)
+    // Storage: TokenizedOptions OpenInterest (r:1 w:1)
+    // Storage: TokenizedOptions MarginPositions (r:2 w:1)
+    // Storage: Assets Asset (r:0 w:1)
+    // Storage: TokenizedOptions Buyers (r:0 w:1)
+    // Storage: Assets TotalIssuance (r:1 w:0)
+    // Storage: TokenizedOptions OptionHashToOptionId (r:0 w:1)
+    // Storage: TokenizedOptions OptionIdToSeries (r:0 w:1)
+    // Storage: TokenizedOptions PendingSettlements (r:0 w:1)
+    // Storage: TokenizedOptions RetiredOptionAssets (r:0 w:1)
+    fn clean_up_option(s: u32, h: u32) -> Weight {
+        (52_614_000 as Weight)
+            .saturating_add((118_942_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add((21_307_000 as Weight).saturating_mul(h as Weight))
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().reads((21 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(h as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes((14 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(h as Weight)))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:2 w:1) per leg
+    // Storage: TokenizedOptions buy_option() or sell_option() (r:15 w:8) per leg
+    // Storage: TokenizedOptions SpreadPledges (r:1 w:1) per leg
+    // Storage: Assets Account (r:2 w:2) per leg
+    fn execute_strategy(l: u32) -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads((20 as Weight).saturating_mul(l as Weight)))
+            .saturating_add(T::DbWeight::get().writes((12 as Weight).saturating_mul(l as Weight)))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: TokenizedOptions MarginPositions (r:11 w:1)
+    // Storage: OptionsPricing volatility and greeks (r:4 w:0)
+    // Storage: Oracle Prices (r:1 w:0)
+    // Storage: Assets Account (r:2 w:2)
+    fn sell_option_with_margin() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(19 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:0)
+    // Storage: TokenizedOptions MarginPositions (r:1 w:1)
+    // Storage: Assets Account (r:2 w:2)
+    fn add_margin() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: TokenizedOptions MarginPositions (r:1 w:1)
+    // Storage: OptionsPricing volatility and greeks (r:4 w:0)
+    // Storage: Oracle Prices (r:1 w:0)
+    // Storage: TokenizedOptions sell_option() (r:12 w:7)
+    // Storage: Assets Account (r:2 w:2)
+    fn liquidate_margin_position() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(21 as Weight))
+            .saturating_add(T::DbWeight::get().writes(11 as Weight))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:0)
+    // Storage: TokenizedOptions MarginPositions (r:1 w:1)
+    // Storage: Assets Account (r:4 w:4)
+    fn withdraw_margin() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:0)
+    // Storage: TokenizedOptions Sellers (r:1 w:1)
+    // Storage: TokenizedOptions AssetToVault (r:1 w:0)
+    // Storage: Assets TotalIssuance (r:1 w:0)
+    // Storage: Vault Vaults (r:1 w:0)
+    // Storage: Assets Accounts (r:4 w:4)
+    fn release_unsold_collateral() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+
+    // Storage: TokenizedOptions ProtocolFees (r:0 w:1)
+    fn update_protocol_fees() -> Weight {
+        (1000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    // Storage: TokenizedOptions AssetRiskLimits (r:0 w:1)
+    fn update_risk_limits() -> Weight {
+        (1000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+
+    // Storage: TokenizedOptions AssetToVault (r:1 w:0)
+    // Storage: Vault Vaults (r:1 w:0)
+    // Storage: Vault CapitalStructure (r:1 w:1)
+    // Storage: Tokens Accounts (r:3 w:3)
+    fn rebalance_collateral_strategy() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+
+    // Storage: TokenizedOptions OptionIdToOption (r:1 w:1)
+    // Storage: TokenizedOptions AutoExerciseOptOut (r:1 w:0)
+    // Storage: TokenizedOptions ProtocolFees (r:1 w:0)
+    // Storage: TokenizedOptions Buyers (r:1 w:1)
+    // Storage: Assets Account (r:4 w:4)
+    fn exercise_for() -> Weight {
+        (1000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+
+    // Storage: TokenizedOptions AutoExerciseOptOut (r:0 w:1)
+    fn update_auto_exercise() -> Weight {
+        (1000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -134,4 +432,98 @@ impl WeightInfo for () {
     fn withdraw_collateral() -> Weight {
         WEIGHT as Weight
     }
+
+    fn sell_back_option() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn transfer_option() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn settle_option_with_price() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn create_option_series() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn stop_option_series() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn update_collateral_rollover() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn option_deposit_start(_r: u32) -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn option_purchase_start() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn option_exercise_start(_m: u32) -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn option_end(_r: u32) -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn retry_pending_settlement(_m: u32) -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn clean_up_option(s: u32, h: u32) -> Weight {
+        (WEIGHT as Weight)
+            .saturating_add((s as Weight).saturating_mul(100))
+            .saturating_add((h as Weight).saturating_mul(100))
+    }
+
+    fn execute_strategy(l: u32) -> Weight {
+        (WEIGHT as Weight).saturating_add((l as Weight).saturating_mul(100))
+    }
+
+    fn sell_option_with_margin() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn add_margin() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn liquidate_margin_position() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn withdraw_margin() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn release_unsold_collateral() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn update_protocol_fees() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn update_risk_limits() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn rebalance_collateral_strategy() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn exercise_for() -> Weight {
+        WEIGHT as Weight
+    }
+
+    fn update_auto_exercise() -> Weight {
+        WEIGHT as Weight
+    }
 }
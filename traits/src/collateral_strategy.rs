@@ -0,0 +1,35 @@
+use frame_support::pallet_prelude::*;
+
+/// Strategy earning yield on the collateral allocated to it by the option vaults.
+///
+/// Funds are moved in and out of the strategy through an account owned by the caller. The
+/// principal deposited can be withdrawn at any time, while the yield earned on it is paid out
+/// separately when harvested.
+pub trait CollateralStrategy {
+    type AccountId;
+    type AssetId;
+    type Balance;
+
+    /// Deposits `amount` of `asset_id` from `from` into the strategy.
+    fn deposit(
+        asset_id: Self::AssetId,
+        from: &Self::AccountId,
+        amount: Self::Balance,
+    ) -> Result<(), DispatchError>;
+
+    /// Withdraws `amount` of the principal of `asset_id` deposited into the strategy to `to`.
+    fn withdraw(
+        asset_id: Self::AssetId,
+        to: &Self::AccountId,
+        amount: Self::Balance,
+    ) -> Result<(), DispatchError>;
+
+    /// Pays to `to` the yield earned on `asset_id` since the last harvest, returning its amount.
+    fn harvest(
+        asset_id: Self::AssetId,
+        to: &Self::AccountId,
+    ) -> Result<Self::Balance, DispatchError>;
+
+    /// Returns the principal of `asset_id` deposited into the strategy.
+    fn balance(asset_id: Self::AssetId) -> Self::Balance;
+}
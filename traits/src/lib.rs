@@ -13,6 +13,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod clearing_house;
+pub mod collateral_strategy;
 pub mod options_pricing;
 pub mod swap_bytes;
 pub mod tokenized_options;
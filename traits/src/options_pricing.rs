@@ -1,5 +1,7 @@
 use crate::tokenized_options::OptionType;
 use frame_support::pallet_prelude::*;
+use sp_runtime::FixedI128;
+
 #[allow(unused_variables)]
 #[derive(Clone, Encode, Decode, PartialEq, TypeInfo, MaxEncodedLen, Debug)]
 pub struct BlackScholesParams<AssetId, Balance, Moment> {
@@ -12,6 +14,26 @@ pub struct BlackScholesParams<AssetId, Balance, Moment> {
     pub total_premium_paid: Balance,
 }
 
+/// Sensitivities of the price of an option.
+///
+/// The greeks are additive, so the exposure of a position of `n` options is given by the greeks
+/// multiplied by `n`. Greeks expressed in price units are given as a fraction of the spot price of
+/// the underlying asset, which makes them comparable across options on the same asset regardless
+/// of their strike prices.
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct Greeks {
+    /// Change of the option price for a unit change of the spot price.
+    pub delta: FixedI128,
+    /// Change of delta for a 1% change of the spot price.
+    pub gamma: FixedI128,
+    /// Change of the option price for a 1% change of the volatility.
+    pub vega: FixedI128,
+    /// Change of the option price per year.
+    pub theta: FixedI128,
+    /// Change of the option price for a 1% change of the interest rate.
+    pub rho: FixedI128,
+}
+
 pub trait OptionsPricing {
     type AssetId;
     type Balance;
@@ -26,5 +48,10 @@ pub trait OptionsPricing {
     fn calculate_option_greeks(
         option_id: Self::OptionId,
         params: BlackScholesParams<Self::AssetId, Self::Balance, Self::Moment>,
-    ) -> Result<(), DispatchError>;
+    ) -> Result<Greeks, DispatchError>;
+
+    /// Computes the greeks of an option without storing them.
+    fn get_option_greeks(
+        params: BlackScholesParams<Self::AssetId, Self::Balance, Self::Moment>,
+    ) -> Result<Greeks, DispatchError>;
 }
@@ -17,6 +17,14 @@ pub enum ExerciseType {
     American,
 }
 
+/// Indicates how the option is settled on exercise: `Cash` pays the in-the-money amount in the
+/// collateral asset, `Physical` swaps the underlying for the strike.
+#[derive(Copy, Clone, Encode, Decode, Debug, PartialEq, TypeInfo, MaxEncodedLen)]
+pub enum SettlementType {
+    Cash,
+    Physical,
+}
+
 /// Indicates the type of phases of the option.
 #[derive(Clone, Copy, Encode, Decode, Debug, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
 pub enum Status {
@@ -25,6 +33,9 @@ pub enum Status {
     Purchase,
     Exercise,
     End,
+    /// The option could not be settled when its exercise phase should have started. Exercises
+    /// and withdrawals are blocked until settlement succeeds.
+    SettlementPending,
 }
 
 // ----------------------------------------------------------------------------------------------------
@@ -41,6 +52,16 @@ pub struct Epoch<Moment> {
     pub end: Moment,
 }
 
+/// Position of a buyer in an option: the options held, the premium paid for them and the amount of
+/// options exercised. The average premium paid for each option held is
+/// `premium_paid / option_amount`.
+#[derive(Clone, Copy, Default, Encode, Decode, Debug, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+pub struct BuyerPosition<Balance> {
+    pub option_amount: Balance,
+    pub premium_paid: Balance,
+    pub exercised_amount: Balance,
+}
+
 /// Configuration for creating an option
 #[derive(Clone, Encode, Decode, PartialEq, TypeInfo, MaxEncodedLen, Debug)]
 pub struct OptionConfig<AssetId, Balance, Moment> {
@@ -51,6 +72,7 @@ pub struct OptionConfig<AssetId, Balance, Moment> {
     pub option_type: OptionType,
     pub expiring_date: Moment,
     pub exercise_type: ExerciseType,
+    pub settlement_type: SettlementType,
     pub epoch: Epoch<Moment>,
     pub status: Status,
     pub base_asset_amount_per_option: Balance,
@@ -95,6 +117,7 @@ pub trait TokenizedOptions {
         from: &Self::AccountId,
         option_amount: Self::Balance,
         option_id: Self::OptionId,
+        max_premium: Self::Balance,
     ) -> Result<(), DispatchError>;
 
     fn exercise_option(
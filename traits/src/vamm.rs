@@ -1,7 +1,7 @@
 //! # Virtual Automated Market Maker
 //!
 //! Common traits and data structures for vamm implementation.
-use frame_support::pallet_prelude::*;
+use frame_support::{pallet_prelude::*, sp_std::vec::Vec};
 use sp_arithmetic::traits::Unsigned;
 use sp_core::U256;
 use sp_runtime::{traits::AtLeast32BitUnsigned, FixedPointNumber};
@@ -31,6 +31,10 @@ pub trait Vamm {
     /// Configuration for swap assets in a vamm.
     type SwapConfig;
 
+    /// Configuration for swapping assets in a vamm, receiving an exact amount
+    /// of the desired asset.
+    type SwapExactOutputConfig;
+
     /// Configuration for moving prices in a vamm.
     type MovePriceConfig;
 
@@ -51,6 +55,32 @@ pub trait Vamm {
         config: &Self::SwapConfig,
     ) -> Result<SwapOutput<Self::Balance>, DispatchError>;
 
+    /// Performs several swaps of assets atomically, possibly spanning
+    /// multiple vamms, returning the output of each swap in the same order
+    /// as the given configs. If any swap fails, none of them takes effect.
+    fn swap_batch(
+        configs: &[Self::SwapConfig],
+    ) -> Result<Vec<SwapOutput<Self::Balance>>, DispatchError>;
+
+    /// Performs swap of assets, receiving an exact amount of the desired asset
+    /// and paying whatever amount of the other asset is needed for it.
+    fn swap_exact_output(
+        config: &Self::SwapExactOutputConfig,
+    ) -> Result<SwapOutput<Self::Balance>, DispatchError>;
+
+    /// Performs exact output swap simulation.
+    ///
+    /// This is kept apart from [`swap_simulation`](Self::swap_simulation)
+    /// rather than folded into [`SwapConfig`](Self::SwapConfig) because the
+    /// two configs bound opposite sides of the trade: `SwapConfig` fixes the
+    /// amount swapped and limits the amount received, while
+    /// `SwapExactOutputConfig` fixes the amount received and limits the amount
+    /// paid. Callers sizing positions in base asset (e.g. a long of exactly
+    /// `n` base) should simulate them through this function.
+    fn swap_exact_output_simulation(
+        config: &Self::SwapExactOutputConfig,
+    ) -> Result<SwapOutput<Self::Balance>, DispatchError>;
+
     /// Sets the amount of base and quote asset reserves, modifying the
     /// invariant of the desired vamm.
     fn move_price(config: &Self::MovePriceConfig) -> Result<U256, DispatchError>;
@@ -67,6 +97,32 @@ pub trait Vamm {
         asset_type: AssetType,
     ) -> Result<Self::Decimal, DispatchError>;
 
+    /// Computes the arithmetic time weighted average price of the base asset
+    /// over the last `window` seconds, from the vamm's price observations.
+    fn get_twap_over(
+        vamm_id: Self::VammId,
+        window: Self::Moment,
+    ) -> Result<Self::Decimal, DispatchError>;
+
+    /// Computes the relative change of the base asset price, as a fraction of
+    /// the current price, which the given swap would cause.
+    fn get_price_impact(config: &Self::SwapConfig) -> Result<Self::Decimal, DispatchError>;
+
+    /// Computes the amount of base asset which must be bought from and sold
+    /// to the vamm to move its base asset price up and down, respectively, by
+    /// `price_change` (e.g. `0.01` for 1%).
+    fn get_depth(
+        vamm_id: Self::VammId,
+        price_change: Self::Decimal,
+    ) -> Result<Depth<Self::Balance>, DispatchError>;
+
+    /// Computes the asset reserves for which the base asset price of the vamm
+    /// would be `target_price`, keeping its invariant.
+    fn get_reserves_for_price(
+        vamm_id: Self::VammId,
+        target_price: Self::Decimal,
+    ) -> Result<Reserves<Self::Balance>, DispatchError>;
+
     /// Updates the twap for the base asset, returning it if successful.
     fn update_twap(
         vamm_id: Self::VammId,
@@ -78,6 +134,27 @@ pub trait Vamm {
 
     /// Schedules a closing time for the vamm, after which it will be considered closed.
     fn close(vamm_id: Self::VammId, closing_time: Self::Moment) -> Result<(), DispatchError>;
+
+    /// Returns the weight of a single [`swap`](Self::swap), for callers to
+    /// account for it in the weight of their own dispatchables.
+    fn swap_weight() -> Weight;
+
+    /// Returns the weight of a [`swap_batch`](Self::swap_batch) of `swaps`
+    /// swaps, for callers to account for it in the weight of their own
+    /// dispatchables.
+    fn swap_batch_weight(swaps: u32) -> Weight;
+
+    /// Sets (or removes, if `config` is `None`) the circuit breaker of the
+    /// vamm, which rejects swaps moving its price abnormally.
+    ///
+    /// Only state-changing swaps are checked against the circuit breaker;
+    /// simulations and queries are not. Since a rejected swap is reverted,
+    /// it doesn't halt the vamm: halts are only triggered by the price check
+    /// done at the start of each block.
+    fn set_circuit_breaker(
+        vamm_id: Self::VammId,
+        config: Option<CircuitBreakerConfig<Self::Decimal, Self::Moment>>,
+    ) -> Result<(), DispatchError>;
 }
 
 /// Specify a common encapsulation layer for the [`create`](Vamm::create) function.
@@ -104,6 +181,19 @@ pub struct SwapConfig<VammId, Balance> {
     pub output_amount_limit: Option<Balance>,
 }
 
+/// Specify a common encapsulation layer for the exact output swap functions.
+#[derive(Clone, Debug)]
+pub struct SwapExactOutputConfig<VammId, Balance> {
+    /// The identifier of the vamm to swap against.
+    pub vamm_id: VammId,
+    /// The asset the caller wants to receive (i.e., remove from the vamm).
+    pub asset: AssetType,
+    /// The exact amount of `asset` the caller wants to receive.
+    pub output_amount: Balance,
+    /// The maximum amount of the other asset the caller is willing to pay.
+    pub input_amount_limit: Option<Balance>,
+}
+
 /// Distinguish between asset types present in the vamm.
 #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -142,5 +232,42 @@ pub struct SwapOutput<Balance> {
     pub negative: bool,
 }
 
+/// Specify the return type for [`Vamm::get_depth`].
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Depth<Balance> {
+    /// The amount of base asset which must be removed from the vamm to move
+    /// its base asset price up by the desired amount.
+    pub up: Balance,
+    /// The amount of base asset which must be added to the vamm to move its
+    /// base asset price down by the desired amount.
+    pub down: Balance,
+}
+
+/// Specify the return type for [`Vamm::get_reserves_for_price`].
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Reserves<Balance> {
+    /// The amount of base asset in the reserves.
+    pub base_asset_reserves: Balance,
+    /// The amount of quote asset in the reserves.
+    pub quote_asset_reserves: Balance,
+}
+
+/// Specify a common encapsulation layer for the
+/// [`set_circuit_breaker`](Vamm::set_circuit_breaker) function.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CircuitBreakerConfig<Decimal, Moment> {
+    /// The maximum relative deviation (e.g. `0.1` for 10%) allowed between
+    /// the base asset price after a swap and both the price at the start of
+    /// the block and the base asset twap.
+    pub max_price_deviation: Decimal,
+    /// For how long the vamm halts once an abnormal price is detected at the
+    /// start of a block. If `None`, the vamm never halts. Swaps rejected for
+    /// deviating too much are reverted, and thus never trigger a halt.
+    pub halt_period: Option<Moment>,
+}
+
 /// The minimum allowed value for [`twap_period`](VammConfig::twap_period).
 pub const MINIMUM_TWAP_PERIOD: u32 = 10;
@@ -0,0 +1,43 @@
+//! # Vamm Runtime API
+//!
+//! Runtime API for querying the market depth and price impact of the virtual
+//! automated market makers managed by the Vamm Pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_runtime::DispatchError;
+use traits::vamm::{AssetType, Depth, Direction, Reserves};
+
+sp_api::decl_runtime_apis! {
+    /// Queries exposed by the Vamm Pallet to clients.
+    pub trait VammRuntimeApi<VammId, Balance, Decimal>
+    where
+        VammId: Codec,
+        Balance: Codec,
+        Decimal: Codec,
+    {
+        /// Returns the relative change of the base asset price, as a fraction
+        /// of the current price, which swapping `input_amount` of `asset` in
+        /// the given `direction` would cause.
+        fn get_price_impact(
+            vamm_id: VammId,
+            asset: AssetType,
+            input_amount: Balance,
+            direction: Direction,
+        ) -> Result<Decimal, DispatchError>;
+
+        /// Returns the amount of base asset which must be bought from and
+        /// sold to the vamm to move its base asset price up and down,
+        /// respectively, by `price_change`.
+        fn get_depth(vamm_id: VammId, price_change: Decimal) -> Result<Depth<Balance>, DispatchError>;
+
+        /// Returns the asset reserves for which the base asset price of the
+        /// vamm would be `target_price`.
+        fn get_reserves_for_price(
+            vamm_id: VammId,
+            target_price: Decimal,
+        ) -> Result<Reserves<Balance>, DispatchError>;
+    }
+}
@@ -37,6 +37,25 @@ impl<T: Config> Pallet<T> {
             Error::<T>::VammIsClosed
         );
 
+        Self::sanity_check_swap_amount(config, vamm_state)
+    }
+
+    /// Checks if the following properties hold for the amount of a single
+    /// swap:
+    ///
+    /// * There is a sufficient amount of assets in the reserves to give to the
+    /// caller if the swap is a [`Remove`](Direction::Remove) operation.
+    /// * The total amount of assets in the reserve will not overflow if the
+    /// swap is a [`Add`](Direction::Add) operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::InsufficientFundsForTrade`]
+    /// * [`Error::<T>::TradeExtrapolatesMaximumSupportedAmount`]
+    pub fn sanity_check_swap_amount(
+        config: &SwapConfigOf<T>,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<(), DispatchError> {
         match config.direction {
             // If we intend to remove some asset amount from vamm, we must
             // have sufficient funds for it.
@@ -81,16 +100,54 @@ impl<T: Config> Pallet<T> {
     /// ../../traits/vamm/struct.SwapConfig.html#structfield.output_amount_limit).
     /// * Base assets was not completely drained.
     /// * Quote assets was not completely drained.
+    /// * Base asset price doesn't trip the circuit breaker of the vamm.
     ///
     /// # Errors
     /// * [`Error::<T>::SwappedAmountLessThanMinimumLimit`]
     /// * [`Error::<T>::SwappedAmountMoreThanMaximumLimit`]
     /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
     /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::PriceDeviationExceedsLimit`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
     pub fn sanity_check_after_swap(
         vamm_state: &VammStateOf<T>,
         config: &SwapConfigOf<T>,
         amount_swapped: &SwapOutput<T::Balance>,
+    ) -> Result<(), DispatchError> {
+        Self::sanity_check_swap_limit(config, amount_swapped)?;
+        Self::sanity_check_state_after_swap(config.vamm_id, vamm_state)
+    }
+
+    /// Checks if the properties of the vamm state hold after performing one
+    /// or several swaps:
+    ///
+    /// * Base assets was not completely drained.
+    /// * Quote assets was not completely drained.
+    /// * Base asset price doesn't trip the circuit breaker of the vamm.
+    ///
+    /// # Errors
+    /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::PriceDeviationExceedsLimit`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn sanity_check_state_after_swap(
+        vamm_id: T::VammId,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<(), DispatchError> {
+        Self::sanity_check_reserves(vamm_state)?;
+        Self::sanity_check_price_deviation(vamm_id, vamm_state)
+    }
+
+    /// Checks if the swapped amount respects the limit specified in
+    /// [`SwapConfig::output_amount_limit`](
+    /// ../../traits/vamm/struct.SwapConfig.html#structfield.output_amount_limit).
+    ///
+    /// # Errors
+    /// * [`Error::<T>::SwappedAmountLessThanMinimumLimit`]
+    /// * [`Error::<T>::SwappedAmountMoreThanMaximumLimit`]
+    pub fn sanity_check_swap_limit(
+        config: &SwapConfigOf<T>,
+        amount_swapped: &SwapOutput<T::Balance>,
     ) -> Result<(), DispatchError> {
         // Ensure swapped amount is valid.
         if let Some(limit) = config.output_amount_limit {
@@ -106,6 +163,19 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        // TODO(Cardosaum): Write one more `ensure!` block regarding
+        // amount_swapped negative or positive?
+
+        Ok(())
+    }
+
+    /// Checks if neither the base nor the quote asset reserves were
+    /// completely drained.
+    ///
+    /// # Errors
+    /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
+    pub fn sanity_check_reserves(vamm_state: &VammStateOf<T>) -> Result<(), DispatchError> {
         // Ensure both quote and base assets weren't completely drained from vamm.
         ensure!(
             !vamm_state.base_asset_reserves.is_zero(),
@@ -116,9 +186,6 @@ impl<T: Config> Pallet<T> {
             Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained
         );
 
-        // TODO(Cardosaum): Write one more `ensure!` block regarding
-        // amount_swapped negative or positive?
-
         Ok(())
     }
 
@@ -0,0 +1,167 @@
+use crate::{
+    BlockStartPrices, CircuitBreakers, Config, Error, Event, HaltedUntil, Pallet, VammMap,
+    VammStateOf,
+};
+use frame_support::{pallet_prelude::*, weights::Weight};
+use sp_runtime::{
+    traits::{CheckedDiv, CheckedSub, Saturating, Zero},
+    ArithmeticError,
+};
+use traits::vamm::AssetType;
+
+impl<T: Config> Pallet<T> {
+    /// Checks if the base asset price of `vamm_state` deviates from the
+    /// price at the start of the block or from the base asset twap by more
+    /// than allowed by the circuit breaker of the vamm, if any.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::PriceDeviationExceedsLimit`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn sanity_check_price_deviation(
+        vamm_id: T::VammId,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<(), DispatchError> {
+        let circuit_breaker = match CircuitBreakers::<T>::get(vamm_id) {
+            Some(circuit_breaker) => circuit_breaker,
+            None => return Ok(()),
+        };
+
+        let price = Self::do_get_price(vamm_state, AssetType::Base)?;
+        let twap = vamm_state.base_asset_twap.get_twap();
+
+        for reference_price in BlockStartPrices::<T>::get(vamm_id)
+            .into_iter()
+            .chain(Some(twap))
+        {
+            ensure!(
+                Self::price_deviation(price, reference_price)? <=
+                    circuit_breaker.max_price_deviation,
+                Error::<T>::PriceDeviationExceedsLimit
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns a boolean informing if the vamm is halted by its circuit
+    /// breaker or not.
+    pub fn is_vamm_halted(vamm_id: &T::VammId, now: &Option<T::Moment>) -> bool {
+        match HaltedUntil::<T>::get(vamm_id) {
+            Some(halted_until) => Self::now(now) < halted_until,
+            None => false,
+        }
+    }
+
+    /// Records the price at the start of the block for every vamm with a
+    /// circuit breaker, halting the vamms whose price deviates from the one
+    /// at the start of the previous block or from the base asset twap by
+    /// more than allowed.
+    ///
+    /// Swaps which would trip the circuit breaker are reverted without
+    /// leaving any trace, so they are never taken into account here. The
+    /// halt is thus driven by price moves that didn't go through a swap and
+    /// by the drift between the price and the base asset twap.
+    ///
+    /// Once the halt period of a vamm is over, its base asset twap is reset
+    /// to the current price, otherwise the twap would keep rejecting every
+    /// swap until it caught up with the new price.
+    ///
+    /// Returns the weight consumed.
+    pub fn check_circuit_breakers(now: &Option<T::Moment>) -> Weight {
+        let now = Self::now(now);
+        // The last read finds the end of the circuit breakers map.
+        let mut reads: u64 = 1;
+        let mut writes: u64 = 0;
+
+        for (vamm_id, circuit_breaker) in CircuitBreakers::<T>::iter() {
+            // Circuit breaker and vamm state.
+            reads = reads.saturating_add(2);
+
+            let mut vamm_state = match VammMap::<T>::get(vamm_id) {
+                Some(vamm_state) if !Self::is_vamm_closed(&vamm_state, &Some(now)) => vamm_state,
+                _ => continue,
+            };
+            let price = match Self::do_get_price(&vamm_state, AssetType::Base) {
+                Ok(price) => price,
+                Err(_) => continue,
+            };
+
+            // Halt end and block start price.
+            reads = reads.saturating_add(2);
+            let mut halted = false;
+
+            if let Some(halted_until) = HaltedUntil::<T>::get(vamm_id) {
+                if now >= halted_until {
+                    HaltedUntil::<T>::remove(vamm_id);
+                    vamm_state.base_asset_twap.set_twap(price);
+                    vamm_state.base_asset_twap.set_timestamp(now);
+                    VammMap::<T>::insert(vamm_id, vamm_state);
+                    writes = writes.saturating_add(2);
+                } else {
+                    halted = true;
+                }
+            }
+
+            let block_start_price = BlockStartPrices::<T>::get(vamm_id);
+
+            if let (Some(halt_period), false) = (circuit_breaker.halt_period, halted) {
+                let twap = vamm_state.base_asset_twap.get_twap();
+                let abnormal =
+                    block_start_price
+                        .into_iter()
+                        .chain(Some(twap))
+                        .any(|reference_price| {
+                            Self::price_deviation(price, reference_price)
+                                .map_or(true, |deviation| {
+                                    deviation > circuit_breaker.max_price_deviation
+                                })
+                        });
+
+                if abnormal {
+                    let halted_until = now.saturating_add(halt_period);
+                    HaltedUntil::<T>::insert(vamm_id, halted_until);
+                    writes = writes.saturating_add(1);
+                    Self::deposit_event(Event::<T>::CircuitBreakerTriggered {
+                        vamm_id,
+                        price,
+                        halted_until,
+                    });
+                }
+            }
+
+            if block_start_price != Some(price) {
+                BlockStartPrices::<T>::insert(vamm_id, price);
+                writes = writes.saturating_add(1);
+            }
+        }
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    /// Returns the deviation of `price` relative to `reference_price`, i.e.
+    /// `|price - reference_price| / reference_price`.
+    ///
+    /// # Errors
+    ///
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    fn price_deviation(
+        price: T::Decimal,
+        reference_price: T::Decimal,
+    ) -> Result<T::Decimal, DispatchError> {
+        if reference_price.is_zero() {
+            return Ok(Zero::zero())
+        }
+
+        let difference = if price >= reference_price {
+            price.checked_sub(&reference_price)
+        } else {
+            reference_price.checked_sub(&price)
+        }
+        .ok_or(ArithmeticError::Underflow)?;
+
+        Ok(difference
+            .checked_div(&reference_price)
+            .ok_or(ArithmeticError::DivisionByZero)?)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Config, Error, Pallet};
+use crate::{Config, Error, Pallet, VammStateOf};
 use core::cmp::Ordering;
 use frame_support::pallet_prelude::*;
 use helpers::numbers::IntoU256;
@@ -8,6 +8,7 @@ use sp_runtime::{
     ArithmeticError::{DivisionByZero, Overflow},
     FixedPointNumber,
 };
+use traits::vamm::Reserves;
 
 impl<T: Config> Pallet<T> {
     /// Returns the vamm invariant (aka. `K`), given `base` and `quote` asset
@@ -39,6 +40,64 @@ impl<T: Config> Pallet<T> {
         Ok(invariant)
     }
 
+    /// Returns the asset reserves for which the base asset price of the vamm
+    /// would be `target_price`, keeping its invariant.
+    ///
+    /// As `price = quote * peg / base` and `k = base * quote`, the new
+    /// reserves can be derived as:
+    ///
+    /// - `base = sqrt(k * peg / price)`
+    /// - `quote = k / base`
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::TargetPriceIsZero`]
+    /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::TradeExtrapolatesMaximumSupportedAmount`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn compute_reserves_for_price(
+        vamm_state: &VammStateOf<T>,
+        target_price: T::Decimal,
+    ) -> Result<Reserves<T::Balance>, DispatchError> {
+        ensure!(!target_price.is_zero(), Error::<T>::TargetPriceIsZero);
+
+        let base_squared = vamm_state
+            .invariant
+            .checked_mul(vamm_state.peg_multiplier.into_u256())
+            .ok_or(Overflow)?
+            .checked_mul(T::Decimal::DIV.into_u256())
+            .ok_or(Overflow)?
+            .checked_div(target_price.into_inner().into_u256())
+            .ok_or(DivisionByZero)?;
+        let base_u256 = base_squared.integer_sqrt();
+        ensure!(
+            !base_u256.is_zero(),
+            Error::<T>::BaseAssetReservesWouldBeCompletelyDrained
+        );
+
+        let quote_u256 = vamm_state
+            .invariant
+            .checked_div(base_u256)
+            .ok_or(DivisionByZero)?;
+        ensure!(
+            !quote_u256.is_zero(),
+            Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained
+        );
+
+        let base_u128: u128 = base_u256
+            .try_into()
+            .map_err(|_| Error::<T>::TradeExtrapolatesMaximumSupportedAmount)?;
+        let quote_u128: u128 = quote_u256
+            .try_into()
+            .map_err(|_| Error::<T>::TradeExtrapolatesMaximumSupportedAmount)?;
+
+        Ok(Reserves {
+            base_asset_reserves: base_u128.into(),
+            quote_asset_reserves: quote_u128.into(),
+        })
+    }
+
     /// Calculates the exponential moving average (EMA) following the formula:
     ///
     /// - `ema = ((x1 * w1) + (x2 * w2)) / (w1 + w2)`
@@ -85,6 +144,23 @@ impl<T: Config> Pallet<T> {
         Ok(T::Decimal::from_inner(twap_u128.into()))
     }
 
+    /// Returns `numerator / denominator`, rounding the result up instead of
+    /// truncating it.
+    ///
+    /// # Errors
+    ///
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn div_rounding_up(numerator: U256, denominator: U256) -> Result<U256, DispatchError> {
+        let quotient = numerator.checked_div(denominator).ok_or(DivisionByZero)?;
+        let remainder = numerator.checked_rem(denominator).ok_or(DivisionByZero)?;
+
+        if remainder.is_zero() {
+            Ok(quotient)
+        } else {
+            Ok(quotient.checked_add(U256::one()).ok_or(Overflow)?)
+        }
+    }
+
     /// Returns the absolute difference between balances `a` and `b`.
     pub fn abs_balance_diff(a: T::Balance, b: T::Balance) -> T::Balance {
         match a.cmp(&b) {
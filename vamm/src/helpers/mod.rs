@@ -1,5 +1,7 @@
 mod checks;
+mod circuit_breaker;
 mod math;
+mod observations;
 mod price;
 mod swap;
 mod twap;
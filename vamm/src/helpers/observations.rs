@@ -0,0 +1,214 @@
+use crate::{
+    types::Observation, Config, Error, ObservationIndex, ObservationOf, Observations, Pallet,
+    VammStateOf,
+};
+use frame_support::pallet_prelude::*;
+use helpers::numbers::IntoU256;
+use sp_core::U256;
+use sp_runtime::{
+    traits::{CheckedSub, Zero},
+    ArithmeticError::{DivisionByZero, Overflow, Underflow},
+    FixedPointNumber,
+};
+use sp_std::vec::Vec;
+use traits::vamm::AssetType;
+
+impl<T: Config> Pallet<T> {
+    /// Records a new price observation for the desired vamm in its ring
+    /// buffer, accumulating the price that prevailed since the last
+    /// observation.
+    ///
+    /// This function must be called *before* the reserves of the vamm change,
+    /// as the price in `vamm_state` is assumed to be the one that prevailed
+    /// since the most recent observation. At most one observation is recorded
+    /// per timestamp; subsequent calls with the same timestamp are no-ops.
+    ///
+    /// Once the ring buffer holds [`MaxObservations`](Config::MaxObservations)
+    /// entries, the oldest one is overwritten.
+    ///
+    /// # Errors
+    ///
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn write_observation(
+        vamm_id: T::VammId,
+        vamm_state: &VammStateOf<T>,
+        now: &Option<T::Moment>,
+    ) -> Result<(), DispatchError> {
+        let now = Self::now(now);
+        let index = ObservationIndex::<T>::get(vamm_id);
+
+        Observations::<T>::try_mutate(vamm_id, |observations| -> DispatchResult {
+            let observation = match observations.get(index as usize) {
+                Some(last) => {
+                    // Only one observation per timestamp is recorded.
+                    if last.timestamp >= now {
+                        return Ok(())
+                    }
+                    Self::transform_observation(last, vamm_state, now)?
+                },
+                None => ObservationOf::<T> {
+                    timestamp: now,
+                    base_price_cumulative: U256::zero(),
+                },
+            };
+
+            let next_index = if observations.is_empty() {
+                0
+            } else {
+                index.saturating_add(1) % T::MaxObservations::get().max(1)
+            };
+
+            match observations.get_mut(next_index as usize) {
+                Some(slot) => *slot = observation,
+                None => observations
+                    .try_push(observation)
+                    .map_err(|_| Error::<T>::ObservationsBufferIsFull)?,
+            }
+            ObservationIndex::<T>::insert(vamm_id, next_index);
+
+            Ok(())
+        })
+    }
+
+    /// Computes the arithmetic time weighted average price of the base asset
+    /// over the last `window` seconds, using the observations ring buffer.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::TwapWindowIsZero`]
+    /// * [`Error::<T>::InsufficientObservationHistory`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn compute_twap_over(
+        vamm_id: T::VammId,
+        vamm_state: &VammStateOf<T>,
+        window: T::Moment,
+        now: &Option<T::Moment>,
+    ) -> Result<T::Decimal, DispatchError> {
+        ensure!(!window.is_zero(), Error::<T>::TwapWindowIsZero);
+
+        let now = Self::now(now);
+        let then = now
+            .checked_sub(&window)
+            .ok_or(Error::<T>::InsufficientObservationHistory)?;
+
+        let cumulative_now = Self::observe(vamm_id, vamm_state, now)?;
+        let cumulative_then = Self::observe(vamm_id, vamm_state, then)?;
+
+        let twap_u256 = cumulative_now
+            .checked_sub(cumulative_then)
+            .ok_or(Underflow)?
+            .checked_div(U256::from(window.into()))
+            .ok_or(DivisionByZero)?;
+        let twap_u128: u128 = twap_u256.try_into()?;
+
+        Ok(T::Decimal::from_inner(twap_u128.into()))
+    }
+
+    /// Returns the cumulative base asset price of the desired vamm at time
+    /// `target`, interpolating between the stored observations if needed.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::InsufficientObservationHistory`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn observe(
+        vamm_id: T::VammId,
+        vamm_state: &VammStateOf<T>,
+        target: T::Moment,
+    ) -> Result<U256, DispatchError> {
+        let observations = Self::chronological_observations(vamm_id);
+
+        let latest = observations
+            .last()
+            .ok_or(Error::<T>::InsufficientObservationHistory)?;
+
+        // Target is after the latest observation, so we extrapolate using the
+        // current price, which prevailed since then.
+        if target >= latest.timestamp {
+            return Ok(Self::transform_observation(latest, vamm_state, target)?.base_price_cumulative)
+        }
+
+        // Target is before the oldest observation we have.
+        let oldest = observations
+            .first()
+            .ok_or(Error::<T>::InsufficientObservationHistory)?;
+        ensure!(
+            target >= oldest.timestamp,
+            Error::<T>::InsufficientObservationHistory
+        );
+
+        // Find the pair of observations surrounding the target. The price is
+        // constant between two consecutive observations, so linear
+        // interpolation of the cumulative price is exact.
+        let (before, after) = observations
+            .windows(2)
+            .find_map(|pair| match pair {
+                [before, after] if before.timestamp <= target && target < after.timestamp =>
+                    Some((*before, *after)),
+                _ => None,
+            })
+            .ok_or(Error::<T>::InsufficientObservationHistory)?;
+
+        let elapsed = U256::from(
+            target
+                .checked_sub(&before.timestamp)
+                .ok_or(Underflow)?
+                .into(),
+        );
+        let interval = U256::from(
+            after
+                .timestamp
+                .checked_sub(&before.timestamp)
+                .ok_or(Underflow)?
+                .into(),
+        );
+        let delta = after
+            .base_price_cumulative
+            .checked_sub(before.base_price_cumulative)
+            .ok_or(Underflow)?
+            .checked_mul(elapsed)
+            .ok_or(Overflow)?
+            .checked_div(interval)
+            .ok_or(DivisionByZero)?;
+
+        Ok(before
+            .base_price_cumulative
+            .checked_add(delta)
+            .ok_or(Overflow)?)
+    }
+
+    /// Returns the observations of the desired vamm sorted from the oldest to
+    /// the most recent one.
+    pub fn chronological_observations(vamm_id: T::VammId) -> Vec<ObservationOf<T>> {
+        let observations = Observations::<T>::get(vamm_id);
+        let split = (ObservationIndex::<T>::get(vamm_id) as usize)
+            .saturating_add(1)
+            .min(observations.len());
+        let (newest, oldest) = observations.split_at(split);
+        oldest.iter().chain(newest.iter()).copied().collect()
+    }
+
+    /// Advances `observation` up to `now`, assuming the current base asset
+    /// price of `vamm_state` prevailed during the whole period.
+    fn transform_observation(
+        observation: &ObservationOf<T>,
+        vamm_state: &VammStateOf<T>,
+        now: T::Moment,
+    ) -> Result<ObservationOf<T>, DispatchError> {
+        let elapsed = now.checked_sub(&observation.timestamp).ok_or(Underflow)?;
+        let price = Self::do_get_price(vamm_state, AssetType::Base)?;
+        let accumulated = price
+            .into_inner()
+            .into_u256()
+            .checked_mul(U256::from(elapsed.into()))
+            .ok_or(Overflow)?;
+
+        Ok(Observation {
+            timestamp: now,
+            base_price_cumulative: observation
+                .base_price_cumulative
+                .checked_add(accumulated)
+                .ok_or(Overflow)?,
+        })
+    }
+}
@@ -1,8 +1,11 @@
-use crate::{Config, Pallet, VammStateOf};
+use crate::{helpers::swap::Output, Config, Error, Pallet, SwapConfigOf, VammStateOf};
 use frame_support::pallet_prelude::*;
 use helpers::numbers::IntoU256;
-use sp_runtime::{ArithmeticError, FixedPointNumber};
-use traits::vamm::AssetType;
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One},
+    ArithmeticError, FixedPointNumber,
+};
+use traits::vamm::{AssetType, Depth};
 
 impl<T: Config> Pallet<T> {
     /// Computes the current price for the desired asset, returning it.
@@ -42,4 +45,92 @@ impl<T: Config> Pallet<T> {
         let price_u128: u128 = price_u256.try_into()?;
         Ok(T::Decimal::from_inner(price_u128.into()))
     }
+
+    /// Computes the relative change of the base asset price, as a fraction of
+    /// the current price, which the swap described by `config` would cause.
+    ///
+    /// # Errors
+    ///
+    /// * All errors returned by
+    /// [`compute_swap`](struct.Pallet.html#method.compute_swap).
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn do_get_price_impact(
+        config: &SwapConfigOf<T>,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<T::Decimal, DispatchError> {
+        let Output {
+            base_asset_reserves,
+            quote_asset_reserves,
+            ..
+        } = Self::compute_swap(config, vamm_state)?;
+
+        let price_before = Self::do_get_price(vamm_state, AssetType::Base)?;
+        let price_after = Self::do_get_price(
+            &VammStateOf::<T> {
+                base_asset_reserves,
+                quote_asset_reserves,
+                ..*vamm_state
+            },
+            AssetType::Base,
+        )?;
+
+        let price_change = if price_after >= price_before {
+            price_after.checked_sub(&price_before)
+        } else {
+            price_before.checked_sub(&price_after)
+        }
+        .ok_or(ArithmeticError::Underflow)?;
+
+        Ok(price_change
+            .checked_div(&price_before)
+            .ok_or(ArithmeticError::DivisionByZero)?)
+    }
+
+    /// Computes the amount of base asset which must be bought from and sold to
+    /// the vamm to move its base asset price up and down, respectively, by
+    /// `price_change`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::PriceChangeTooLarge`]
+    /// * All errors returned by
+    /// [`compute_reserves_for_price`](
+    /// struct.Pallet.html#method.compute_reserves_for_price).
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn do_get_depth(
+        vamm_state: &VammStateOf<T>,
+        price_change: T::Decimal,
+    ) -> Result<Depth<T::Balance>, DispatchError> {
+        // The price can't go down by 100% or more.
+        let one = T::Decimal::one();
+        ensure!(price_change < one, Error::<T>::PriceChangeTooLarge);
+
+        let price = Self::do_get_price(vamm_state, AssetType::Base)?;
+        let price_up = price
+            .checked_mul(
+                &one.checked_add(&price_change)
+                    .ok_or(ArithmeticError::Overflow)?,
+            )
+            .ok_or(ArithmeticError::Overflow)?;
+        let price_down = price
+            .checked_mul(
+                &one.checked_sub(&price_change)
+                    .ok_or(ArithmeticError::Underflow)?,
+            )
+            .ok_or(ArithmeticError::Overflow)?;
+
+        let reserves_up = Self::compute_reserves_for_price(vamm_state, price_up)?;
+        let reserves_down = Self::compute_reserves_for_price(vamm_state, price_down)?;
+
+        Ok(Depth {
+            up: Self::abs_balance_diff(
+                vamm_state.base_asset_reserves,
+                reserves_up.base_asset_reserves,
+            ),
+            down: Self::abs_balance_diff(
+                vamm_state.base_asset_reserves,
+                reserves_down.base_asset_reserves,
+            ),
+        })
+    }
 }
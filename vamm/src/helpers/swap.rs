@@ -1,8 +1,11 @@
-use crate::{Config, Error, Event, Pallet, SwapConfigOf, SwapOutputOf, VammMap, VammStateOf};
+use crate::{
+    Config, Error, Event, Pallet, SwapConfigOf, SwapExactOutputConfigOf, SwapOutputOf, VammMap,
+    VammStateOf,
+};
 use frame_support::{pallet_prelude::*, transactional};
 use helpers::numbers::{IntoU256, UnsignedMath};
 use sp_runtime::ArithmeticError::DivisionByZero;
-use sp_std::cmp::Ordering;
+use sp_std::{cmp::Ordering, vec::Vec};
 use traits::vamm::{AssetType, Direction, SwapOutput};
 
 /// Helper struct to store intermediate values during the swap computation
@@ -18,10 +21,10 @@ struct CalculateSwapAsset<T: Config> {
 pub struct Output<T: Config> {
     /// The new amount of `base` asset that the vamm would contain after this
     /// swap.
-    base_asset_reserves: T::Balance,
+    pub base_asset_reserves: T::Balance,
     /// The new amount of `quote` asset that the vamm would contain after this
     /// swap.
-    quote_asset_reserves: T::Balance,
+    pub quote_asset_reserves: T::Balance,
     /// The total asset amount the caller will receive/pay in terms of the the
     /// opposite asset for this swap to takes place.
     pub swap_output: SwapOutputOf<T>,
@@ -36,16 +39,24 @@ impl<T: Config> Pallet<T> {
     ///
     /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
     /// * [`Error::<T>::InsufficientFundsForTrade`]
+    /// * [`Error::<T>::PriceDeviationExceedsLimit`]
     /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
     /// * [`Error::<T>::SwappedAmountLessThanMinimumLimit`]
     /// * [`Error::<T>::SwappedAmountMoreThanMaximumLimit`]
     /// * [`Error::<T>::TradeExtrapolatesMaximumSupportedAmount`]
+    /// * [`Error::<T>::VammIsHalted`]
     /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
     #[transactional]
     pub fn do_swap(
         config: &SwapConfigOf<T>,
         vamm_state: &mut VammStateOf<T>,
     ) -> Result<SwapOutputOf<T>, DispatchError> {
+        // Swaps are not allowed while the circuit breaker halts the vamm.
+        ensure!(
+            !Self::is_vamm_halted(&config.vamm_id, &None),
+            Error::<T>::VammIsHalted
+        );
+
         // Compute new reserves of base and quote asset and swap result.
         let Output {
             base_asset_reserves,
@@ -54,14 +65,7 @@ impl<T: Config> Pallet<T> {
         } = Self::compute_swap(config, vamm_state)?;
 
         // Update runtime storage.
-        VammMap::<T>::try_mutate(config.vamm_id, |old_vamm_state| match old_vamm_state {
-            Some(v) => {
-                v.base_asset_reserves = base_asset_reserves;
-                v.quote_asset_reserves = quote_asset_reserves;
-                Ok(())
-            },
-            None => Err(Error::<T>::VammDoesNotExist),
-        })?;
+        Self::update_reserves(config.vamm_id, base_asset_reserves, quote_asset_reserves)?;
 
         // Deposit swap event into blockchain.
         Self::deposit_event(Event::<T>::Swapped {
@@ -75,6 +79,150 @@ impl<T: Config> Pallet<T> {
         Ok(swap_output)
     }
 
+    /// Performs runtime storage changes, effectively conducting a batch of
+    /// asset swaps. For more information about what this function does, read
+    /// [`swap_batch`](struct.Pallet.html#method.swap_batch) function
+    /// documentation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::InsufficientFundsForTrade`]
+    /// * [`Error::<T>::PriceDeviationExceedsLimit`]
+    /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::SwappedAmountLessThanMinimumLimit`]
+    /// * [`Error::<T>::SwappedAmountMoreThanMaximumLimit`]
+    /// * [`Error::<T>::TooManySwapsInBatch`]
+    /// * [`Error::<T>::TradeExtrapolatesMaximumSupportedAmount`]
+    /// * [`Error::<T>::VammDoesNotExist`]
+    /// * [`Error::<T>::VammIsClosed`]
+    /// * [`Error::<T>::VammIsHalted`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    #[transactional]
+    pub fn do_swap_batch(
+        configs: &[SwapConfigOf<T>],
+    ) -> Result<Vec<SwapOutputOf<T>>, DispatchError> {
+        ensure!(
+            configs.len() <= T::MaxSwapsPerBatch::get() as usize,
+            Error::<T>::TooManySwapsInBatch
+        );
+
+        // Vamms touched by the batch, in order of first appearance, holding
+        // their reserves as the legs are applied.
+        let mut vamm_states: Vec<(T::VammId, VammStateOf<T>)> = Vec::new();
+        let mut swap_outputs = Vec::with_capacity(configs.len());
+
+        for config in configs {
+            let position = match vamm_states.iter().position(|(id, _)| *id == config.vamm_id) {
+                Some(position) => position,
+                None => {
+                    let mut vamm_state = Self::get_vamm_state(&config.vamm_id)?;
+
+                    // Checks and bookkeeping which only depend on the state
+                    // of the vamm before the batch are done once per vamm.
+                    ensure!(
+                        !Self::is_vamm_closed(&vamm_state, &None),
+                        Error::<T>::VammIsClosed
+                    );
+                    ensure!(
+                        !Self::is_vamm_halted(&config.vamm_id, &None),
+                        Error::<T>::VammIsHalted
+                    );
+                    Self::try_update_twap(config.vamm_id, &mut vamm_state, None, &None)?;
+                    Self::write_observation(config.vamm_id, &vamm_state, &None)?;
+
+                    vamm_states.push((config.vamm_id, vamm_state));
+                    vamm_states.len() - 1
+                },
+            };
+            let (_, vamm_state) = vamm_states
+                .get_mut(position)
+                .ok_or(Error::<T>::VammDoesNotExist)?;
+
+            // Each leg must still be valid on its own, given the reserves
+            // left by the previous legs.
+            Self::sanity_check_swap_amount(config, vamm_state)?;
+            let Output {
+                base_asset_reserves,
+                quote_asset_reserves,
+                swap_output,
+            } = Self::compute_swap_unchecked(config, vamm_state)?;
+            Self::sanity_check_swap_limit(config, &swap_output)?;
+
+            vamm_state.base_asset_reserves = base_asset_reserves;
+            vamm_state.quote_asset_reserves = quote_asset_reserves;
+
+            Self::deposit_event(Event::<T>::Swapped {
+                vamm_id: config.vamm_id,
+                input_amount: config.input_amount,
+                output_amount: swap_output,
+                input_asset_type: config.asset,
+                direction: config.direction,
+            });
+
+            swap_outputs.push(swap_output);
+        }
+
+        // Check the final state of each vamm and update runtime storage.
+        for (vamm_id, vamm_state) in vamm_states {
+            Self::sanity_check_state_after_swap(vamm_id, &vamm_state)?;
+            Self::update_reserves(
+                vamm_id,
+                vamm_state.base_asset_reserves,
+                vamm_state.quote_asset_reserves,
+            )?;
+        }
+
+        Ok(swap_outputs)
+    }
+
+    /// Performs runtime storage changes, effectively conducting the exact
+    /// output asset swap. For more information about what this function does,
+    /// read [`swap_exact_output`](struct.Pallet.html#method.swap_exact_output)
+    /// function documentation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::InsufficientFundsForTrade`]
+    /// * [`Error::<T>::PriceDeviationExceedsLimit`]
+    /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::SwappedAmountMoreThanMaximumLimit`]
+    /// * [`Error::<T>::VammIsHalted`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    #[transactional]
+    pub fn do_swap_exact_output(
+        config: &SwapExactOutputConfigOf<T>,
+        vamm_state: &mut VammStateOf<T>,
+    ) -> Result<SwapOutputOf<T>, DispatchError> {
+        // Swaps are not allowed while the circuit breaker halts the vamm.
+        ensure!(
+            !Self::is_vamm_halted(&config.vamm_id, &None),
+            Error::<T>::VammIsHalted
+        );
+
+        // Compute new reserves of base and quote asset and swap result.
+        let Output {
+            base_asset_reserves,
+            quote_asset_reserves,
+            swap_output,
+        } = Self::compute_swap_exact_output(config, vamm_state)?;
+
+        // Update runtime storage.
+        Self::update_reserves(config.vamm_id, base_asset_reserves, quote_asset_reserves)?;
+
+        // Deposit swap event into blockchain.
+        Self::deposit_event(Event::<T>::Swapped {
+            vamm_id: config.vamm_id,
+            input_amount: config.output_amount,
+            output_amount: swap_output,
+            input_asset_type: config.asset,
+            direction: Direction::Remove,
+        });
+
+        Ok(swap_output)
+    }
+
     /// Performs runtime storage changes, effectively conducting the asset swap.
     /// For more information about what this function does, read
     /// [`swap`](struct.Pallet.html#method.swap) function documentation.
@@ -83,6 +231,7 @@ impl<T: Config> Pallet<T> {
     ///
     /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
     /// * [`Error::<T>::InsufficientFundsForTrade`]
+    /// * [`Error::<T>::PriceDeviationExceedsLimit`]
     /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
     /// * [`Error::<T>::SwappedAmountLessThanMinimumLimit`]
     /// * [`Error::<T>::SwappedAmountMoreThanMaximumLimit`]
@@ -95,10 +244,61 @@ impl<T: Config> Pallet<T> {
         // Check if initial swap properties are valid.
         Self::sanity_check_before_swap(config, vamm_state)?;
 
+        // Delegate alternate computation to helper functions.
+        let swap = Self::compute_swap_unchecked(config, vamm_state)?;
+
+        // Check if swap doesn't violate Vamm properties and swap requirements.
+        Self::sanity_check_after_swap(
+            &VammStateOf::<T> {
+                base_asset_reserves: swap.base_asset_reserves,
+                quote_asset_reserves: swap.quote_asset_reserves,
+                ..*vamm_state
+            },
+            config,
+            &swap.swap_output,
+        )?;
+
+        Ok(swap)
+    }
+
+    /// Computes the result of an exact output swap, without modifying runtime
+    /// storage.
+    ///
+    /// The caller receives exactly
+    /// [`output_amount`](traits::vamm::SwapExactOutputConfig::output_amount)
+    /// of the desired asset, which is removed from the vamm. The amount of the
+    /// other asset the caller has to pay is rounded up, so that any rounding
+    /// error is always in favor of the vamm.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::<T>::BaseAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::InsufficientFundsForTrade`]
+    /// * [`Error::<T>::PriceDeviationExceedsLimit`]
+    /// * [`Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained`]
+    /// * [`Error::<T>::SwappedAmountMoreThanMaximumLimit`]
+    /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+    pub fn compute_swap_exact_output(
+        config: &SwapExactOutputConfigOf<T>,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<Output<T>, DispatchError> {
+        // An exact output swap removes the desired asset from the vamm, so the
+        // same properties of a regular removal must hold.
+        let equivalent_config = SwapConfigOf::<T> {
+            vamm_id: config.vamm_id,
+            asset: config.asset,
+            input_amount: config.output_amount,
+            direction: Direction::Remove,
+            output_amount_limit: config.input_amount_limit,
+        };
+
+        // Check if initial swap properties are valid.
+        Self::sanity_check_before_swap(&equivalent_config, vamm_state)?;
+
         // Delegate alternate computation to helper functions.
         let swap = match config.asset {
-            AssetType::Quote => Self::compute_swap_quote_asset(config, vamm_state),
-            AssetType::Base => Self::compute_swap_base_asset(config, vamm_state),
+            AssetType::Base => Self::compute_swap_exact_output_base_asset(config, vamm_state),
+            AssetType::Quote => Self::compute_swap_exact_output_quote_asset(config, vamm_state),
         }?;
 
         // Check if swap doesn't violate Vamm properties and swap requirements.
@@ -108,13 +308,109 @@ impl<T: Config> Pallet<T> {
                 quote_asset_reserves: swap.quote_asset_reserves,
                 ..*vamm_state
             },
-            config,
+            &equivalent_config,
             &swap.swap_output,
         )?;
 
         Ok(swap)
     }
 
+    fn compute_swap_exact_output_base_asset(
+        config: &SwapExactOutputConfigOf<T>,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<Output<T>, DispatchError> {
+        let base_asset_reserves = vamm_state
+            .base_asset_reserves
+            .try_sub(&config.output_amount)?;
+        let quote_asset_reserves =
+            Self::calculate_reserve_rounding_up(&base_asset_reserves, vamm_state)?;
+
+        let quote_asset_amount = quote_asset_reserves
+            .try_sub(&vamm_state.quote_asset_reserves)?
+            .try_mul(&vamm_state.peg_multiplier)?;
+
+        Ok(Output {
+            base_asset_reserves,
+            quote_asset_reserves,
+            swap_output: SwapOutput {
+                output: quote_asset_amount,
+                negative: true,
+            },
+        })
+    }
+
+    fn compute_swap_exact_output_quote_asset(
+        config: &SwapExactOutputConfigOf<T>,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<Output<T>, DispatchError> {
+        // Removing more quote asset reserves than strictly needed favors the
+        // vamm, since it requires more base asset in return.
+        let quote_asset_reserve_amount: u128 = Self::div_rounding_up(
+            config.output_amount.into_u256(),
+            vamm_state.peg_multiplier.into_u256(),
+        )?
+        .try_into()?;
+        let quote_asset_reserves = vamm_state
+            .quote_asset_reserves
+            .try_sub(&quote_asset_reserve_amount.into())?;
+        let base_asset_reserves =
+            Self::calculate_reserve_rounding_up(&quote_asset_reserves, vamm_state)?;
+
+        let base_asset_amount = base_asset_reserves.try_sub(&vamm_state.base_asset_reserves)?;
+
+        Ok(Output {
+            base_asset_reserves,
+            quote_asset_reserves,
+            swap_output: SwapOutput {
+                output: base_asset_amount,
+                negative: true,
+            },
+        })
+    }
+
+    /// Computes the new reserves and the swap result for a regular swap,
+    /// without performing any sanity check.
+    fn compute_swap_unchecked(
+        config: &SwapConfigOf<T>,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<Output<T>, DispatchError> {
+        match config.asset {
+            AssetType::Quote => Self::compute_swap_quote_asset(config, vamm_state),
+            AssetType::Base => Self::compute_swap_base_asset(config, vamm_state),
+        }
+    }
+
+    /// Returns the reserves of one asset needed to keep the vamm invariant,
+    /// given the new reserves of the other asset, rounding the result up.
+    fn calculate_reserve_rounding_up(
+        other_asset_reserves: &T::Balance,
+        vamm_state: &VammStateOf<T>,
+    ) -> Result<T::Balance, DispatchError> {
+        let reserves_u256 =
+            Self::div_rounding_up(vamm_state.invariant, other_asset_reserves.into_u256())?;
+        let reserves_u128: u128 = reserves_u256
+            .try_into()
+            .map_err(|_| Error::<T>::TradeExtrapolatesMaximumSupportedAmount)?;
+
+        Ok(reserves_u128.into())
+    }
+
+    /// Updates the asset reserves of the desired vamm in runtime storage.
+    fn update_reserves(
+        vamm_id: T::VammId,
+        base_asset_reserves: T::Balance,
+        quote_asset_reserves: T::Balance,
+    ) -> Result<(), DispatchError> {
+        VammMap::<T>::try_mutate(vamm_id, |old_vamm_state| match old_vamm_state {
+            Some(v) => {
+                v.base_asset_reserves = base_asset_reserves;
+                v.quote_asset_reserves = quote_asset_reserves;
+                Ok(())
+            },
+            None => Err(Error::<T>::VammDoesNotExist.into()),
+        })
+    }
+
     fn compute_swap_quote_asset(
         config: &SwapConfigOf<T>,
         vamm_state: &VammStateOf<T>,
@@ -53,6 +53,17 @@
 //! asset in a vamm.
 //! * [`get_twap`](pallet/struct.Pallet.html#method.get_twap): Gets the time
 //! weighted average price of the desired asset.
+//! * [`get_twap_over`](pallet/struct.Pallet.html#method.get_twap_over): Gets
+//! the arithmetic time weighted average price of the base asset over an
+//! arbitrary window, computed from the vamm's price observations.
+//! * [`get_price_impact`](pallet/struct.Pallet.html#method.get_price_impact):
+//! Computes the relative change of the base asset price a swap would cause.
+//! * [`get_depth`](pallet/struct.Pallet.html#method.get_depth): Computes how
+//! much base asset can be traded before the price moves by a given percentage
+//! in each direction.
+//! * [`get_reserves_for_price`](
+//! pallet/struct.Pallet.html#method.get_reserves_for_price): Computes the
+//! asset reserves needed for the vamm to reach a target price.
 //! * [`move_price`](pallet/struct.Pallet.html#method.move_price): Changes
 //! amount of
 //! [`base`](types/struct.VammState.html#structfield.base_asset_reserves) and
@@ -64,17 +75,40 @@
 //! Performs the *simulation* of the swap operation for the desired asset
 //! against the vamm, returning the expected amount such a trade would result if
 //! the swap were in fact executed.
+//! * [`swap_batch`](pallet/struct.Pallet.html#method.swap_batch): Performs
+//! several swaps atomically, possibly against different vamms.
+//! * [`swap_exact_output`](pallet/struct.Pallet.html#method.swap_exact_output):
+//! Performs the swap against the vamm, receiving an exact amount of the desired
+//! asset and paying whatever amount of the other asset is needed for it.
+//! * [`swap_exact_output_simulation`](
+//! pallet/struct.Pallet.html#method.swap_exact_output_simulation): Performs
+//! the *simulation* of the exact output swap operation.
 //! * [`update_twap`](pallet/struct.Pallet.html#method.update_twap): Updates the
 //! time weighted average price of the desired asset.
 //! * [`close`](pallet/struct.Pallet.html#method.close): Schedules a closing
 //! date for the desired vamm, after which the vamm will be considered closed
 //! and all operations in it will be halted.
+//! * [`set_circuit_breaker`](pallet/struct.Pallet.html#method.set_circuit_breaker):
+//! Sets or removes the circuit breaker of the desired vamm, which rejects
+//! swaps moving its price abnormally and optionally halts it.
 //!
 //! ### Runtime Storage Objects
 //!
 //! - [`VammCounter`](VammCounter): The number of created vamms.
 //! - [`VammMap`](VammMap): Mapping of a [`VammId`](Config::VammId) to it's
 //! corresponding [`VammState`](types/struct.VammState.html#).
+//! - [`Observations`](Observations): Mapping of a [`VammId`](Config::VammId) to
+//! the ring buffer of its price
+//! [`Observation`](types/struct.Observation.html#)s.
+//! - [`ObservationIndex`](ObservationIndex): Mapping of a
+//! [`VammId`](Config::VammId) to the position of its most recent observation.
+//! - [`CircuitBreakers`](CircuitBreakers): Mapping of a
+//! [`VammId`](Config::VammId) to its circuit breaker configuration.
+//! - [`BlockStartPrices`](BlockStartPrices): Mapping of a
+//! [`VammId`](Config::VammId) to its base asset price at the start of the
+//! block.
+//! - [`HaltedUntil`](HaltedUntil): Mapping of a [`VammId`](Config::VammId) to
+//! the timestamp until which it is halted by its circuit breaker.
 //!
 //! ## Usage
 //!
@@ -163,6 +197,9 @@ pub mod types;
 /// Helper functions and types for low-level functionalities of the Vamm Pallet.
 pub mod helpers;
 
+/// Weight information for the operations of the Vamm Pallet.
+pub mod weights;
+
 pub use pallet::*;
 
 #[allow(clippy::too_many_lines, clippy::let_underscore_drop)]
@@ -172,7 +209,10 @@ pub mod pallet {
     //                                       Imports and Dependencies
     // ----------------------------------------------------------------------------------------------------
 
-    use crate::types::VammState;
+    use crate::{
+        types::{Observation, VammState},
+        weights::WeightInfo,
+    };
     use codec::{Codec, FullCodec};
     use frame_support::{
         pallet_prelude::*, sp_std::fmt::Debug, traits::UnixTime, transactional, Blake2_128Concat,
@@ -189,8 +229,8 @@ pub mod pallet {
         ArithmeticError, FixedPointNumber, FixedU128,
     };
     use traits::vamm::{
-        AssetType, Direction, MovePriceConfig, SwapConfig, SwapOutput, Vamm, VammConfig,
-        MINIMUM_TWAP_PERIOD,
+        AssetType, CircuitBreakerConfig, Depth, Direction, MovePriceConfig, Reserves, SwapConfig,
+        SwapExactOutputConfig, SwapOutput, Vamm, VammConfig, MINIMUM_TWAP_PERIOD,
     };
 
     // ----------------------------------------------------------------------------------------------------
@@ -278,6 +318,27 @@ pub mod pallet {
 
         /// Implementation for querying the current Unix timestamp.
         type TimeProvider: UnixTime;
+
+        /// The maximum number of price observations kept in the ring buffer of
+        /// each vamm. Older observations are overwritten once the limit is
+        /// reached, bounding how far in the past a twap can be computed by
+        /// [`get_twap_over`](Pallet::get_twap_over).
+        #[pallet::constant]
+        type MaxObservations: Get<u32>;
+
+        /// The maximum number of swaps that can be executed at once by
+        /// [`swap_batch`](Pallet::swap_batch).
+        #[pallet::constant]
+        type MaxSwapsPerBatch: Get<u32>;
+
+        /// The maximum number of vamms which can have a circuit breaker set
+        /// at the same time, bounding the work done at the start of each
+        /// block.
+        #[pallet::constant]
+        type MaxCircuitBreakers: Get<u32>;
+
+        /// Weight information for the operations of this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -290,12 +351,23 @@ pub mod pallet {
     /// Type alias for the [`SwapConfig`] value of the Vamm Pallet.
     pub type SwapConfigOf<T> = SwapConfig<<T as Config>::VammId, <T as Config>::Balance>;
 
+    /// Type alias for the [`SwapExactOutputConfig`] value of the Vamm Pallet.
+    pub type SwapExactOutputConfigOf<T> =
+        SwapExactOutputConfig<<T as Config>::VammId, <T as Config>::Balance>;
+
     /// Type alias for the [`Twap`] value of the Vamm Pallet.
     pub type TwapOf<T> = Twap<<T as Config>::Decimal, <T as Config>::Moment>;
 
     /// Type alias for the [`VammState`] value of the Vamm Pallet.
     pub type VammStateOf<T> = VammState<<T as Config>::Balance, <T as Config>::Moment, TwapOf<T>>;
 
+    /// Type alias for the [`Observation`] value of the Vamm Pallet.
+    pub type ObservationOf<T> = Observation<<T as Config>::Moment>;
+
+    /// Type alias for the [`CircuitBreakerConfig`] value of the Vamm Pallet.
+    pub type CircuitBreakerConfigOf<T> =
+        CircuitBreakerConfig<<T as Config>::Decimal, <T as Config>::Moment>;
+
     // ----------------------------------------------------------------------------------------------------
     //                                           Runtime  Storage
     // ----------------------------------------------------------------------------------------------------
@@ -317,6 +389,46 @@ pub mod pallet {
     #[pallet::getter(fn get_vamm)]
     pub type VammMap<T: Config> = StorageMap<_, Blake2_128Concat, T::VammId, VammStateOf<T>>;
 
+    /// Maps [VammId](Config::VammId) to the ring buffer of its base asset
+    /// price [`Observation`]s.
+    #[pallet::storage]
+    #[pallet::getter(fn get_observations)]
+    #[allow(clippy::disallowed_types)]
+    pub type Observations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::VammId,
+        BoundedVec<ObservationOf<T>, T::MaxObservations>,
+        ValueQuery,
+    >;
+
+    /// Maps [VammId](Config::VammId) to the position of the most recent entry
+    /// in its [`Observations`] ring buffer.
+    #[pallet::storage]
+    #[pallet::getter(fn get_observation_index)]
+    #[allow(clippy::disallowed_types)]
+    pub type ObservationIndex<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::VammId, u32, ValueQuery>;
+
+    /// Maps [VammId](Config::VammId) to the configuration of its circuit
+    /// breaker, if any.
+    #[pallet::storage]
+    #[pallet::getter(fn get_circuit_breaker)]
+    pub type CircuitBreakers<T: Config> =
+        CountedStorageMap<_, Blake2_128Concat, T::VammId, CircuitBreakerConfigOf<T>>;
+
+    /// Maps [VammId](Config::VammId) to its base asset price at the start of
+    /// the current block. Only tracked for vamms with a circuit breaker.
+    #[pallet::storage]
+    #[pallet::getter(fn get_block_start_price)]
+    pub type BlockStartPrices<T: Config> = StorageMap<_, Blake2_128Concat, T::VammId, T::Decimal>;
+
+    /// Maps [VammId](Config::VammId) to the timestamp until which it is
+    /// halted by its circuit breaker.
+    #[pallet::storage]
+    #[pallet::getter(fn get_halted_until)]
+    pub type HaltedUntil<T: Config> = StorageMap<_, Blake2_128Concat, T::VammId, T::Moment>;
+
     // ----------------------------------------------------------------------------------------------------
     //                                            Runtime Events
     // ----------------------------------------------------------------------------------------------------
@@ -390,6 +502,25 @@ pub mod pallet {
             /// reaching the specified time the vamm will be considered *closed*.
             closing_time: T::Moment,
         },
+        /// Emitted after a successful call to the
+        /// [`set_circuit_breaker`](Pallet::set_circuit_breaker) function.
+        CircuitBreakerUpdated {
+            /// The identifier for the Vamm where the operation took place.
+            vamm_id: T::VammId,
+            /// The new circuit breaker configuration, if any.
+            config: Option<CircuitBreakerConfigOf<T>>,
+        },
+        /// Emitted when the circuit breaker of a vamm detects an abnormal
+        /// price at the start of a block, halting the vamm.
+        CircuitBreakerTriggered {
+            /// The identifier for the halted Vamm.
+            vamm_id: T::VammId,
+            /// The [`base asset`](VammState::base_asset_reserves) price which
+            /// triggered the circuit breaker.
+            price: T::Decimal,
+            /// The timestamp until which swaps are rejected.
+            halted_until: T::Moment,
+        },
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -437,12 +568,19 @@ pub mod pallet {
         ///
         /// * [`Pallet::get_price`]
         /// * [`Pallet::get_twap`]
+        /// * [`Pallet::get_twap_over`]
         /// * [`Pallet::update_twap`]
         /// * [`Pallet::swap`]
         /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        /// * [`Pallet::swap_exact_output_simulation`]
         /// * [`Pallet::move_price`]
         /// * [`Pallet::close`]
         /// * [`Pallet::get_vamm_state`]
+        /// * [`Pallet::get_price_impact`]
+        /// * [`Pallet::get_depth`]
+        /// * [`Pallet::get_reserves_for_price`]
         VammDoesNotExist,
         /// Tried to execute a trade but the Vamm didn't have enough funds to
         /// fulfill it.
@@ -451,9 +589,13 @@ pub mod pallet {
         ///
         /// * [`Pallet::swap`]
         /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        /// * [`Pallet::swap_exact_output_simulation`]
         /// * [`Pallet::do_swap`]
         /// * [`Pallet::compute_swap`]
         /// * [`Pallet::sanity_check_before_swap`]
+        /// * [`Pallet::get_price_impact`]
         InsufficientFundsForTrade,
         /// Tried to add some amount of asset to Vamm but it would exceed the
         /// supported maximum value.
@@ -462,9 +604,16 @@ pub mod pallet {
         ///
         /// * [`Pallet::swap`]
         /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        /// * [`Pallet::swap_exact_output_simulation`]
         /// * [`Pallet::do_swap`]
         /// * [`Pallet::compute_swap`]
         /// * [`Pallet::sanity_check_before_swap`]
+        /// * [`Pallet::get_price_impact`]
+        /// * [`Pallet::get_depth`]
+        /// * [`Pallet::get_reserves_for_price`]
+        /// * [`Pallet::compute_reserves_for_price`]
         TradeExtrapolatesMaximumSupportedAmount,
         /// Tried to perform operation against a closed Vamm.
         ///
@@ -472,14 +621,21 @@ pub mod pallet {
         ///
         /// * [`Pallet::get_price`]
         /// * [`Pallet::get_twap`]
+        /// * [`Pallet::get_twap_over`]
         /// * [`Pallet::update_twap`]
         /// * [`Pallet::swap`]
         /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        /// * [`Pallet::swap_exact_output_simulation`]
         /// * [`Pallet::move_price`]
         /// * [`Pallet::close`]
         /// * [`Pallet::sanity_check_before_swap`]
         /// * [`Pallet::sanity_check_before_update_twap`]
         /// * [`Pallet::sanity_check_before_close`]
+        /// * [`Pallet::get_price_impact`]
+        /// * [`Pallet::get_depth`]
+        /// * [`Pallet::get_reserves_for_price`]
         VammIsClosed,
         /// Tried to perform operation against a closing Vamm, but this specific
         /// operation is not allowed.
@@ -502,9 +658,11 @@ pub mod pallet {
         ///
         /// * [`Pallet::swap`]
         /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
         /// * [`Pallet::do_swap`]
         /// * [`Pallet::compute_swap`]
         /// * [`Pallet::sanity_check_after_swap`]
+        /// * [`Pallet::get_price_impact`]
         SwappedAmountLessThanMinimumLimit,
         /// Tried to swap assets but the amount returned was more than the maximum expected.
         ///
@@ -512,9 +670,13 @@ pub mod pallet {
         ///
         /// * [`Pallet::swap`]
         /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        /// * [`Pallet::swap_exact_output_simulation`]
         /// * [`Pallet::do_swap`]
         /// * [`Pallet::compute_swap`]
         /// * [`Pallet::sanity_check_after_swap`]
+        /// * [`Pallet::get_price_impact`]
         SwappedAmountMoreThanMaximumLimit,
         /// Tried to perform swap operation but it would drain all
         /// [`base`](VammState::base_asset_reserves) asset reserves.
@@ -523,9 +685,16 @@ pub mod pallet {
         ///
         /// * [`Pallet::swap`]
         /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        /// * [`Pallet::swap_exact_output_simulation`]
         /// * [`Pallet::do_swap`]
         /// * [`Pallet::compute_swap`]
         /// * [`Pallet::sanity_check_after_swap`]
+        /// * [`Pallet::get_price_impact`]
+        /// * [`Pallet::get_depth`]
+        /// * [`Pallet::get_reserves_for_price`]
+        /// * [`Pallet::compute_reserves_for_price`]
         BaseAssetReservesWouldBeCompletelyDrained,
         /// Tried to perform swap operation but it would drain all
         /// [`quote`](VammState::quote_asset_reserves) asset reserves.
@@ -534,9 +703,16 @@ pub mod pallet {
         ///
         /// * [`Pallet::swap`]
         /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        /// * [`Pallet::swap_exact_output_simulation`]
         /// * [`Pallet::do_swap`]
         /// * [`Pallet::compute_swap`]
         /// * [`Pallet::sanity_check_after_swap`]
+        /// * [`Pallet::get_price_impact`]
+        /// * [`Pallet::get_depth`]
+        /// * [`Pallet::get_reserves_for_price`]
+        /// * [`Pallet::compute_reserves_for_price`]
         QuoteAssetReservesWouldBeCompletelyDrained,
         /// Tried to update twap for an asset, but its last twap update was
         /// more recent than the current time.
@@ -582,6 +758,93 @@ pub mod pallet {
         /// * [`Pallet::close`]
         /// * [`Pallet::sanity_check_before_close`]
         ClosingDateIsInThePast,
+        /// Tried to compute a twap over a window of zero seconds.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::get_twap_over`]
+        /// * [`Pallet::compute_twap_over`]
+        TwapWindowIsZero,
+        /// Tried to compute a twap over a window which starts before the
+        /// oldest price observation available for the vamm.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::get_twap_over`]
+        /// * [`Pallet::compute_twap_over`]
+        /// * [`Pallet::observe`]
+        InsufficientObservationHistory,
+        /// Tried to record a new price observation, but the ring buffer could
+        /// not hold it.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::write_observation`]
+        ObservationsBufferIsFull,
+        /// Tried to execute a batch with more swaps than
+        /// [`MaxSwapsPerBatch`](Config::MaxSwapsPerBatch).
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::do_swap_batch`]
+        TooManySwapsInBatch,
+        /// Tried to perform a swap which would move the base asset price
+        /// further from the price at the start of the block or from the base
+        /// asset twap than allowed by the vamm's circuit breaker.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::swap`]
+        /// * [`Pallet::swap_simulation`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        /// * [`Pallet::swap_exact_output_simulation`]
+        /// * [`Pallet::compute_swap`]
+        /// * [`Pallet::sanity_check_after_swap`]
+        /// * [`Pallet::sanity_check_price_deviation`]
+        /// * [`Pallet::get_price_impact`]
+        PriceDeviationExceedsLimit,
+        /// Tried to perform a swap against a vamm halted by its circuit
+        /// breaker.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::swap`]
+        /// * [`Pallet::swap_batch`]
+        /// * [`Pallet::swap_exact_output`]
+        VammIsHalted,
+        /// Tried to set a circuit breaker with a maximum price deviation of
+        /// zero, which would reject every swap.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::set_circuit_breaker`]
+        MaxPriceDeviationIsZero,
+        /// Tried to set a circuit breaker, but
+        /// [`MaxCircuitBreakers`](Config::MaxCircuitBreakers) vamms already
+        /// have one.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::set_circuit_breaker`]
+        TooManyCircuitBreakers,
+        /// Tried to compute the reserves for a target price of zero.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::get_reserves_for_price`]
+        /// * [`Pallet::get_depth`]
+        /// * [`Pallet::compute_reserves_for_price`]
+        TargetPriceIsZero,
+        /// Tried to compute the depth for a price change of 100% or more,
+        /// which would require the price to go down to zero or below.
+        ///
+        /// ## Occurrences
+        ///
+        /// * [`Pallet::get_depth`]
+        /// * [`Pallet::do_get_depth`]
+        PriceChangeTooLarge,
     }
 
     // ----------------------------------------------------------------------------------------------------
@@ -589,7 +852,11 @@ pub mod pallet {
     // ----------------------------------------------------------------------------------------------------
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+        fn on_initialize(_: T::BlockNumber) -> Weight {
+            Self::check_circuit_breakers(&None)
+        }
+    }
 
     // ----------------------------------------------------------------------------------------------------
     //                                         Genesis Configuration
@@ -633,6 +900,7 @@ pub mod pallet {
         type Moment = T::Moment;
         type MovePriceConfig = MovePriceConfig<T::VammId, T::Balance>;
         type SwapConfig = SwapConfigOf<T>;
+        type SwapExactOutputConfig = SwapExactOutputConfigOf<T>;
         type VammConfig = VammConfig<T::Balance, T::Moment>;
         type VammId = T::VammId;
 
@@ -672,7 +940,8 @@ pub mod pallet {
         /// * [`Created`](Event::<T>::Created)
         ///
         /// ## State Changes
-        /// Updates [`VammMap`] storage map and [`VammCounter`] storage value.
+        /// Updates [`VammMap`] and [`Observations`] storage maps and
+        /// [`VammCounter`] storage value.
         ///
         /// ## Errors
         /// * [`BaseAssetReserveIsZero`](Error::<T>::BaseAssetReserveIsZero)
@@ -723,6 +992,7 @@ pub mod pallet {
                 };
 
                 VammMap::<T>::insert(id, vamm_state);
+                Self::write_observation(id, &vamm_state, &Some(now))?;
                 *next_id = id
                     .checked_add(&One::one())
                     .ok_or(ArithmeticError::Overflow)?;
@@ -851,6 +1121,234 @@ pub mod pallet {
             }
         }
 
+        /// Returns the arithmetic time weighted average price of the base asset
+        /// over the last `window` seconds.
+        ///
+        /// # Overview
+        /// Contrary to [`get_twap`](Self::get_twap), which returns a single
+        /// exponential moving average with a fixed
+        /// [`twap_period`](VammState::base_asset_twap), this function computes
+        /// the twap over any desired window, as long as it is covered by the
+        /// vamm's price [`Observations`]. This allows callers to query several
+        /// windows (e.g. 5 minutes, 1 hour and 8 hours) for the same vamm.
+        ///
+        /// The twap is computed following the formula:
+        ///
+        /// $$
+        /// twap = \frac{C_{now} - C_{now - window}}{window}
+        /// $$
+        ///
+        /// Where $C_t$ is the cumulative base asset price at time $t$,
+        /// interpolated between the two surrounding observations if needed.
+        ///
+        /// ## Parameters
+        ///  - [`vamm_id`](Config::VammId): The ID of the desired vamm to query.
+        ///  - `window`: The number of seconds in the past to compute the twap
+        ///  over.
+        ///
+        /// ## Returns
+        /// The arithmetic twap for the base asset over the specified window.
+        ///
+        /// ## Assumptions or Requirements
+        /// * The requested [`VammId`](Config::VammId) must exist.
+        /// * The requested Vamm must be open.
+        /// * The `window` must be greater than zero.
+        /// * The oldest observation available must be at least `window`
+        /// seconds old.
+        ///
+        /// ## Emits
+        /// No event is emitted for this function.
+        ///
+        /// ## State Changes
+        /// This function does not mutate runtime storage.
+        ///
+        /// ## Errors
+        /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
+        /// * [`VammIsClosed`](Error::<T>::VammIsClosed)
+        /// * [`TwapWindowIsZero`](Error::<T>::TwapWindowIsZero)
+        /// * [`InsufficientObservationHistory`](Error::<T>::InsufficientObservationHistory)
+        /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+        ///
+        /// # Runtime
+        /// `O(n)`, where `n` is [`MaxObservations`](Config::MaxObservations).
+        fn get_twap_over(
+            vamm_id: T::VammId,
+            window: T::Moment,
+        ) -> Result<T::Decimal, DispatchError> {
+            // Sanity Checks
+            // 1) Vamm must exist
+            let vamm_state = Self::get_vamm_state(&vamm_id)?;
+
+            // 2) Vamm must be open
+            ensure!(
+                !Self::is_vamm_closed(&vamm_state, &None),
+                Error::<T>::VammIsClosed
+            );
+
+            Self::compute_twap_over(vamm_id, &vamm_state, window, &None)
+        }
+
+        /// Computes the price impact of a swap against the vamm.
+        ///
+        /// # Overview
+        /// Simulates the swap described by `config` and returns the relative
+        /// change of the base asset price it would cause, as a fraction of the
+        /// current price, i.e.:
+        ///
+        /// $$
+        /// impact = \frac{|price_{after} - price_{before}|}{price_{before}}
+        /// $$
+        ///
+        /// ## Parameters
+        ///  - `config`: Specification for the swap.
+        ///
+        /// ## Returns
+        /// The price impact of the swap (e.g. `0.01` for 1%).
+        ///
+        /// ## Assumptions or Requirements
+        /// * The requested [`VammId`](Config::VammId) must exist.
+        /// * The requested Vamm must be open.
+        /// * The swap must fulfill the requirements of [`swap`](Self::swap).
+        ///
+        /// ## Emits
+        /// No event is emitted for this function.
+        ///
+        /// ## State Changes
+        /// This function does not mutate runtime storage.
+        ///
+        /// ## Errors
+        /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
+        /// * [`VammIsClosed`](Error::<T>::VammIsClosed)
+        /// * [`InsufficientFundsForTrade`](Error::<T>::InsufficientFundsForTrade)
+        /// * [`TradeExtrapolatesMaximumSupportedAmount`](Error::<T>::TradeExtrapolatesMaximumSupportedAmount)
+        /// * [`BaseAssetReservesWouldBeCompletelyDrained`](Error::<T>::BaseAssetReservesWouldBeCompletelyDrained)
+        /// * [`QuoteAssetReservesWouldBeCompletelyDrained`](Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained)
+        /// * [`SwappedAmountLessThanMinimumLimit`](Error::<T>::SwappedAmountLessThanMinimumLimit)
+        /// * [`SwappedAmountMoreThanMaximumLimit`](Error::<T>::SwappedAmountMoreThanMaximumLimit)
+        /// * [`PriceDeviationExceedsLimit`](Error::<T>::PriceDeviationExceedsLimit)
+        /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+        ///
+        /// # Runtime
+        /// `O(1)`
+        fn get_price_impact(config: &SwapConfigOf<T>) -> Result<T::Decimal, DispatchError> {
+            let vamm_state = Self::get_vamm_state(&config.vamm_id)?;
+
+            Self::do_get_price_impact(config, &vamm_state)
+        }
+
+        /// Computes the depth of the vamm around its current price.
+        ///
+        /// # Overview
+        /// Answers the question "how much base asset can be traded before the
+        /// price moves by `price_change`?" in both directions, i.e. the amount
+        /// of base asset which must be removed from the vamm for its base
+        /// asset price to go up by `price_change`, and the amount which must
+        /// be added to it for the price to go down by `price_change`.
+        ///
+        /// ## Parameters
+        ///  - [`vamm_id`](Config::VammId): The ID of the desired vamm to query.
+        ///  - `price_change`: The relative price change, as a fraction of the
+        ///  current price (e.g. `0.01` for 1%).
+        ///
+        /// ## Returns
+        /// The [`Depth`] of the vamm for the desired price change.
+        ///
+        /// ## Assumptions or Requirements
+        /// * The requested [`VammId`](Config::VammId) must exist.
+        /// * The requested Vamm must be open.
+        /// * The `price_change` must be less than `1`, as the price can't go
+        /// down by 100% or more.
+        ///
+        /// ## Emits
+        /// No event is emitted for this function.
+        ///
+        /// ## State Changes
+        /// This function does not mutate runtime storage.
+        ///
+        /// ## Errors
+        /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
+        /// * [`VammIsClosed`](Error::<T>::VammIsClosed)
+        /// * [`PriceChangeTooLarge`](Error::<T>::PriceChangeTooLarge)
+        /// * [`BaseAssetReservesWouldBeCompletelyDrained`](Error::<T>::BaseAssetReservesWouldBeCompletelyDrained)
+        /// * [`QuoteAssetReservesWouldBeCompletelyDrained`](Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained)
+        /// * [`TradeExtrapolatesMaximumSupportedAmount`](Error::<T>::TradeExtrapolatesMaximumSupportedAmount)
+        /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+        ///
+        /// # Runtime
+        /// `O(1)`
+        fn get_depth(
+            vamm_id: T::VammId,
+            price_change: T::Decimal,
+        ) -> Result<Depth<T::Balance>, DispatchError> {
+            // Sanity Checks
+            // 1) Vamm must exist
+            let vamm_state = Self::get_vamm_state(&vamm_id)?;
+
+            // 2) Vamm must be open
+            ensure!(
+                !Self::is_vamm_closed(&vamm_state, &None),
+                Error::<T>::VammIsClosed
+            );
+
+            Self::do_get_depth(&vamm_state, price_change)
+        }
+
+        /// Computes the asset reserves needed for the vamm to reach the
+        /// desired base asset price.
+        ///
+        /// # Overview
+        /// Returns the [`base`](VammState::base_asset_reserves) and
+        /// [`quote`](VammState::quote_asset_reserves) asset reserves for which
+        /// the base asset price would be `target_price`, keeping the vamm's
+        /// invariant. The difference between these and the current reserves
+        /// is the trade needed to move the price to `target_price`.
+        ///
+        /// ## Parameters
+        ///  - [`vamm_id`](Config::VammId): The ID of the desired vamm to query.
+        ///  - `target_price`: The desired base asset price.
+        ///
+        /// ## Returns
+        /// The [`Reserves`] for which the vamm would have the target price.
+        ///
+        /// ## Assumptions or Requirements
+        /// * The requested [`VammId`](Config::VammId) must exist.
+        /// * The requested Vamm must be open.
+        /// * The `target_price` must be greater than zero.
+        ///
+        /// ## Emits
+        /// No event is emitted for this function.
+        ///
+        /// ## State Changes
+        /// This function does not mutate runtime storage.
+        ///
+        /// ## Errors
+        /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
+        /// * [`VammIsClosed`](Error::<T>::VammIsClosed)
+        /// * [`TargetPriceIsZero`](Error::<T>::TargetPriceIsZero)
+        /// * [`BaseAssetReservesWouldBeCompletelyDrained`](Error::<T>::BaseAssetReservesWouldBeCompletelyDrained)
+        /// * [`QuoteAssetReservesWouldBeCompletelyDrained`](Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained)
+        /// * [`TradeExtrapolatesMaximumSupportedAmount`](Error::<T>::TradeExtrapolatesMaximumSupportedAmount)
+        /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+        ///
+        /// # Runtime
+        /// `O(1)`
+        fn get_reserves_for_price(
+            vamm_id: T::VammId,
+            target_price: T::Decimal,
+        ) -> Result<Reserves<T::Balance>, DispatchError> {
+            // Sanity Checks
+            // 1) Vamm must exist
+            let vamm_state = Self::get_vamm_state(&vamm_id)?;
+
+            // 2) Vamm must be open
+            ensure!(
+                !Self::is_vamm_closed(&vamm_state, &None),
+                Error::<T>::VammIsClosed
+            );
+
+            Self::compute_reserves_for_price(&vamm_state, target_price)
+        }
+
         /// Updates the time weighted average price of the [base
         /// asset](VammState::base_asset_twap).
         ///
@@ -970,7 +1468,7 @@ pub mod pallet {
         /// * [`Swapped`](Event::<T>::Swapped)
         ///
         /// ## State Changes
-        /// Updates [`VammMap`] storage map.
+        /// Updates [`VammMap`] and [`Observations`] storage maps.
         ///
         /// ## Errors
         /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
@@ -981,6 +1479,8 @@ pub mod pallet {
         /// * [`QuoteAssetReservesWouldBeCompletelyDrained`](Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained)
         /// * [`SwappedAmountLessThanMinimumLimit`](Error::<T>::SwappedAmountLessThanMinimumLimit)
         /// * [`SwappedAmountMoreThanMaximumLimit`](Error::<T>::SwappedAmountMoreThanMaximumLimit)
+        /// * [`VammIsHalted`](Error::<T>::VammIsHalted)
+        /// * [`PriceDeviationExceedsLimit`](Error::<T>::PriceDeviationExceedsLimit)
         /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
         ///
         /// # Runtime
@@ -993,6 +1493,9 @@ pub mod pallet {
             // Tries to update twap before swapping assets.
             Self::try_update_twap(config.vamm_id, &mut vamm_state, None, &None)?;
 
+            // Record the price that prevailed until now before it changes.
+            Self::write_observation(config.vamm_id, &vamm_state, &None)?;
+
             // Delegate swap to helper function.
             let amount_swapped = Self::do_swap(config, &mut vamm_state)?;
 
@@ -1038,6 +1541,7 @@ pub mod pallet {
         /// * [`TradeExtrapolatesMaximumSupportedAmount`](Error::<T>::TradeExtrapolatesMaximumSupportedAmount)
         /// * [`SwappedAmountLessThanMinimumLimit`](Error::<T>::SwappedAmountLessThanMinimumLimit)
         /// * [`SwappedAmountMoreThanMaximumLimit`](Error::<T>::SwappedAmountMoreThanMaximumLimit)
+        /// * [`PriceDeviationExceedsLimit`](Error::<T>::PriceDeviationExceedsLimit)
         /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
         ///
         /// # Runtime
@@ -1054,6 +1558,190 @@ pub mod pallet {
             Ok(swap.swap_output)
         }
 
+        /// Performs several swaps atomically, possibly against different
+        /// vamms.
+        ///
+        /// # Overview
+        /// Callers touching several markets at once, such as basis trades or
+        /// liquidations, would otherwise need to call [`swap`](Self::swap)
+        /// repeatedly. This function applies all the given swaps in order,
+        /// each one against the reserves left by the previous ones, and either
+        /// all of them succeed or none of them takes effect.
+        ///
+        /// The checks which depend only on a vamm's state before the batch,
+        /// like being open, as well as the twap update and the price
+        /// observation, are done once per vamm. The checks regarding the
+        /// reserves of a vamm being drained are done once per vamm, against
+        /// its final state. Each swap still checks its own amount and
+        /// [`output_amount_limit`](traits::vamm::SwapConfig).
+        ///
+        /// The weight of a batch is linear in the number of swaps, see
+        /// [`WeightInfo::swap_batch`](crate::weights::WeightInfo::swap_batch).
+        ///
+        /// ## Parameters
+        ///  - `configs`: Specification for each swap, in the order they must
+        ///  be executed.
+        ///
+        /// ## Returns
+        /// The output of each swap, in the same order as `configs`.
+        ///
+        /// ## Assumptions or Requirements
+        /// * There can be at most [`MaxSwapsPerBatch`](Config::MaxSwapsPerBatch)
+        /// swaps in the batch.
+        /// * Every swap must fulfill the requirements of [`swap`](Self::swap).
+        ///
+        /// ## Emits
+        /// * [`Swapped`](Event::<T>::Swapped), once per swap.
+        ///
+        /// ## State Changes
+        /// Updates [`VammMap`] and [`Observations`] storage maps.
+        ///
+        /// ## Errors
+        /// * [`TooManySwapsInBatch`](Error::<T>::TooManySwapsInBatch)
+        /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
+        /// * [`VammIsClosed`](Error::<T>::VammIsClosed)
+        /// * [`InsufficientFundsForTrade`](Error::<T>::InsufficientFundsForTrade)
+        /// * [`TradeExtrapolatesMaximumSupportedAmount`](Error::<T>::TradeExtrapolatesMaximumSupportedAmount)
+        /// * [`BaseAssetReservesWouldBeCompletelyDrained`](Error::<T>::BaseAssetReservesWouldBeCompletelyDrained)
+        /// * [`QuoteAssetReservesWouldBeCompletelyDrained`](Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained)
+        /// * [`SwappedAmountLessThanMinimumLimit`](Error::<T>::SwappedAmountLessThanMinimumLimit)
+        /// * [`SwappedAmountMoreThanMaximumLimit`](Error::<T>::SwappedAmountMoreThanMaximumLimit)
+        /// * [`VammIsHalted`](Error::<T>::VammIsHalted)
+        /// * [`PriceDeviationExceedsLimit`](Error::<T>::PriceDeviationExceedsLimit)
+        /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+        ///
+        /// # Runtime
+        /// `O(n * m)`, where `n` is the number of swaps and `m` is the number
+        /// of distinct vamms in the batch.
+        #[transactional]
+        fn swap_batch(configs: &[SwapConfigOf<T>]) -> Result<Vec<SwapOutputOf<T>>, DispatchError> {
+            Self::do_swap_batch(configs)
+        }
+
+        /// Performs the swap of the desired asset against the vamm, receiving
+        /// an exact amount of it.
+        ///
+        /// # Overview
+        /// Contrary to [`swap`](Self::swap), where the caller specifies how
+        /// much of an asset to add to or remove from the vamm, in this function
+        /// the caller specifies exactly how much of the desired asset it wants
+        /// to receive, and the vamm charges whatever amount of the other asset
+        /// is needed for it, up to
+        /// [`input_amount_limit`](traits::vamm::SwapExactOutputConfig). This is
+        /// useful, for instance, to open positions sized in base asset units.
+        ///
+        /// The amount charged is rounded up, so that rounding errors are always
+        /// in favor of the vamm.
+        ///
+        /// ## Parameters
+        ///  - `config`: Specification for exact output swaps.
+        ///
+        /// ## Returns
+        /// The amount of the other asset the caller has to pay as a result of
+        /// the swap.
+        ///
+        /// E.g. If the caller wants to receive
+        /// [`base`](VammState::base_asset_reserves) asset, it will pay some
+        /// amount of [`quote`](VammState::quote_asset_reserves) asset (and
+        /// vice-versa).
+        ///
+        /// ## Assumptions or Requirements
+        /// * The requested [`VammId`](Config::VammId) must exists.
+        /// * The requested Vamm must be open.
+        /// * The vamm must have enough reserves of the desired asset.
+        /// * The amount charged must be at most
+        /// [`input_amount_limit`](traits::vamm::SwapExactOutputConfig).
+        ///
+        /// ## Emits
+        /// * [`Swapped`](Event::<T>::Swapped)
+        ///
+        /// ## State Changes
+        /// Updates [`VammMap`] and [`Observations`] storage maps.
+        ///
+        /// ## Errors
+        /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
+        /// * [`VammIsClosed`](Error::<T>::VammIsClosed)
+        /// * [`InsufficientFundsForTrade`](Error::<T>::InsufficientFundsForTrade)
+        /// * [`TradeExtrapolatesMaximumSupportedAmount`](Error::<T>::TradeExtrapolatesMaximumSupportedAmount)
+        /// * [`BaseAssetReservesWouldBeCompletelyDrained`](Error::<T>::BaseAssetReservesWouldBeCompletelyDrained)
+        /// * [`QuoteAssetReservesWouldBeCompletelyDrained`](Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained)
+        /// * [`SwappedAmountMoreThanMaximumLimit`](Error::<T>::SwappedAmountMoreThanMaximumLimit)
+        /// * [`VammIsHalted`](Error::<T>::VammIsHalted)
+        /// * [`PriceDeviationExceedsLimit`](Error::<T>::PriceDeviationExceedsLimit)
+        /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+        ///
+        /// # Runtime
+        /// `O(1)`
+        #[transactional]
+        fn swap_exact_output(
+            config: &SwapExactOutputConfigOf<T>,
+        ) -> Result<SwapOutputOf<T>, DispatchError> {
+            // Get Vamm state.
+            let mut vamm_state = Self::get_vamm_state(&config.vamm_id)?;
+
+            // Tries to update twap before swapping assets.
+            Self::try_update_twap(config.vamm_id, &mut vamm_state, None, &None)?;
+
+            // Record the price that prevailed until now before it changes.
+            Self::write_observation(config.vamm_id, &vamm_state, &None)?;
+
+            // Delegate swap to helper function.
+            Self::do_swap_exact_output(config, &mut vamm_state)
+        }
+
+        /// Performs the *simulation* of the exact output swap operation for
+        /// the desired asset against the vamm, returning the expected amount
+        /// the caller would have to pay if the swap were in fact executed.
+        ///
+        /// # Overview
+        /// This function essentially does the same as
+        /// [`swap_exact_output`](Self::swap_exact_output), except for the fact
+        /// that the runtime storage is not mutated.
+        ///
+        /// ## Parameters
+        ///  - `config`: Specification for exact output swaps.
+        ///
+        /// ## Returns
+        /// The *expected* asset amount the caller would pay for the swap,
+        /// taking into account slippage due to trade size.
+        ///
+        /// ## Assumptions or Requirements
+        /// * The requested [`VammId`](Config::VammId) must exist.
+        /// * The requested Vamm must be open.
+        /// * The vamm must have enough reserves of the desired asset.
+        ///
+        /// ## Emits
+        /// No event is emitted for this function.
+        ///
+        /// ## State Changes
+        /// This function does not mutate runtime storage.
+        ///
+        /// ## Errors
+        /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
+        /// * [`VammIsClosed`](Error::<T>::VammIsClosed)
+        /// * [`InsufficientFundsForTrade`](Error::<T>::InsufficientFundsForTrade)
+        /// * [`TradeExtrapolatesMaximumSupportedAmount`](Error::<T>::TradeExtrapolatesMaximumSupportedAmount)
+        /// * [`BaseAssetReservesWouldBeCompletelyDrained`](Error::<T>::BaseAssetReservesWouldBeCompletelyDrained)
+        /// * [`QuoteAssetReservesWouldBeCompletelyDrained`](Error::<T>::QuoteAssetReservesWouldBeCompletelyDrained)
+        /// * [`SwappedAmountMoreThanMaximumLimit`](Error::<T>::SwappedAmountMoreThanMaximumLimit)
+        /// * [`PriceDeviationExceedsLimit`](Error::<T>::PriceDeviationExceedsLimit)
+        /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+        ///
+        /// # Runtime
+        /// `O(1)`
+        fn swap_exact_output_simulation(
+            config: &SwapExactOutputConfigOf<T>,
+        ) -> Result<SwapOutputOf<T>, DispatchError> {
+            // Get Vamm state.
+            let vamm_state = Self::get_vamm_state(&config.vamm_id)?;
+
+            // Delegate swap to helper function.
+            let swap = Self::compute_swap_exact_output(config, &vamm_state)?;
+
+            // Return swap result.
+            Ok(swap.swap_output)
+        }
+
         /// Moves the price of a vamm to the desired values of
         /// [`base`](VammState::base_asset_reserves) and
         /// [`quote`](VammState::quote_asset_reserves) asset reserves.
@@ -1096,6 +1784,7 @@ pub mod pallet {
         /// [`base`](VammState::base_asset_reserves) and
         /// [`quote`](VammState::quote_asset_reserves) asset reserves as well as
         /// the invariant.
+        /// * [`Observations`], recording the price prior to the change.
         ///
         /// ## Errors
         /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
@@ -1121,6 +1810,9 @@ pub mod pallet {
             let invariant =
                 Self::compute_invariant(config.base_asset_reserves, config.quote_asset_reserves)?;
 
+            // Record the price that prevailed until now before it changes.
+            Self::write_observation(config.vamm_id, &vamm_state, &None)?;
+
             vamm_state.base_asset_reserves = config.base_asset_reserves;
             vamm_state.quote_asset_reserves = config.quote_asset_reserves;
             vamm_state.invariant = invariant;
@@ -1277,5 +1969,123 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Sets or removes the circuit breaker of the desired vamm.
+        ///
+        /// # Overview
+        /// Once set, the circuit breaker rejects every swap which would leave
+        /// the [`base`](VammState::base_asset_reserves) asset price deviating
+        /// by more than
+        /// [`max_price_deviation`](traits::vamm::CircuitBreakerConfig) from
+        /// either the price at the start of the block or the base asset twap.
+        ///
+        /// Additionally, if a
+        /// [`halt_period`](traits::vamm::CircuitBreakerConfig) is configured,
+        /// the price of the vamm is checked against the same references at
+        /// the start of each block, which catches price moves that didn't go
+        /// through a swap. If it deviates by more than allowed, the vamm is
+        /// halted and all swaps are rejected for `halt_period` seconds. Once
+        /// the halt is over, the base asset twap is reset to the current price.
+        ///
+        /// A swap rejected by the circuit breaker is reverted and emits no
+        /// event, so it never halts the vamm by itself: halts are only
+        /// triggered by the check at the start of each block. Since swaps can
+        /// never leave the price out of bounds, the halt is in practice driven
+        /// by price moves that didn't go through a swap and by the drift
+        /// between the price and the base asset twap over several blocks.
+        /// Simulations and queries, such as
+        /// [`swap_simulation`](Self::swap_simulation) or
+        /// [`get_price_impact`](Self::get_price_impact), reject the swaps
+        /// which would deviate too much, but they are not affected by halts.
+        ///
+        /// ## Parameters
+        ///  - [`vamm_id`](Config::VammId): The ID of the desired vamm.
+        ///  - `config`: The new circuit breaker configuration, or `None` to
+        ///  remove it.
+        ///
+        /// ## Assumptions or Requirements
+        /// * The requested [`VammId`](Config::VammId) must exist.
+        /// * The requested Vamm must be open.
+        /// * The `max_price_deviation` must be greater than zero.
+        /// * At most [`MaxCircuitBreakers`](Config::MaxCircuitBreakers) vamms
+        /// can have a circuit breaker.
+        ///
+        /// ## Emits
+        /// * [`CircuitBreakerUpdated`](Event::<T>::CircuitBreakerUpdated)
+        ///
+        /// ## State Changes
+        /// Updates [`CircuitBreakers`], [`BlockStartPrices`] and
+        /// [`HaltedUntil`] storage maps.
+        ///
+        /// ## Errors
+        /// * [`VammDoesNotExist`](Error::<T>::VammDoesNotExist)
+        /// * [`VammIsClosed`](Error::<T>::VammIsClosed)
+        /// * [`MaxPriceDeviationIsZero`](Error::<T>::MaxPriceDeviationIsZero)
+        /// * [`TooManyCircuitBreakers`](Error::<T>::TooManyCircuitBreakers)
+        /// * [`ArithmeticError`](sp_runtime::ArithmeticError)
+        ///
+        /// # Runtime
+        /// `O(1)`
+        #[transactional]
+        fn set_circuit_breaker(
+            vamm_id: T::VammId,
+            config: Option<CircuitBreakerConfigOf<T>>,
+        ) -> Result<(), DispatchError> {
+            // Get Vamm state.
+            let vamm_state = Self::get_vamm_state(&vamm_id)?;
+
+            match config {
+                Some(circuit_breaker) => {
+                    ensure!(
+                        !Self::is_vamm_closed(&vamm_state, &None),
+                        Error::<T>::VammIsClosed
+                    );
+                    ensure!(
+                        !circuit_breaker.max_price_deviation.is_zero(),
+                        Error::<T>::MaxPriceDeviationIsZero
+                    );
+                    ensure!(
+                        CircuitBreakers::<T>::contains_key(vamm_id) ||
+                            CircuitBreakers::<T>::count() < T::MaxCircuitBreakers::get(),
+                        Error::<T>::TooManyCircuitBreakers
+                    );
+
+                    CircuitBreakers::<T>::insert(vamm_id, circuit_breaker);
+                    BlockStartPrices::<T>::insert(
+                        vamm_id,
+                        Self::do_get_price(&vamm_state, AssetType::Base)?,
+                    );
+                },
+                None => {
+                    CircuitBreakers::<T>::remove(vamm_id);
+                    BlockStartPrices::<T>::remove(vamm_id);
+                    HaltedUntil::<T>::remove(vamm_id);
+                },
+            }
+
+            // Emit event.
+            Self::deposit_event(Event::<T>::CircuitBreakerUpdated { vamm_id, config });
+
+            Ok(())
+        }
+
+        /// Returns the weight of a single [`swap`](Self::swap), as given by
+        /// [`WeightInfo::swap`](crate::weights::WeightInfo::swap).
+        ///
+        /// # Runtime
+        /// `O(1)`
+        fn swap_weight() -> Weight {
+            T::WeightInfo::swap()
+        }
+
+        /// Returns the weight of a [`swap_batch`](Self::swap_batch) of `swaps`
+        /// swaps, as given by
+        /// [`WeightInfo::swap_batch`](crate::weights::WeightInfo::swap_batch).
+        ///
+        /// # Runtime
+        /// `O(1)`
+        fn swap_batch_weight(swaps: u32) -> Weight {
+            T::WeightInfo::swap_batch(swaps)
+        }
     }
 }
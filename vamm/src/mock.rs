@@ -119,6 +119,9 @@ impl pallet_timestamp::Config for MockRuntime {
 
 parameter_types! {
     pub const VammPalletId: PalletId = PalletId(*b"vamm____");
+    pub const MaxObservations: u32 = 16;
+    pub const MaxSwapsPerBatch: u32 = 8;
+    pub const MaxCircuitBreakers: u32 = 4;
 }
 
 impl pallet_vamm::Config for MockRuntime {
@@ -126,9 +129,13 @@ impl pallet_vamm::Config for MockRuntime {
     type Decimal = FixedU128;
     type Event = Event;
     type Integer = Integer;
+    type MaxObservations = MaxObservations;
+    type MaxSwapsPerBatch = MaxSwapsPerBatch;
+    type MaxCircuitBreakers = MaxCircuitBreakers;
     type Moment = Moment;
     type TimeProvider = Timestamp;
     type VammId = VammId;
+    type WeightInfo = ();
 }
 
 // ----------------------------------------------------------------------------------------------------
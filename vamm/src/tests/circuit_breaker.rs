@@ -0,0 +1,314 @@
+use crate::{
+    mock::{Balance, ExtBuilder, MaxCircuitBreakers, MockRuntime, System, TestPallet, VammId},
+    pallet::{BlockStartPrices, CircuitBreakers, Error, Event, HaltedUntil, VammMap},
+    tests::{
+        constants::INPUT_AMOUNT,
+        helpers::{as_decimal, create_vamm, run_for_seconds, with_existing_vamm_context},
+        types::{Decimal, TestVammConfig, Timestamp},
+    },
+    types::VammState,
+    CircuitBreakerConfigOf,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::Zero, FixedPointNumber};
+use traits::vamm::{
+    AssetType, Direction, MovePriceConfig, SwapConfig, SwapExactOutputConfig, Vamm as VammTrait,
+};
+
+// -------------------------------------------------------------------------------------------------
+//                                         Helper Functions
+// -------------------------------------------------------------------------------------------------
+
+const HALT_PERIOD: Timestamp = 60;
+
+fn circuit_breaker(halt_period: Option<Timestamp>) -> CircuitBreakerConfigOf<MockRuntime> {
+    CircuitBreakerConfigOf::<MockRuntime> {
+        // 10%
+        max_price_deviation: Decimal::saturating_from_rational(1, 10),
+        halt_period,
+    }
+}
+
+fn add_swap_config(asset: AssetType) -> SwapConfig<VammId, Balance> {
+    SwapConfig {
+        vamm_id: 0,
+        asset,
+        input_amount: INPUT_AMOUNT,
+        direction: Direction::Add,
+        output_amount_limit: None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                            Unit Tests
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn should_fail_if_vamm_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            TestPallet::set_circuit_breaker(0, Some(circuit_breaker(None))),
+            Error::<MockRuntime>::VammDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_vamm_is_closed() {
+    let vamm_state = VammState {
+        base_asset_reserves: as_decimal(4).into_inner(),
+        quote_asset_reserves: as_decimal(8).into_inner(),
+        peg_multiplier: 1,
+        closed: Some(Timestamp::MIN),
+        ..Default::default()
+    };
+    ExtBuilder {
+        vamm_count: 1,
+        vamms: vec![(0, vamm_state)],
+    }
+    .build()
+    .execute_with(|| {
+        run_for_seconds(1);
+        assert_noop!(
+            TestPallet::set_circuit_breaker(0, Some(circuit_breaker(None))),
+            Error::<MockRuntime>::VammIsClosed
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_max_price_deviation_is_zero() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_noop!(
+            TestPallet::set_circuit_breaker(
+                0,
+                Some(CircuitBreakerConfigOf::<MockRuntime> {
+                    max_price_deviation: Decimal::zero(),
+                    halt_period: None,
+                })
+            ),
+            Error::<MockRuntime>::MaxPriceDeviationIsZero
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_too_many_circuit_breakers() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        for vamm_id in 0..MaxCircuitBreakers::get() {
+            if vamm_id > 0 {
+                create_vamm(&TestVammConfig::default().into());
+            }
+            assert_ok!(TestPallet::set_circuit_breaker(
+                vamm_id.into(),
+                Some(circuit_breaker(None))
+            ));
+        }
+        create_vamm(&TestVammConfig::default().into());
+
+        assert_noop!(
+            TestPallet::set_circuit_breaker(
+                MaxCircuitBreakers::get().into(),
+                Some(circuit_breaker(None))
+            ),
+            Error::<MockRuntime>::TooManyCircuitBreakers
+        );
+        // Updating an existing circuit breaker is still allowed.
+        assert_ok!(TestPallet::set_circuit_breaker(
+            0,
+            Some(circuit_breaker(Some(HALT_PERIOD)))
+        ));
+    });
+}
+
+#[test]
+fn should_store_config_and_block_start_price_emitting_event() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(1);
+        let config = Some(circuit_breaker(Some(HALT_PERIOD)));
+
+        assert_ok!(TestPallet::set_circuit_breaker(0, config));
+
+        assert_eq!(TestPallet::get_circuit_breaker(0), config);
+        assert_eq!(TestPallet::get_block_start_price(0), Some(as_decimal(25)));
+        System::assert_last_event(Event::CircuitBreakerUpdated { vamm_id: 0, config }.into());
+    });
+}
+
+#[test]
+fn should_reject_swaps_deviating_from_block_start_price() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(TestPallet::set_circuit_breaker(
+            0,
+            Some(circuit_breaker(None))
+        ));
+
+        // Adding 1 base moves the price from 25 to ~11.1.
+        assert_noop!(
+            TestPallet::swap(&add_swap_config(AssetType::Base)),
+            Error::<MockRuntime>::PriceDeviationExceedsLimit
+        );
+        assert_noop!(
+            TestPallet::swap_batch(&[add_swap_config(AssetType::Base)]),
+            Error::<MockRuntime>::PriceDeviationExceedsLimit
+        );
+        assert_noop!(
+            TestPallet::swap_simulation(&add_swap_config(AssetType::Base)),
+            Error::<MockRuntime>::PriceDeviationExceedsLimit
+        );
+
+        // Removing 1 base moves the price from 25 to 100.
+        let remove_base_config = SwapExactOutputConfig {
+            vamm_id: 0,
+            asset: AssetType::Base,
+            output_amount: INPUT_AMOUNT,
+            input_amount_limit: None,
+        };
+        assert_noop!(
+            TestPallet::swap_exact_output(&remove_base_config),
+            Error::<MockRuntime>::PriceDeviationExceedsLimit
+        );
+        assert_noop!(
+            TestPallet::swap_exact_output_simulation(&remove_base_config),
+            Error::<MockRuntime>::PriceDeviationExceedsLimit
+        );
+
+        // Adding 1 quote moves the price from 25 to ~26.01.
+        assert_ok!(TestPallet::swap(&add_swap_config(AssetType::Quote)));
+    });
+}
+
+#[test]
+fn should_not_halt_simulations_and_queries() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(TestPallet::set_circuit_breaker(
+            0,
+            Some(circuit_breaker(Some(HALT_PERIOD)))
+        ));
+        HaltedUntil::<MockRuntime>::insert(0, TestPallet::now(&None) + HALT_PERIOD);
+
+        assert_noop!(
+            TestPallet::swap(&add_swap_config(AssetType::Quote)),
+            Error::<MockRuntime>::VammIsHalted
+        );
+        assert_ok!(TestPallet::swap_simulation(&add_swap_config(
+            AssetType::Quote
+        )));
+        assert_ok!(TestPallet::get_price_impact(&add_swap_config(
+            AssetType::Quote
+        )));
+    });
+}
+
+#[test]
+fn should_reject_swaps_deviating_from_twap() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(1);
+        assert_ok!(TestPallet::set_circuit_breaker(
+            0,
+            Some(circuit_breaker(None))
+        ));
+        assert_ok!(TestPallet::update_twap(0, Some(as_decimal(30))));
+
+        // The price stays within 10% of the block start price, but not of the
+        // twap.
+        assert_noop!(
+            TestPallet::swap(&add_swap_config(AssetType::Quote)),
+            Error::<MockRuntime>::PriceDeviationExceedsLimit
+        );
+    });
+}
+
+#[test]
+fn should_halt_vamm_on_abnormal_price_at_block_start() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(TestPallet::set_circuit_breaker(
+            0,
+            Some(circuit_breaker(Some(HALT_PERIOD)))
+        ));
+        // Halve the price without going through a swap.
+        assert_ok!(TestPallet::move_price(&MovePriceConfig {
+            vamm_id: 0,
+            base_asset_reserves: as_decimal(4).into_inner(),
+            quote_asset_reserves: as_decimal(50).into_inner(),
+        }));
+
+        run_for_seconds(1);
+        let halted_until = TestPallet::now(&None) + HALT_PERIOD;
+        assert_eq!(HaltedUntil::<MockRuntime>::get(0), Some(halted_until));
+        System::assert_has_event(
+            Event::CircuitBreakerTriggered {
+                vamm_id: 0,
+                price: Decimal::saturating_from_rational(25, 2),
+                halted_until,
+            }
+            .into(),
+        );
+        assert_noop!(
+            TestPallet::swap(&add_swap_config(AssetType::Quote)),
+            Error::<MockRuntime>::VammIsHalted
+        );
+
+        run_for_seconds(HALT_PERIOD - 1);
+        assert!(TestPallet::is_vamm_halted(&0, &None));
+
+        // Once the halt is over the twap is reset to the new price.
+        run_for_seconds(1);
+        assert!(!TestPallet::is_vamm_halted(&0, &None));
+        assert_eq!(HaltedUntil::<MockRuntime>::get(0), None);
+        assert_eq!(
+            VammMap::<MockRuntime>::get(0)
+                .unwrap()
+                .base_asset_twap
+                .get_twap(),
+            Decimal::saturating_from_rational(25, 2)
+        );
+        assert_ok!(TestPallet::swap(&add_swap_config(AssetType::Quote)));
+    });
+}
+
+#[test]
+fn should_not_halt_vamm_without_halt_period() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(TestPallet::set_circuit_breaker(
+            0,
+            Some(circuit_breaker(None))
+        ));
+        assert_ok!(TestPallet::move_price(&MovePriceConfig {
+            vamm_id: 0,
+            base_asset_reserves: as_decimal(4).into_inner(),
+            quote_asset_reserves: as_decimal(50).into_inner(),
+        }));
+
+        run_for_seconds(1);
+        assert!(!TestPallet::is_vamm_halted(&0, &None));
+        assert_eq!(
+            BlockStartPrices::<MockRuntime>::get(0),
+            Some(Decimal::saturating_from_rational(25, 2))
+        );
+    });
+}
+
+#[test]
+fn should_remove_circuit_breaker() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(1);
+        assert_ok!(TestPallet::set_circuit_breaker(
+            0,
+            Some(circuit_breaker(Some(HALT_PERIOD)))
+        ));
+
+        assert_ok!(TestPallet::set_circuit_breaker(0, None));
+
+        assert_eq!(CircuitBreakers::<MockRuntime>::count(), 0);
+        assert_eq!(BlockStartPrices::<MockRuntime>::get(0), None);
+        System::assert_last_event(
+            Event::CircuitBreakerUpdated {
+                vamm_id: 0,
+                config: None,
+            }
+            .into(),
+        );
+        assert_ok!(TestPallet::swap(&add_swap_config(AssetType::Base)));
+    });
+}
@@ -0,0 +1,113 @@
+use crate::{
+    mock::{ExtBuilder, MockRuntime, TestPallet},
+    pallet::Error,
+    tests::{
+        constants::RUN_CASES,
+        helpers::{as_decimal, run_for_seconds, with_existing_vamm_context},
+        types::{Decimal, TestVammConfig, Timestamp},
+    },
+    types::VammState,
+};
+use frame_support::{assert_noop, assert_ok, assert_storage_noop};
+use helpers::tests::default_acceptable_computation_error;
+use proptest::prelude::*;
+use sp_runtime::{
+    traits::{One, Zero},
+    FixedPointNumber,
+};
+use traits::vamm::{AssetType, Depth, Direction, SwapConfig, Vamm as VammTrait};
+
+// -------------------------------------------------------------------------------------------------
+//                                            Unit Tests
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn should_fail_if_vamm_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            TestPallet::get_depth(0, Decimal::from_rational(1, 100)),
+            Error::<MockRuntime>::VammDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_vamm_is_closed() {
+    let vamm_state = VammState {
+        base_asset_reserves: as_decimal(4).into_inner(),
+        quote_asset_reserves: as_decimal(8).into_inner(),
+        peg_multiplier: 1,
+        closed: Some(Timestamp::MIN),
+        ..Default::default()
+    };
+    ExtBuilder {
+        vamm_count: 1,
+        vamms: vec![(0, vamm_state)],
+    }
+    .build()
+    .execute_with(|| {
+        run_for_seconds(1);
+        assert_noop!(
+            TestPallet::get_depth(0, Decimal::from_rational(1, 100)),
+            Error::<MockRuntime>::VammIsClosed
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_price_change_is_too_large() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_noop!(
+            TestPallet::get_depth(0, Decimal::one()),
+            Error::<MockRuntime>::PriceChangeTooLarge
+        );
+    });
+}
+
+#[test]
+fn should_return_zero_depth_for_zero_price_change() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(
+            TestPallet::get_depth(0, Decimal::zero()),
+            Depth { up: 0, down: 0 }
+        );
+    });
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                             Proptests
+// -------------------------------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(RUN_CASES))]
+    #[test]
+    fn should_move_price_by_desired_amount(
+        price_change in 1..=99_u128,
+        move_up in any::<bool>(),
+    ) {
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            let price_change = Decimal::from_rational(price_change, 100);
+            assert_storage_noop!(TestPallet::get_depth(0, price_change));
+
+            let price = TestPallet::get_price(0, AssetType::Base).unwrap();
+            let depth = TestPallet::get_depth(0, price_change).unwrap();
+            let (input_amount, direction, target_price) = if move_up {
+                (depth.up, Direction::Remove, price + price * price_change)
+            } else {
+                (depth.down, Direction::Add, price - price * price_change)
+            };
+
+            assert_ok!(TestPallet::swap(&SwapConfig {
+                vamm_id: 0,
+                asset: AssetType::Base,
+                input_amount,
+                direction,
+                output_amount_limit: None,
+            }));
+            assert_ok!(default_acceptable_computation_error(
+                TestPallet::get_price(0, AssetType::Base).unwrap().into_inner(),
+                target_price.into_inner()
+            ));
+        });
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    mock::{ExtBuilder, MockRuntime, TestPallet},
+    pallet::Error,
+    tests::{
+        constants::RUN_CASES,
+        helpers::{as_decimal, with_existing_vamm_context},
+        types::{Decimal, TestSwapConfig, TestVammConfig},
+    },
+};
+use frame_support::{assert_noop, assert_ok, assert_storage_noop};
+use proptest::prelude::*;
+use sp_runtime::{traits::Zero, FixedPointNumber};
+use traits::vamm::{AssetType, Direction, SwapConfig, Vamm as VammTrait};
+
+// -------------------------------------------------------------------------------------------------
+//                                            Unit Tests
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn should_fail_if_vamm_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            TestPallet::get_price_impact(&TestSwapConfig::default().into()),
+            Error::<MockRuntime>::VammDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_swap_is_invalid() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_noop!(
+            TestPallet::get_price_impact(&SwapConfig {
+                input_amount: TestVammConfig::default().base_asset_reserves,
+                direction: Direction::Remove,
+                output_amount_limit: None,
+                ..TestSwapConfig::default().into()
+            }),
+            Error::<MockRuntime>::InsufficientFundsForTrade
+        );
+    });
+}
+
+#[test]
+fn should_return_relative_price_change() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        // Removing 1 base from a vamm with 2 base and 50 quote leaves 1 base
+        // and 100 quote, so the price goes from 25 to 100.
+        assert_ok!(
+            TestPallet::get_price_impact(&SwapConfig {
+                direction: Direction::Remove,
+                output_amount_limit: None,
+                ..TestSwapConfig::default().into()
+            }),
+            as_decimal(3)
+        );
+    });
+}
+
+#[test]
+fn should_return_zero_for_empty_swap() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(
+            TestPallet::get_price_impact(&SwapConfig {
+                input_amount: 0,
+                output_amount_limit: None,
+                ..TestSwapConfig::default().into()
+            }),
+            Decimal::zero()
+        );
+    });
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                             Proptests
+// -------------------------------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(RUN_CASES))]
+    #[test]
+    fn should_grow_with_swap_size(
+        small in 1..=TestVammConfig::default().base_asset_reserves / 2,
+        extra in 1..=TestVammConfig::default().base_asset_reserves / 4,
+        is_base in any::<bool>(),
+        is_add in any::<bool>(),
+    ) {
+        let asset = if is_base { AssetType::Base } else { AssetType::Quote };
+        let direction = if is_add { Direction::Add } else { Direction::Remove };
+        let config = |input_amount| SwapConfig {
+            vamm_id: 0,
+            asset,
+            input_amount,
+            direction,
+            output_amount_limit: None,
+        };
+
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            assert_storage_noop!(TestPallet::get_price_impact(&config(small)));
+
+            let small_impact = TestPallet::get_price_impact(&config(small)).unwrap();
+            let large_impact = TestPallet::get_price_impact(&config(small + extra)).unwrap();
+            assert!(small_impact <= large_impact);
+        });
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    mock::{ExtBuilder, MockRuntime, TestPallet},
+    pallet::Error,
+    tests::{
+        constants::{BASE_ASSET_RESERVES, QUOTE_ASSET_RESERVES, RUN_CASES},
+        helpers::{as_decimal, as_decimal_inner, run_for_seconds, with_existing_vamm_context},
+        types::{Decimal, TestVammConfig, Timestamp},
+    },
+    types::VammState,
+};
+use frame_support::{assert_noop, assert_ok, assert_storage_noop};
+use helpers::tests::default_acceptable_computation_error;
+use proptest::prelude::*;
+use sp_core::U256;
+use sp_runtime::FixedPointNumber;
+use traits::vamm::{AssetType, Reserves, Vamm as VammTrait};
+
+// -------------------------------------------------------------------------------------------------
+//                                            Unit Tests
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn should_fail_if_vamm_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            TestPallet::get_reserves_for_price(0, as_decimal(1)),
+            Error::<MockRuntime>::VammDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_vamm_is_closed() {
+    let vamm_state = VammState {
+        base_asset_reserves: as_decimal(4).into_inner(),
+        quote_asset_reserves: as_decimal(8).into_inner(),
+        peg_multiplier: 1,
+        closed: Some(Timestamp::MIN),
+        ..Default::default()
+    };
+    ExtBuilder {
+        vamm_count: 1,
+        vamms: vec![(0, vamm_state)],
+    }
+    .build()
+    .execute_with(|| {
+        run_for_seconds(1);
+        assert_noop!(
+            TestPallet::get_reserves_for_price(0, as_decimal(1)),
+            Error::<MockRuntime>::VammIsClosed
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_target_price_is_zero() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_noop!(
+            TestPallet::get_reserves_for_price(0, Decimal::from_inner(0)),
+            Error::<MockRuntime>::TargetPriceIsZero
+        );
+    });
+}
+
+#[test]
+fn should_return_current_reserves_for_current_price() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(
+            TestPallet::get_reserves_for_price(0, as_decimal(25)),
+            Reserves {
+                base_asset_reserves: BASE_ASSET_RESERVES,
+                quote_asset_reserves: QUOTE_ASSET_RESERVES,
+            }
+        );
+    });
+}
+
+#[test]
+fn should_return_reserves_for_target_price() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        // k = 2 * 50 = 100, so for a price of 100 both reserves must be
+        // sqrt(100 / 100) = 1 and 100 / 1 = 100.
+        assert_ok!(
+            TestPallet::get_reserves_for_price(0, as_decimal(100)),
+            Reserves {
+                base_asset_reserves: as_decimal_inner(1),
+                quote_asset_reserves: as_decimal_inner(100),
+            }
+        );
+    });
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                             Proptests
+// -------------------------------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(RUN_CASES))]
+    #[test]
+    fn should_keep_invariant_and_reach_target_price(
+        target_price in as_decimal_inner(1) / 100..=as_decimal_inner(1_000_000),
+    ) {
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            let target_price = Decimal::from_inner(target_price);
+            assert_storage_noop!(TestPallet::get_reserves_for_price(0, target_price));
+
+            let reserves = TestPallet::get_reserves_for_price(0, target_price).unwrap();
+            let vamm_state = TestPallet::get_vamm(0).unwrap();
+            let new_vamm_state = VammState {
+                base_asset_reserves: reserves.base_asset_reserves,
+                quote_asset_reserves: reserves.quote_asset_reserves,
+                ..vamm_state
+            };
+
+            let invariant = U256::from(reserves.base_asset_reserves)
+                * U256::from(reserves.quote_asset_reserves);
+            assert!(invariant <= vamm_state.invariant);
+            assert_ok!(default_acceptable_computation_error(
+                invariant.as_u128(),
+                vamm_state.invariant.as_u128()
+            ));
+            assert_ok!(default_acceptable_computation_error(
+                TestPallet::do_get_price(&new_vamm_state, AssetType::Base)
+                    .unwrap()
+                    .into_inner(),
+                target_price.into_inner()
+            ));
+        });
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{
+    mock::{ExtBuilder, MaxObservations, MockRuntime, TestPallet},
+    pallet::Error,
+    tests::{
+        constants::{BASE_ASSET_RESERVES, QUOTE_ASSET_RESERVES, RUN_CASES},
+        helpers::{as_decimal, as_decimal_inner, run_for_seconds, with_existing_vamm_context},
+        types::{Decimal, TestSwapConfig, TestVammConfig, Timestamp},
+    },
+    types::VammState,
+};
+use frame_support::{assert_noop, assert_ok, assert_storage_noop};
+use proptest::prelude::*;
+use sp_runtime::FixedPointNumber;
+use traits::vamm::{MovePriceConfig, Vamm as VammTrait};
+
+// -------------------------------------------------------------------------------------------------
+//                                         Helper Functions
+// -------------------------------------------------------------------------------------------------
+
+fn move_quote_reserves_to(quote_asset_reserves: u128) {
+    assert_ok!(TestPallet::move_price(&MovePriceConfig {
+        vamm_id: 0,
+        base_asset_reserves: BASE_ASSET_RESERVES,
+        quote_asset_reserves,
+    }));
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                            Unit Tests
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn should_fail_if_vamm_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            TestPallet::get_twap_over(0, 10),
+            Error::<MockRuntime>::VammDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_vamm_is_closed() {
+    let vamm_state = VammState {
+        base_asset_reserves: as_decimal(4).into_inner(),
+        quote_asset_reserves: as_decimal(8).into_inner(),
+        peg_multiplier: 1,
+        closed: Some(Timestamp::MIN),
+        ..Default::default()
+    };
+    ExtBuilder {
+        vamm_count: 1,
+        vamms: vec![(0, vamm_state)],
+    }
+    .build()
+    .execute_with(|| {
+        run_for_seconds(1);
+        assert_noop!(
+            TestPallet::get_twap_over(0, 1),
+            Error::<MockRuntime>::VammIsClosed
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_window_is_zero() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(10);
+        assert_noop!(
+            TestPallet::get_twap_over(0, 0),
+            Error::<MockRuntime>::TwapWindowIsZero
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_window_is_older_than_history() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(10);
+        assert_noop!(
+            TestPallet::get_twap_over(0, 11),
+            Error::<MockRuntime>::InsufficientObservationHistory
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_vamm_has_no_observations() {
+    let vamm_state = VammState {
+        base_asset_reserves: as_decimal(4).into_inner(),
+        quote_asset_reserves: as_decimal(8).into_inner(),
+        peg_multiplier: 1,
+        ..Default::default()
+    };
+    ExtBuilder {
+        vamm_count: 1,
+        vamms: vec![(0, vamm_state)],
+    }
+    .build()
+    .execute_with(|| {
+        run_for_seconds(10);
+        assert_noop!(
+            TestPallet::get_twap_over(0, 1),
+            Error::<MockRuntime>::InsufficientObservationHistory
+        );
+    });
+}
+
+#[test]
+fn should_return_current_price_if_it_did_not_change() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(100);
+        let price = TestPallet::get_price(0, traits::vamm::AssetType::Base).unwrap();
+        assert_eq!(TestPallet::get_twap_over(0, 1), Ok(price));
+        assert_eq!(TestPallet::get_twap_over(0, 50), Ok(price));
+        assert_eq!(TestPallet::get_twap_over(0, 100), Ok(price));
+    });
+}
+
+#[test]
+fn should_compute_arithmetic_twap_across_price_changes() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        // Price is 25 from t = 0 until t = 100.
+        run_for_seconds(100);
+        move_quote_reserves_to(QUOTE_ASSET_RESERVES * 2);
+
+        // Price is 50 from t = 100 until t = 200.
+        run_for_seconds(100);
+
+        assert_eq!(TestPallet::get_twap_over(0, 100), Ok(as_decimal(50)));
+        assert_eq!(
+            TestPallet::get_twap_over(0, 200),
+            Ok(Decimal::from_inner(as_decimal_inner(75) / 2))
+        );
+        assert_eq!(
+            TestPallet::get_twap_over(0, 150),
+            Ok(Decimal::from_inner(
+                (as_decimal_inner(25) * 50 + as_decimal_inner(50) * 100) / 150
+            ))
+        );
+    });
+}
+
+#[test]
+fn should_record_observation_when_swapping() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(10);
+        assert_ok!(TestPallet::swap(&TestSwapConfig::default().into()));
+
+        let observations = TestPallet::chronological_observations(0);
+        assert_eq!(observations.len(), 2);
+        assert_eq!(observations[1].timestamp, 10);
+        assert_eq!(
+            observations[1].base_price_cumulative,
+            as_decimal_inner(25 * 10).into()
+        );
+    });
+}
+
+#[test]
+fn should_record_at_most_one_observation_per_timestamp() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(10);
+        move_quote_reserves_to(QUOTE_ASSET_RESERVES * 2);
+        move_quote_reserves_to(QUOTE_ASSET_RESERVES * 3);
+
+        assert_eq!(TestPallet::chronological_observations(0).len(), 2);
+    });
+}
+
+#[test]
+fn should_overwrite_oldest_observation_when_buffer_is_full() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        let max_observations = MaxObservations::get();
+        for i in 1..=max_observations {
+            run_for_seconds(10);
+            move_quote_reserves_to(QUOTE_ASSET_RESERVES + as_decimal_inner(i.into()));
+        }
+
+        let observations = TestPallet::chronological_observations(0);
+        assert_eq!(observations.len(), max_observations as usize);
+        // The observation recorded at creation was overwritten.
+        assert_eq!(observations[0].timestamp, 10);
+        assert!(observations
+            .windows(2)
+            .all(|pair| pair[0].timestamp < pair[1].timestamp));
+
+        run_for_seconds(10);
+        let history: Timestamp = observations[0].timestamp;
+        let now: Timestamp = u64::from(max_observations + 1) * 10;
+        assert_ok!(TestPallet::get_twap_over(0, now - history));
+        assert_noop!(
+            TestPallet::get_twap_over(0, now - history + 1),
+            Error::<MockRuntime>::InsufficientObservationHistory
+        );
+    });
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                             Proptests
+// -------------------------------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(RUN_CASES))]
+    #[test]
+    fn should_succeed_not_modifying_storage(
+        elapsed in 1..=1_000_000_u64,
+        window in 1..=1_000_000_u64,
+    ) {
+        prop_assume!(window <= elapsed);
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            run_for_seconds(elapsed);
+            assert_ok!(TestPallet::get_twap_over(0, window));
+            assert_storage_noop!(TestPallet::get_twap_over(0, window));
+        });
+    }
+
+    #[test]
+    fn should_be_bounded_by_min_and_max_prices(
+        first_period in 1..=10_000_u64,
+        second_period in 1..=10_000_u64,
+        quote_multiplier in 1..=100_u128,
+    ) {
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            let first_price = TestPallet::get_price(0, traits::vamm::AssetType::Base).unwrap();
+            run_for_seconds(first_period);
+            move_quote_reserves_to(QUOTE_ASSET_RESERVES * quote_multiplier);
+            let second_price = TestPallet::get_price(0, traits::vamm::AssetType::Base).unwrap();
+            run_for_seconds(second_period);
+
+            let twap = TestPallet::get_twap_over(0, first_period + second_period).unwrap();
+            assert!(first_price.min(second_price) <= twap);
+            assert!(twap <= first_price.max(second_price));
+        });
+    }
+}
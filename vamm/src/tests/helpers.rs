@@ -28,6 +28,7 @@ pub fn run_to_block(n: u64) {
         let _ = TimestampPallet::set(Origin::none(), SystemPallet::block_number() * 1000);
         SystemPallet::on_initialize(SystemPallet::block_number());
         TimestampPallet::on_initialize(SystemPallet::block_number());
+        TestPallet::on_initialize(SystemPallet::block_number());
     }
 }
 
@@ -46,6 +47,7 @@ pub fn run_for_seconds(seconds: u64) {
     );
     SystemPallet::on_initialize(SystemPallet::block_number());
     TimestampPallet::on_initialize(SystemPallet::block_number());
+    TestPallet::on_initialize(SystemPallet::block_number());
 }
 
 pub fn as_decimal(x: u128) -> Decimal {
@@ -1,17 +1,24 @@
 // Allow use of .unwrap() in tests and unused Results from function calls
 #![allow(clippy::disallowed_methods, unused_must_use, dead_code)]
 
+mod circuit_breaker;
 mod close;
 mod compute_invariant;
 mod constants;
 mod create_vamm;
+mod get_depth;
 mod get_price;
+mod get_price_impact;
+mod get_reserves_for_price;
 mod get_settlement_price;
 mod get_twap;
+mod get_twap_over;
 mod helpers;
 mod helpers_propcompose;
 mod move_price;
 mod swap;
+mod swap_batch;
+mod swap_exact_output;
 mod swap_simulation;
 mod types;
 mod update_twap;
@@ -0,0 +1,215 @@
+use crate::{
+    mock::{Balance, MaxSwapsPerBatch, MockRuntime, System, TestPallet, VammId},
+    pallet::{Error, Event, VammMap},
+    tests::{
+        constants::{QUOTE_REQUIRED_FOR_REMOVING_BASE, RUN_CASES},
+        helpers::{
+            create_vamm, run_for_seconds, run_to_block, swap_config, with_existing_vamm_context,
+        },
+        types::{TestSwapConfig, TestVammConfig},
+    },
+    types::VammState,
+    weights::WeightInfo,
+};
+use frame_support::{assert_noop, assert_ok};
+use proptest::prelude::*;
+use traits::vamm::{AssetType, Direction, SwapConfig, SwapOutput, Vamm as VammTrait};
+
+// -------------------------------------------------------------------------------------------------
+//                                         Helper Functions
+// -------------------------------------------------------------------------------------------------
+
+fn leg(vamm_id: VammId, asset: AssetType, direction: Direction) -> SwapConfig<VammId, Balance> {
+    SwapConfig {
+        vamm_id,
+        asset,
+        direction,
+        output_amount_limit: None,
+        ..TestSwapConfig::default().into()
+    }
+}
+
+fn reserves(vamm_id: VammId) -> (Balance, Balance) {
+    let VammState {
+        base_asset_reserves,
+        quote_asset_reserves,
+        ..
+    } = VammMap::<MockRuntime>::get(vamm_id).unwrap();
+    (base_asset_reserves, quote_asset_reserves)
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                            Unit Tests
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn should_succeed_with_empty_batch() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(TestPallet::swap_batch(&[]), vec![]);
+    });
+}
+
+#[test]
+fn should_fail_if_batch_is_too_large() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        let configs =
+            vec![leg(0, AssetType::Base, Direction::Add); MaxSwapsPerBatch::get() as usize + 1];
+        assert_noop!(
+            TestPallet::swap_batch(&configs),
+            Error::<MockRuntime>::TooManySwapsInBatch
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_any_vamm_does_not_exist() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_noop!(
+            TestPallet::swap_batch(&[
+                leg(0, AssetType::Base, Direction::Add),
+                leg(1, AssetType::Base, Direction::Add),
+            ]),
+            Error::<MockRuntime>::VammDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn should_revert_previous_legs_if_a_leg_fails() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(10);
+        assert_noop!(
+            TestPallet::swap_batch(&[
+                leg(0, AssetType::Quote, Direction::Add),
+                SwapConfig {
+                    output_amount_limit: Some(QUOTE_REQUIRED_FOR_REMOVING_BASE),
+                    ..leg(0, AssetType::Base, Direction::Remove)
+                },
+            ]),
+            Error::<MockRuntime>::SwappedAmountMoreThanMaximumLimit
+        );
+    });
+}
+
+#[test]
+fn should_match_sequential_swaps_on_the_same_vamm() {
+    let configs = [
+        leg(0, AssetType::Base, Direction::Add),
+        leg(0, AssetType::Quote, Direction::Remove),
+        leg(0, AssetType::Base, Direction::Remove),
+    ];
+
+    let mut sequential = (vec![], (0, 0));
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        sequential = (
+            configs
+                .iter()
+                .map(|config| TestPallet::swap(config).unwrap())
+                .collect(),
+            reserves(0),
+        );
+    });
+
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_ok!(TestPallet::swap_batch(&configs), sequential.0);
+        assert_eq!(reserves(0), sequential.1);
+    });
+}
+
+#[test]
+fn should_swap_across_multiple_vamms_emitting_one_event_per_leg() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        create_vamm(&TestVammConfig::default().into());
+        // For event emission
+        run_to_block(1);
+        let events_before = System::events().len();
+
+        let configs = [
+            leg(0, AssetType::Base, Direction::Add),
+            leg(1, AssetType::Base, Direction::Remove),
+        ];
+        let outputs = TestPallet::swap_batch(&configs).unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert!(!outputs[0].negative);
+        assert!(outputs[1].negative);
+        assert_eq!(System::events().len(), events_before + 2);
+        System::assert_has_event(
+            Event::Swapped {
+                vamm_id: 0,
+                input_amount: configs[0].input_amount,
+                output_amount: outputs[0],
+                input_asset_type: AssetType::Base,
+                direction: Direction::Add,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::Swapped {
+                vamm_id: 1,
+                input_amount: configs[1].input_amount,
+                output_amount: SwapOutput {
+                    output: QUOTE_REQUIRED_FOR_REMOVING_BASE,
+                    negative: true,
+                },
+                input_asset_type: AssetType::Base,
+                direction: Direction::Remove,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn should_record_a_single_observation_per_vamm() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        run_for_seconds(10);
+        assert_ok!(TestPallet::swap_batch(&[
+            leg(0, AssetType::Base, Direction::Add),
+            leg(0, AssetType::Base, Direction::Remove),
+        ]));
+
+        assert_eq!(TestPallet::chronological_observations(0).len(), 2);
+    });
+}
+
+#[test]
+fn should_charge_weight_per_leg() {
+    let weight_of_one = <() as WeightInfo>::swap_batch(1);
+    assert_eq!(weight_of_one, <() as WeightInfo>::swap());
+    assert_eq!(<() as WeightInfo>::swap_batch(3), weight_of_one * 3);
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                             Proptests
+// -------------------------------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(RUN_CASES))]
+    #[test]
+    fn should_be_equivalent_to_sequential_swaps(
+        configs in prop::collection::vec(swap_config(), 1..=MaxSwapsPerBatch::get() as usize)
+    ) {
+        let configs: Vec<_> = configs
+            .into_iter()
+            .map(|config| SwapConfig { output_amount_limit: None, ..config })
+            .collect();
+
+        let mut sequential = None;
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            sequential = configs
+                .iter()
+                .map(TestPallet::swap)
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+                .map(|outputs| (outputs, reserves(0)));
+        });
+
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            let batch = TestPallet::swap_batch(&configs)
+                .ok()
+                .map(|outputs| (outputs, reserves(0)));
+            assert_eq!(batch, sequential);
+        });
+    }
+}
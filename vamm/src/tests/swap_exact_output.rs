@@ -0,0 +1,251 @@
+use crate::{
+    mock::{Balance, ExtBuilder, MockRuntime, System, TestPallet, VammId},
+    pallet::{Error, Event, VammMap},
+    tests::{
+        constants::{
+            BASE_ASSET_RESERVES, BASE_REQUIRED_FOR_REMOVING_QUOTE, INPUT_AMOUNT,
+            QUOTE_ASSET_RESERVES, QUOTE_REQUIRED_FOR_REMOVING_BASE, RUN_CASES,
+        },
+        helpers::{as_decimal, run_for_seconds, run_to_block, with_existing_vamm_context},
+        types::{TestVammConfig, Timestamp},
+    },
+    types::VammState,
+    SwapExactOutputConfigOf,
+};
+use frame_support::{assert_noop, assert_ok, assert_storage_noop};
+use proptest::prelude::*;
+use sp_core::U256;
+use sp_runtime::FixedPointNumber;
+use traits::vamm::{AssetType, Direction, SwapConfig, SwapOutput, Vamm as VammTrait};
+
+// -------------------------------------------------------------------------------------------------
+//                                         Helper Functions
+// -------------------------------------------------------------------------------------------------
+
+fn exact_output_config(
+    asset: AssetType,
+    output_amount: Balance,
+) -> SwapExactOutputConfigOf<MockRuntime> {
+    SwapExactOutputConfigOf::<MockRuntime> {
+        vamm_id: 0,
+        asset,
+        output_amount,
+        input_amount_limit: None,
+    }
+}
+
+fn reserves_product(vamm_id: VammId) -> U256 {
+    let vamm_state = VammMap::<MockRuntime>::get(vamm_id).unwrap();
+    U256::from(vamm_state.base_asset_reserves) * U256::from(vamm_state.quote_asset_reserves)
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                            Unit Tests
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn should_fail_if_vamm_does_not_exist() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            TestPallet::swap_exact_output(&exact_output_config(AssetType::Base, INPUT_AMOUNT)),
+            Error::<MockRuntime>::VammDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_vamm_is_closed() {
+    let vamm_state = VammState {
+        base_asset_reserves: as_decimal(4).into_inner(),
+        quote_asset_reserves: as_decimal(8).into_inner(),
+        peg_multiplier: 1,
+        closed: Some(Timestamp::MIN),
+        ..Default::default()
+    };
+    ExtBuilder {
+        vamm_count: 1,
+        vamms: vec![(0, vamm_state)],
+    }
+    .build()
+    .execute_with(|| {
+        run_for_seconds(1);
+        assert_noop!(
+            TestPallet::swap_exact_output(&exact_output_config(AssetType::Base, INPUT_AMOUNT)),
+            Error::<MockRuntime>::VammIsClosed
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_output_drains_reserves() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_noop!(
+            TestPallet::swap_exact_output(&exact_output_config(
+                AssetType::Base,
+                BASE_ASSET_RESERVES
+            )),
+            Error::<MockRuntime>::InsufficientFundsForTrade
+        );
+        assert_noop!(
+            TestPallet::swap_exact_output(&exact_output_config(
+                AssetType::Quote,
+                QUOTE_ASSET_RESERVES
+            )),
+            Error::<MockRuntime>::InsufficientFundsForTrade
+        );
+    });
+}
+
+#[test]
+fn should_fail_if_required_input_exceeds_limit() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        assert_noop!(
+            TestPallet::swap_exact_output(&SwapExactOutputConfigOf::<MockRuntime> {
+                input_amount_limit: Some(QUOTE_REQUIRED_FOR_REMOVING_BASE - 1),
+                ..exact_output_config(AssetType::Base, INPUT_AMOUNT)
+            }),
+            Error::<MockRuntime>::SwappedAmountMoreThanMaximumLimit
+        );
+    });
+}
+
+#[test]
+fn should_succeed_returning_correct_values_and_emitting_events_base() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        // For event emission
+        run_to_block(1);
+
+        let config = SwapExactOutputConfigOf::<MockRuntime> {
+            input_amount_limit: Some(QUOTE_REQUIRED_FOR_REMOVING_BASE),
+            ..exact_output_config(AssetType::Base, INPUT_AMOUNT)
+        };
+        let swap = TestPallet::swap_exact_output(&config);
+        assert_ok!(
+            swap,
+            SwapOutput {
+                output: QUOTE_REQUIRED_FOR_REMOVING_BASE,
+                negative: true
+            }
+        );
+
+        let vamm_after_swap = VammMap::<MockRuntime>::get(0).unwrap();
+        assert_eq!(
+            vamm_after_swap.base_asset_reserves,
+            BASE_ASSET_RESERVES - INPUT_AMOUNT
+        );
+        assert_eq!(
+            vamm_after_swap.quote_asset_reserves,
+            QUOTE_ASSET_RESERVES + QUOTE_REQUIRED_FOR_REMOVING_BASE
+        );
+        System::assert_last_event(
+            Event::Swapped {
+                vamm_id: 0,
+                input_amount: INPUT_AMOUNT,
+                output_amount: swap.unwrap(),
+                input_asset_type: AssetType::Base,
+                direction: Direction::Remove,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn should_succeed_returning_correct_values_and_emitting_events_quote() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        // For event emission
+        run_to_block(1);
+
+        let config = exact_output_config(AssetType::Quote, INPUT_AMOUNT);
+        let swap = TestPallet::swap_exact_output(&config);
+        // The regular swap rounds the required base asset down, while the
+        // exact output swap rounds it up.
+        assert_ok!(
+            swap,
+            SwapOutput {
+                output: BASE_REQUIRED_FOR_REMOVING_QUOTE + 1,
+                negative: true
+            }
+        );
+
+        let vamm_after_swap = VammMap::<MockRuntime>::get(0).unwrap();
+        assert_eq!(
+            vamm_after_swap.base_asset_reserves,
+            BASE_ASSET_RESERVES + BASE_REQUIRED_FOR_REMOVING_QUOTE + 1
+        );
+        assert_eq!(
+            vamm_after_swap.quote_asset_reserves,
+            QUOTE_ASSET_RESERVES - INPUT_AMOUNT
+        );
+        System::assert_last_event(
+            Event::Swapped {
+                vamm_id: 0,
+                input_amount: INPUT_AMOUNT,
+                output_amount: swap.unwrap(),
+                input_asset_type: AssetType::Quote,
+                direction: Direction::Remove,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn should_simulate_without_modifying_storage() {
+    with_existing_vamm_context(TestVammConfig::default(), || {
+        let config = exact_output_config(AssetType::Quote, INPUT_AMOUNT);
+        assert_storage_noop!(TestPallet::swap_exact_output_simulation(&config));
+
+        let simulation = TestPallet::swap_exact_output_simulation(&config);
+        assert_eq!(simulation, TestPallet::swap_exact_output(&config));
+    });
+}
+
+// -------------------------------------------------------------------------------------------------
+//                                             Proptests
+// -------------------------------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(RUN_CASES))]
+    #[test]
+    fn should_never_cost_less_than_regular_removal(
+        output_amount in 1..QUOTE_ASSET_RESERVES,
+        is_base in any::<bool>(),
+    ) {
+        let asset = if is_base { AssetType::Base } else { AssetType::Quote };
+        prop_assume!(!is_base || output_amount < BASE_ASSET_RESERVES);
+
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            let regular = TestPallet::swap_simulation(&SwapConfig {
+                vamm_id: 0,
+                asset,
+                input_amount: output_amount,
+                direction: Direction::Remove,
+                output_amount_limit: None,
+            });
+            let exact = TestPallet::swap_exact_output_simulation(
+                &exact_output_config(asset, output_amount),
+            );
+
+            if let (Ok(regular), Ok(exact)) = (regular, exact) {
+                assert!(exact.output >= regular.output);
+            }
+        });
+    }
+
+    #[test]
+    fn should_never_decrease_invariant(
+        output_amount in 1..QUOTE_ASSET_RESERVES,
+        is_base in any::<bool>(),
+    ) {
+        let asset = if is_base { AssetType::Base } else { AssetType::Quote };
+        prop_assume!(!is_base || output_amount < BASE_ASSET_RESERVES);
+
+        with_existing_vamm_context(TestVammConfig::default(), || {
+            let invariant = reserves_product(0);
+            if TestPallet::swap_exact_output(&exact_output_config(asset, output_amount)).is_ok() {
+                assert!(reserves_product(0) >= invariant);
+            }
+        });
+    }
+}
@@ -47,6 +47,25 @@ pub struct VammState<Balance, Moment, Twap> {
     pub base_asset_twap: Twap,
 }
 
+/// A single entry of the price observations ring buffer kept for each vamm.
+///
+/// Each observation stores the accumulated sum of the
+/// [`base`](traits::vamm::AssetType::Base) asset price multiplied by the
+/// number of seconds it prevailed, from the vamm's creation up to
+/// [`timestamp`](Self::timestamp). Given two observations, the arithmetic time
+/// weighted average price between them is simply the difference of their
+/// cumulative prices divided by the time elapsed between them.
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Observation<Moment> {
+    /// The time at which this observation was recorded.
+    pub timestamp: Moment,
+
+    /// The cumulative base asset price (in the inner representation of the
+    /// decimal type) times seconds elapsed, up to `timestamp`.
+    pub base_price_cumulative: U256,
+}
+
 /// Represents the closing state of the vamm.
 pub enum ClosingState {
     /// The vamm is open. All functionalities are working without restriction.
@@ -1,21 +1,45 @@
-use frame_support::weights::Weight;
+use frame_support::{traits::Get, weights::Weight};
 use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
-	fn test() -> Weight;
+    fn test() -> Weight;
+    fn swap() -> Weight;
+    fn swap_batch(n: u32) -> Weight;
 }
 
 /// Weights for pallet_vamm using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	fn test() -> Weight {
-		1_000 as Weight
-	}
+    fn test() -> Weight {
+        1_000 as Weight
+    }
+    fn swap() -> Weight {
+        1_000 as Weight
+    }
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Vamm VammMap (r:1 w:1)
+    // Storage: Vamm Observations (r:1 w:1)
+    // Storage: Vamm ObservationIndex (r:1 w:1)
+    // Storage: Vamm HaltedUntil (r:1 w:0)
+    // Storage: Vamm CircuitBreakers (r:1 w:0)
+    // Storage: Vamm BlockStartPrices (r:1 w:0)
+    fn swap_batch(n: u32) -> Weight {
+        (1_000 as Weight)
+            .saturating_add((1_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	fn test() -> Weight {
-		1_000 as Weight
-	}
+    fn test() -> Weight {
+        1_000 as Weight
+    }
+    fn swap() -> Weight {
+        1_000 as Weight
+    }
+    fn swap_batch(n: u32) -> Weight {
+        (1_000 as Weight).saturating_mul(n as Weight)
+    }
 }